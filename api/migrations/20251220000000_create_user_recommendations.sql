@@ -0,0 +1,19 @@
+-- personalised recommendations, precomputed by a background job
+CREATE TABLE IF NOT EXISTS user_recommendations (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    movie_id BIGINT NOT NULL,
+    -- position in the user's recommendations, starting at 1
+    rank INTEGER NOT NULL CHECK (rank >= 1),
+    score DOUBLE PRECISION NOT NULL,
+    -- which recommender produced this entry
+    source TEXT NOT NULL CHECK (source IN ('collaborative', 'content')),
+    -- looked up on TMDB when the recommendation is computed
+    title TEXT,
+    poster_path TEXT,
+    -- the movie the user liked that led to this recommendation, if any
+    reason_movie_id BIGINT,
+    reason_title TEXT,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, movie_id),
+    UNIQUE (user_id, rank)
+);
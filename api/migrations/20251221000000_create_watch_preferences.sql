@@ -0,0 +1,10 @@
+-- the region used for watch provider availability, as an ISO 3166-1 code
+ALTER TABLE users ADD COLUMN IF NOT EXISTS region TEXT;
+
+-- streaming services a user is subscribed to
+CREATE TABLE IF NOT EXISTS user_watch_providers (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider_id BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, provider_id)
+);
@@ -0,0 +1,2 @@
+-- the most restrictive certification, in the user's region, allowed in movie lists
+ALTER TABLE users ADD COLUMN IF NOT EXISTS max_certification TEXT;
@@ -0,0 +1,20 @@
+-- full text search over Cinescore's own content, kept up to date by a trigger
+ALTER TABLE users ADD COLUMN IF NOT EXISTS search_vector TSVECTOR;
+
+-- usernames are not natural language, so they are indexed with the 'simple' configuration to
+-- skip stemming and stop words
+CREATE OR REPLACE FUNCTION users_search_vector_update() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector := to_tsvector('simple', NEW.username);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS users_search_vector_update ON users;
+CREATE TRIGGER users_search_vector_update
+    BEFORE INSERT OR UPDATE OF username ON users
+    FOR EACH ROW EXECUTE FUNCTION users_search_vector_update();
+
+UPDATE users SET search_vector = to_tsvector('simple', username) WHERE search_vector IS NULL;
+
+CREATE INDEX IF NOT EXISTS users_search_vector_idx ON users USING GIN (search_vector);
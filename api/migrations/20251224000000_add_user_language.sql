@@ -0,0 +1,3 @@
+-- the language TMDB content is returned in, as an ISO 639-1 code optionally followed by an
+-- ISO 3166-1 code (e.g. `pt-BR`)
+ALTER TABLE users ADD COLUMN IF NOT EXISTS language TEXT;
@@ -0,0 +1,9 @@
+-- the poster a user has chosen to show for a movie in their own lists
+CREATE TABLE IF NOT EXISTS user_movie_posters (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    movie_id BIGINT NOT NULL,
+    poster_path TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, movie_id)
+);
@@ -0,0 +1,4 @@
+-- community trending only looks at recent activity
+CREATE INDEX IF NOT EXISTS movie_likes_created_at_idx ON movie_likes (created_at);
+CREATE INDEX IF NOT EXISTS movie_ratings_updated_at_idx ON movie_ratings (updated_at);
+CREATE INDEX IF NOT EXISTS movie_watchlist_created_at_idx ON movie_watchlist (created_at);
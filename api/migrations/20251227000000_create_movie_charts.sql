@@ -0,0 +1,41 @@
+-- snapshots of TMDB movie details in every language they have been fetched in, which the charts
+-- take release years and genres from. An empty language is TMDB's default
+CREATE TABLE IF NOT EXISTS movies (
+    movie_id BIGINT NOT NULL,
+    language TEXT NOT NULL DEFAULT '',
+    title TEXT NOT NULL,
+    overview TEXT,
+    release_date DATE,
+    poster_path TEXT,
+    runtime INTEGER NOT NULL,
+    genre_ids BIGINT[] NOT NULL DEFAULT '{}',
+    genre_names TEXT[] NOT NULL DEFAULT '{}',
+    -- NULL until the movie has been fetched along with its credits
+    directors TEXT[],
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (movie_id, language)
+);
+
+CREATE INDEX IF NOT EXISTS movies_fetched_at_idx ON movies (fetched_at);
+
+-- every rated movie's Cinescore rating, refreshed by a background job
+CREATE MATERIALIZED VIEW IF NOT EXISTS movie_charts AS
+SELECT
+    ratings.movie_id,
+    COUNT(*) AS vote_count,
+    AVG(ratings.rating)::FLOAT8 AS average_rating,
+    -- Bayesian average, which pulls movies with few ratings towards the mean of every rating so
+    -- a single 5 star rating does not top the charts
+    ((SUM(ratings.rating) + 10 * overall.mean) / (COUNT(*) + 10))::FLOAT8 AS score,
+    EXTRACT(YEAR FROM movies.release_date)::INTEGER AS release_year,
+    COALESCE(movies.genre_ids, '{}') AS genre_ids
+FROM movie_ratings ratings
+CROSS JOIN (SELECT AVG(rating) AS mean FROM movie_ratings) overall
+LEFT JOIN movies ON movies.movie_id = ratings.movie_id AND movies.language = ''
+GROUP BY ratings.movie_id, overall.mean, movies.release_date, movies.genre_ids;
+
+-- a unique index lets the view be refreshed concurrently, without blocking reads
+CREATE UNIQUE INDEX IF NOT EXISTS movie_charts_movie_id_idx ON movie_charts (movie_id);
+CREATE INDEX IF NOT EXISTS movie_charts_score_idx ON movie_charts (score DESC);
+CREATE INDEX IF NOT EXISTS movie_charts_release_year_idx ON movie_charts (release_year);
+CREATE INDEX IF NOT EXISTS movie_charts_genre_ids_idx ON movie_charts USING GIN (genre_ids);
@@ -0,0 +1,16 @@
+-- snapshots of TMDB person details in every language they have been fetched in, alongside the
+-- movie snapshots, so people can be shown without fetching each one from TMDB
+CREATE TABLE IF NOT EXISTS people (
+    person_id BIGINT NOT NULL,
+    language TEXT NOT NULL DEFAULT '',
+    name TEXT NOT NULL,
+    profile_path TEXT,
+    known_for_department TEXT NOT NULL,
+    birthday DATE,
+    deathday DATE,
+    popularity FLOAT8 NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (person_id, language)
+);
+
+CREATE INDEX IF NOT EXISTS people_fetched_at_idx ON people (fetched_at);
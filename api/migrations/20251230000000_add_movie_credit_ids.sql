@@ -0,0 +1,4 @@
+-- the TMDB IDs of each movie's directors and top-billed cast, which content-based recommendations
+-- are made from. NULL until the movie has been fetched along with its credits, like `directors`
+ALTER TABLE movies ADD COLUMN IF NOT EXISTS director_ids BIGINT[];
+ALTER TABLE movies ADD COLUMN IF NOT EXISTS cast_ids BIGINT[];
@@ -0,0 +1,55 @@
+-- Development seed data for trying out personalised recommendations locally.
+--
+-- Load into a migrated development database with:
+--   psql "$DATABASE_URL" -f seeds/recommendations.sql
+--
+-- The seeded users cannot log in, their password hashes are not valid argon2 hashes.
+-- Movie IDs are TMDB IDs.
+
+INSERT INTO users (id, username, email, password_hash) VALUES
+    ('00000000-0000-0000-0000-000000000001', 'seed_alice', 'alice@seed.invalid', '!'),
+    ('00000000-0000-0000-0000-000000000002', 'seed_bob', 'bob@seed.invalid', '!'),
+    ('00000000-0000-0000-0000-000000000003', 'seed_carol', 'carol@seed.invalid', '!'),
+    ('00000000-0000-0000-0000-000000000004', 'seed_dave', 'dave@seed.invalid', '!'),
+    ('00000000-0000-0000-0000-000000000005', 'seed_erin', 'erin@seed.invalid', '!')
+ON CONFLICT DO NOTHING;
+
+-- alice, bob and carol share a taste for science fiction, dave prefers fantasy, and erin has
+-- only rated a single movie so falls back to content-based recommendations
+INSERT INTO movie_ratings (user_id, movie_id, rating) VALUES
+    ('00000000-0000-0000-0000-000000000001', 603, 5),     -- The Matrix
+    ('00000000-0000-0000-0000-000000000001', 27205, 5),   -- Inception
+    ('00000000-0000-0000-0000-000000000001', 157336, 4),  -- Interstellar
+    ('00000000-0000-0000-0000-000000000001', 120, 2),     -- The Fellowship of the Ring
+    ('00000000-0000-0000-0000-000000000002', 603, 5),
+    ('00000000-0000-0000-0000-000000000002', 27205, 4),
+    ('00000000-0000-0000-0000-000000000002', 78, 5),      -- Blade Runner
+    ('00000000-0000-0000-0000-000000000002', 155, 4),     -- The Dark Knight
+    ('00000000-0000-0000-0000-000000000003', 27205, 5),
+    ('00000000-0000-0000-0000-000000000003', 157336, 5),
+    ('00000000-0000-0000-0000-000000000003', 78, 4),
+    ('00000000-0000-0000-0000-000000000003', 155, 5),
+    ('00000000-0000-0000-0000-000000000004', 120, 5),
+    ('00000000-0000-0000-0000-000000000004', 121, 5),     -- The Two Towers
+    ('00000000-0000-0000-0000-000000000004', 122, 4),     -- The Return of the King
+    ('00000000-0000-0000-0000-000000000004', 603, 2),
+    ('00000000-0000-0000-0000-000000000005', 603, 5)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO movie_likes (user_id, movie_id) VALUES
+    ('00000000-0000-0000-0000-000000000001', 603),
+    ('00000000-0000-0000-0000-000000000002', 78),
+    ('00000000-0000-0000-0000-000000000003', 157336),
+    ('00000000-0000-0000-0000-000000000004', 121)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO user_top_five (user_id, rank, movie_id) VALUES
+    ('00000000-0000-0000-0000-000000000001', 1, 27205),
+    ('00000000-0000-0000-0000-000000000002', 1, 603),
+    ('00000000-0000-0000-0000-000000000003', 1, 155),
+    ('00000000-0000-0000-0000-000000000004', 1, 120)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO movie_watchlist (user_id, movie_id) VALUES
+    ('00000000-0000-0000-0000-000000000001', 78)
+ON CONFLICT DO NOTHING;
@@ -32,6 +32,13 @@ pub struct User {
     username: String,
     email: String,
     password_hash: String,
+    /// The ISO 3166-1 code of the region the user watches movies in, if they have saved one.
+    pub region: Option<String>,
+    /// The most restrictive age certification in the user's region that movie lists may
+    /// include, if they have set a parental filter.
+    pub max_certification: Option<String>,
+    /// The language the user wants TMDB content in (e.g. `pt-BR`), if they have saved one.
+    pub language: Option<String>,
 }
 
 impl Debug for User {
@@ -41,6 +48,9 @@ impl Debug for User {
             .field("username", &self.username)
             .field("email", &self.email)
             .field("password_hash", &"[redacted]")
+            .field("region", &self.region)
+            .field("max_certification", &self.max_certification)
+            .field("language", &self.language)
             .finish()
     }
 }
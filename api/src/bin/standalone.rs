@@ -1,8 +1,11 @@
 //! The standalone cinescore API binary
-use std::net::Ipv4Addr;
+use std::{net::Ipv4Addr, str::FromStr};
 
-use api::build_router;
-use clap::Parser;
+use api::{
+    build_router,
+    jobs::{self, JobKind, JobRunner},
+};
+use clap::{Parser, Subcommand};
 use sqlx::postgres::PgPoolOptions;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -16,10 +19,64 @@ struct Cli {
     /// Specify a port to run the server on
     #[arg(short, long, default_value_t = 8000)]
     port: u16,
+    /// How many background jobs can run at once in this process. Set to 0 when serving the API
+    /// to leave jobs to separate workers
+    #[arg(short, long, default_value_t = 1)]
+    workers: usize,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Commands other than serving the API, which is the default
+#[derive(Subcommand)]
+enum Command {
+    /// Run background jobs without serving the API
+    Worker,
+    /// Queue a background job to run as soon as a worker is free
+    Enqueue {
+        /// The job to run
+        #[arg(value_parser = JobKind::from_str)]
+        kind: JobKind,
+    },
+}
+
+/// Completes when the process is asked to stop, by Ctrl+C or `SIGTERM`.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => (),
+        _ = terminate => (),
+    }
+
+    tracing::info!("Shutting down");
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Cli::parse();
+
     // load dotenv file if exists, and if not, check that environment variable is defined
     if dotenvy::from_filename(".env").is_err() {
         panic!(".env file does not exist");
@@ -59,9 +116,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .await
         .expect("Failed to run migrations");
 
-    let args = Cli::parse();
+    match args.command {
+        Some(Command::Worker) => {
+            let runner = JobRunner::start(pool, args.workers.max(1));
+            shutdown_signal().await;
+            runner.shutdown().await;
+            return Ok(());
+        }
+        Some(Command::Enqueue { kind }) => {
+            let id = jobs::enqueue(&pool, kind).await?;
+            tracing::info!("Queued {} job {}", kind, id);
+            return Ok(());
+        }
+        None => (),
+    }
+
     let address = format!("{}:{}", args.bind, args.port);
 
+    let runner = (args.workers > 0).then(|| JobRunner::start(pool.clone(), args.workers));
     let router = build_router(pool).await;
 
     let listener = match tokio::net::TcpListener::bind(&address).await {
@@ -74,10 +146,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     tracing::info!("Bound to http://{}", address);
 
-    match axum::serve(listener, router).await {
+    match axum::serve(listener, router)
+        .with_graceful_shutdown(shutdown_signal())
+        .await
+    {
         Ok(_) => (),
         Err(e) => tracing::error!("Error serving axum app: {}", e),
     };
 
+    if let Some(runner) = runner {
+        runner.shutdown().await;
+    }
+
     Ok(())
 }
@@ -0,0 +1,262 @@
+//! Top rated charts built from Cinescore ratings rather than TMDB's vote averages.
+//!
+//! Every rated movie's rating is kept in the `movie_charts` materialized view, which a
+//! background job ([`refresh_charts`]) refreshes on a schedule. Charts are broken down by
+//! genre and release year, which are joined in from the movie cache (see [`crate::metadata`]).
+use std::ops::RangeInclusive;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use axum_login::AuthSession;
+use serde::Deserialize;
+use sqlx::{postgres::PgArguments, prelude::FromRow, Arguments, PgPool};
+
+use crate::{
+    auth::Backend,
+    discover::{apply_parental_filter, get_tmdb_client},
+    frontend_models::movies::{FrontendMovieList, MovieListing},
+    language::PreferredLanguage,
+    metadata::load_movies,
+    pagination::{fetch_page, DatabasePage, PAGE_SIZE},
+};
+
+/// The fewest ratings a movie needs to appear in the charts.
+const MIN_VOTES: i64 = 3;
+
+/// Limits the charts to movies with at least `$1` ratings, in genre `$2` and released between
+/// years `$3` and `$4`, if given. Shared by the queries listing and counting a chart.
+const CHART_FILTER: &str = "WHERE vote_count >= $1
+    AND ($2::BIGINT IS NULL OR genre_ids @> ARRAY[$2::BIGINT])
+    AND ($3::INTEGER IS NULL OR release_year BETWEEN $3 AND $4)";
+
+/// The years charts can be fetched for, from the earliest films to well past any announced
+/// release.
+const CHART_YEARS: RangeInclusive<i32> = 1870..=2100;
+
+/// Builds the router for chart routes. These are nested under `/api/v1/charts`.
+pub fn build_router(pool: PgPool) -> Router {
+    Router::new()
+        .route("/top_rated", get(fetch_top_rated_chart))
+        .route("/genre/{id}", get(fetch_genre_chart))
+        .route("/decade/{decade}", get(fetch_decade_chart))
+        .route("/year/{year}", get(fetch_year_chart))
+        .with_state(pool)
+}
+
+/// Parameters for chart queries.
+#[derive(Deserialize)]
+pub struct ChartParams {
+    /// The page of results to return, starting from 1.
+    page: Option<u32>,
+}
+
+/// Which movies a chart is limited to.
+#[derive(Debug, Clone, Copy, Default)]
+struct ChartFilter {
+    /// Only movies in this genre.
+    genre_id: Option<i64>,
+    /// Only movies released in this range of years, inclusive.
+    years: Option<(i32, i32)>,
+}
+
+/// A movie in a chart, as returned by the database.
+#[derive(FromRow)]
+struct ChartRow {
+    /// The TMDB ID of the movie.
+    movie_id: i64,
+    /// The average rating Cinescore users have given the movie.
+    average_rating: f64,
+}
+
+/// Refreshes the charts from the latest ratings and movie snapshots.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+pub async fn refresh_charts(pool: &PgPool) -> Result<(), sqlx::Error> {
+    sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY movie_charts")
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Fetches a page of a chart, highest rated first. Each movie's overall score is its average
+/// Cinescore rating.
+///
+/// # Arguments
+///
+/// * `auth_session` - The session of the user, whose parental filter is applied.
+/// * `language` - The language to fetch each movie's details in.
+/// * `pool` - The database pool.
+/// * `page` - The page of the chart to fetch, starting from 1.
+/// * `filter` - Which movies the chart is limited to.
+async fn fetch_chart(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    pool: PgPool,
+    page: Option<u32>,
+    filter: ChartFilter,
+) -> Response {
+    let (from_year, to_year) = filter.years.unzip();
+
+    let list_query = format!(
+        "SELECT movie_id, average_rating
+        FROM movie_charts
+        {}
+        ORDER BY score DESC, vote_count DESC, movie_id",
+        CHART_FILTER
+    );
+    let count_query = format!("SELECT COUNT(*) FROM movie_charts {}", CHART_FILTER);
+    let binds = |arguments: &mut PgArguments| {
+        arguments.add(MIN_VOTES)?;
+        arguments.add(filter.genre_id)?;
+        arguments.add(from_year)?;
+        arguments.add(to_year)
+    };
+
+    let DatabasePage {
+        rows,
+        page,
+        total_results,
+    } = match fetch_page::<ChartRow, _>(&pool, &list_query, &count_query, binds, page).await {
+        Ok(page) => page,
+        Err(response) => return response,
+    };
+
+    tracing::info!("Fetching {} charted movies ({:?})", rows.len(), filter);
+
+    let client = get_tmdb_client();
+    let movie_ids: Vec<u64> = rows.iter().map(|row| row.movie_id as u64).collect();
+
+    let movies = match load_movies(&pool, &client, &movie_ids, &language).await {
+        Ok(movies) => movies,
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let movies: Vec<MovieListing> = movies
+        .into_iter()
+        .map(|movie| {
+            let average_rating = rows
+                .iter()
+                .find(|row| row.movie_id == movie.movie_id)
+                .map_or(0.0, |row| row.average_rating);
+
+            let mut listing = MovieListing::from(movie);
+            listing.set_overall_score(average_rating as f32);
+            listing
+        })
+        .collect();
+
+    let list = FrontendMovieList::new(movies, page, PAGE_SIZE, total_results);
+
+    match apply_parental_filter(&client, &auth_session, list).await {
+        Ok(list) => Json(language.localise(list)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// The response for a chart requested for a year outside [`CHART_YEARS`].
+fn invalid_year() -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        format!(
+            "Charts are only available for {} to {}",
+            CHART_YEARS.start(),
+            CHART_YEARS.end()
+        ),
+    )
+        .into_response()
+}
+
+/// Fetches the all-time top rated movies on Cinescore.
+async fn fetch_top_rated_chart(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    State(pool): State<PgPool>,
+    params: Query<ChartParams>,
+) -> Response {
+    fetch_chart(
+        auth_session,
+        language,
+        pool,
+        params.page,
+        ChartFilter::default(),
+    )
+    .await
+}
+
+/// Fetches the top rated movies on Cinescore in a genre.
+async fn fetch_genre_chart(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    State(pool): State<PgPool>,
+    Path(genre_id): Path<i64>,
+    params: Query<ChartParams>,
+) -> Response {
+    let filter = ChartFilter {
+        genre_id: Some(genre_id),
+        ..Default::default()
+    };
+
+    fetch_chart(auth_session, language, pool, params.page, filter).await
+}
+
+/// Fetches the top rated movies on Cinescore released in a decade, given by its first year
+/// (e.g., `1990`).
+async fn fetch_decade_chart(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    State(pool): State<PgPool>,
+    Path(decade): Path<i32>,
+    params: Query<ChartParams>,
+) -> Response {
+    if decade % 10 != 0 {
+        return (
+            StatusCode::BAD_REQUEST,
+            "Decades are given by their first year, e.g. 1990",
+        )
+            .into_response();
+    }
+
+    // checked before adding to the decade, so the addition cannot overflow
+    if !CHART_YEARS.contains(&decade) {
+        return invalid_year();
+    }
+    let last_year = (decade + 9).min(*CHART_YEARS.end());
+
+    let filter = ChartFilter {
+        years: Some((decade, last_year)),
+        ..Default::default()
+    };
+
+    fetch_chart(auth_session, language, pool, params.page, filter).await
+}
+
+/// Fetches the top rated movies on Cinescore released in a year.
+async fn fetch_year_chart(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    State(pool): State<PgPool>,
+    Path(year): Path<i32>,
+    params: Query<ChartParams>,
+) -> Response {
+    if !CHART_YEARS.contains(&year) {
+        return invalid_year();
+    }
+
+    let filter = ChartFilter {
+        years: Some((year, year)),
+        ..Default::default()
+    };
+
+    fetch_chart(auth_session, language, pool, params.page, filter).await
+}
@@ -0,0 +1,148 @@
+//! Search over Cinescore's own content, which TMDB knows nothing about.
+//!
+//! Searchable text is indexed into `tsvector` columns kept up to date by triggers (see the
+//! `add_community_search` migration), so searches are served from GIN indexes. Only usernames are
+//! searchable for now.
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use sqlx::{postgres::PgArguments, prelude::FromRow, Arguments, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    frontend_models::search::{
+        FrontendCommunitySearchResult, FrontendCommunitySearchResults, FrontendCommunityUserMatch,
+        FrontendHighlight, FrontendUserListing,
+    },
+    pagination::{fetch_page, DatabasePage, PAGE_SIZE},
+};
+
+/// The most words of a search that are used, so a long search cannot build a huge query.
+const MAX_SEARCH_TERMS: usize = 8;
+
+/// Marks the start of a match in snippets. Control characters are used so they can never clash
+/// with the text being searched.
+const HIGHLIGHT_START: char = '\u{2}';
+
+/// Marks the end of a match in snippets.
+const HIGHLIGHT_STOP: char = '\u{3}';
+
+/// Parameters for community search queries.
+#[derive(Deserialize)]
+pub struct CommunitySearchParams {
+    /// The text to search for.
+    query: String,
+    /// The page of results to return, starting from 1.
+    page: Option<u32>,
+}
+
+/// A user matching a community search, as returned by the database.
+#[derive(FromRow)]
+struct UserMatchRow {
+    /// The ID of the user.
+    id: Uuid,
+    /// The user's username.
+    username: String,
+    /// The username with matches wrapped in [`HIGHLIGHT_START`] and [`HIGHLIGHT_STOP`].
+    snippet: String,
+    /// How closely the user matches the search.
+    rank: f32,
+}
+
+/// Builds a prefix-matching `tsquery` from a search, so results appear while a word is still
+/// being typed. Returns `None` if the search has no words in it.
+///
+/// Only letters and digits are kept, which also means nothing in the search can be interpreted
+/// as `tsquery` syntax.
+///
+/// # Arguments
+///
+/// * `query` - The text being searched for.
+fn build_prefix_tsquery(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .take(MAX_SEARCH_TERMS)
+        .map(|term| format!("{}:*", term))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" & "))
+    }
+}
+
+/// Searches Cinescore's own content, returning ranked results with highlighted snippets.
+pub async fn search_community(
+    State(pool): State<PgPool>,
+    params: Query<CommunitySearchParams>,
+) -> impl IntoResponse {
+    let Some(tsquery) = build_prefix_tsquery(&params.query) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "The search must contain at least one letter or digit",
+        )
+            .into_response();
+    };
+
+    // the markers are control characters, so they are built from their code points in SQL
+    let list_query = format!(
+        "SELECT id, username,
+            ts_headline('simple', username, query,
+                'StartSel=' || CHR({}) || ',StopSel=' || CHR({}) || ',HighlightAll=true'
+            ) AS snippet,
+            ts_rank(search_vector, query) AS rank
+        FROM users, to_tsquery('simple', $1) AS query
+        WHERE search_vector @@ query
+        ORDER BY rank DESC, LENGTH(username), username",
+        HIGHLIGHT_START as u32, HIGHLIGHT_STOP as u32
+    );
+
+    let DatabasePage {
+        rows,
+        page,
+        total_results,
+    } = match fetch_page::<UserMatchRow, _>(
+        &pool,
+        &list_query,
+        "SELECT COUNT(*) FROM users WHERE search_vector @@ to_tsquery('simple', $1)",
+        |arguments: &mut PgArguments| arguments.add(&tsquery),
+        params.page,
+    )
+    .await
+    {
+        Ok(page) => page,
+        Err(response) => return response,
+    };
+
+    let results = rows
+        .into_iter()
+        .map(|row| {
+            FrontendCommunitySearchResult::User(FrontendCommunityUserMatch {
+                user: FrontendUserListing {
+                    id: row.id,
+                    username: row.username,
+                },
+                snippet: FrontendHighlight::split_headline(
+                    &row.snippet,
+                    HIGHLIGHT_START,
+                    HIGHLIGHT_STOP,
+                ),
+                rank: row.rank,
+            })
+        })
+        .collect();
+
+    Json(FrontendCommunitySearchResults::new(
+        results,
+        page,
+        PAGE_SIZE,
+        total_results,
+    ))
+    .into_response()
+}
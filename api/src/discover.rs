@@ -1,33 +1,64 @@
 //! Defines axum route handlers for interacting with the cinescore backend and the TMDB API
+use std::{collections::HashMap, sync::Arc};
+
 use axum::{
     extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
     Json,
 };
 use axum_login::AuthSession;
-use chrono::{Months, Utc};
+use chrono::{Days, Months, NaiveDate, Utc};
 use serde::Deserialize;
 use sqlx::PgPool;
+use tokio::{sync::Semaphore, task::JoinSet};
 
 use crate::{
     auth::Backend,
+    filters::{validate_country, DiscoverParams},
     frontend_models::{
+        collections::{CollectionInteraction, FrontendCollection},
+        companies::FrontendCompanyDetails,
+        genres::FrontendGenreList,
+        images::{FrontendMovieImages, FrontendPersonImages},
         movies::{FrontendMovieDetails, FrontendMovieList},
         people::{FrontendPeopleList, FrontendPersonDetails},
+        release_dates::FrontendCertificationList,
+        reviews::FrontendReviewList,
+        search::FrontendSearchResults,
+        watch_providers::FrontendWatchProviderList,
     },
+    language::PreferredLanguage,
+    metadata,
+    pagination::validate_page,
+    preferences::{load_parental_filter, load_watch_services},
     tmdb::{
-        client::{ApiFetchError, TMDBClient},
+        client::{ApiFetchError, TMDBClient, MAX_CONCURRENT_REQUESTS},
+        models::{movie::MovieDetails, release_dates::ReleaseType},
         queries::{
-            movie_details::MovieDetailsRequest,
+            certifications::MovieCertificationsRequest,
+            collections::CollectionDetailsRequest,
+            companies::CompanyDetailsRequest,
+            genres::MovieGenresRequest,
+            movie_details::{
+                to_frontend_details, MovieDetailsRequest, MovieImagesRequest, MovieReviewsRequest,
+            },
             movie_lists::{
-                DiscoverMoviesRequest, MovieListNowPlayingRequest, MovieListTrendingRequest,
+                DiscoverMoviesRequest, MovieListNowPlayingRequest, MovieListPopularRequest,
+                MovieListTopRatedRequest, MovieListTrendingRequest, MovieRecommendationsRequest,
+                SimilarMoviesRequest,
             },
-            people_details::{PersonDetailsRequest, TrendingPeopleRequest},
-            search::{SearchMoviesRequest, SearchPeopleRequest},
+            people_details::{PersonDetailsRequest, PersonImagesRequest, TrendingPeopleRequest},
             traits::{
                 AppendToResponseQueryParam, IdQuery, IncludeAdultQueryParam,
-                IncludeVideoQueryParam, LanguageQueryParam, PrimaryReleaseDateQueryParam, Query,
-                QueryQueryParam, SortBy, SortByQueryParam,
+                IncludeImageLanguageQueryParam, IncludeVideoLanguageQueryParam,
+                IncludeVideoQueryParam, PageQueryParam, PrimaryReleaseDateQueryParam, Query,
+                RegionQueryParam, ReleaseDateQueryParam, SortBy, SortByQueryParam, TimeWindow,
+                TimeWindowParam, WatchRegionQueryParam, WithCompaniesQueryParam,
+                WithGenresQueryParam, WithKeywordsQueryParam, WithReleaseTypeQueryParam,
             },
+            trending::TrendingAllRequest,
+            watch_providers::WatchProviderListRequest,
         },
     },
 };
@@ -36,7 +67,7 @@ use crate::{
 ///
 /// # Panics
 /// If the `TMDB_API_KEY` environment variable is not set, logs an error and exits the process.
-fn get_tmdb_client() -> TMDBClient {
+pub fn get_tmdb_client() -> TMDBClient {
     TMDBClient::new(match std::env::var("TMDB_API_KEY") {
         Ok(v) => v,
         Err(_) => {
@@ -46,27 +77,307 @@ fn get_tmdb_client() -> TMDBClient {
     })
 }
 
+/// Removes every movie from a list that the logged in user's parental filter does not allow. The
+/// list is returned unchanged if the user has not set a parental filter. A filtered list's totals
+/// are upper bounds (see
+/// [`ParentalFilter::apply`](crate::tmdb::queries::certifications::ParentalFilter::apply)).
+///
+/// # Arguments
+/// * `client` - The `TMDBClient` instance used to check certifications.
+/// * `auth_session` - The session of the user whose parental filter to apply.
+/// * `list` - The list of movies to filter.
+///
+/// # Errors
+/// Returns an [`ApiFetchError`] if the certification list cannot be fetched.
+pub(crate) async fn apply_parental_filter(
+    client: &TMDBClient,
+    auth_session: &AuthSession<Backend>,
+    list: FrontendMovieList,
+) -> Result<FrontendMovieList, ApiFetchError> {
+    match load_parental_filter(client, auth_session.user.as_ref()).await? {
+        Some(filter) => Ok(filter.apply(client, list).await),
+        None => Ok(list),
+    }
+}
+
+/// Removes every movie from search results that the logged in user's parental filter does not
+/// allow. The results are returned unchanged if the user has not set a parental filter.
+///
+/// # Arguments
+/// * `client` - The `TMDBClient` instance used to check certifications.
+/// * `auth_session` - The session of the user whose parental filter to apply.
+/// * `results` - The search results to filter.
+///
+/// # Errors
+/// Returns an [`ApiFetchError`] if the certification list cannot be fetched.
+pub(crate) async fn apply_parental_filter_to_search(
+    client: &TMDBClient,
+    auth_session: &AuthSession<Backend>,
+    mut results: FrontendSearchResults,
+) -> Result<FrontendSearchResults, ApiFetchError> {
+    if let Some(filter) = load_parental_filter(client, auth_session.user.as_ref()).await? {
+        let allowed = filter.allowed_movies(client, results.movie_ids()).await;
+        results.retain_movies(|movie_id| allowed.contains(&movie_id));
+    }
+
+    Ok(results)
+}
+
+/// Fetches the details of several movies concurrently, making at most
+/// [`MAX_CONCURRENT_REQUESTS`] requests at once. Movies that fail to fetch are left out rather
+/// than failing the whole list.
+///
+/// # Arguments
+///
+/// * `client` - The `TMDBClient` instance used to make API requests.
+/// * `movie_ids` - The IDs of the movies to fetch, in the order they should be returned.
+/// * `append` - Extra data to append to each movie's details, if any.
+/// * `language` - The language to fetch each movie's details in.
+pub async fn fetch_movie_details_batch(
+    client: &TMDBClient,
+    movie_ids: Vec<u64>,
+    append: Option<&'static str>,
+    language: &PreferredLanguage,
+) -> Vec<MovieDetails> {
+    let mut tasks = JoinSet::new();
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS));
+
+    for (position, movie_id) in movie_ids.into_iter().enumerate() {
+        let client = client.clone();
+        let request = language.apply(MovieDetailsRequest::new());
+        let permits = permits.clone();
+        tasks.spawn(async move {
+            // the semaphore is never closed, so acquiring always succeeds
+            let _permit = permits.acquire().await;
+            let request = match append {
+                Some(append) => request.append_to_response(append),
+                None => request,
+            };
+            let details = request.fetch_details(&client, movie_id).await;
+            (position, movie_id, details)
+        });
+    }
+
+    let mut movies = Vec::new();
+
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok((position, _, Ok(details))) => movies.push((position, details)),
+            Ok((_, movie_id, Err(e))) => {
+                tracing::warn!("Leaving movie {} out of list: {}", movie_id, e);
+            }
+            Err(e) => tracing::error!("Movie details task failed: {}", e),
+        }
+    }
+
+    movies.sort_by_key(|(position, _)| *position);
+    movies.into_iter().map(|(_, details)| details).collect()
+}
+
+/// Parameters for trending queries.
+#[derive(Deserialize)]
+pub struct TrendingParams {
+    /// Whether to list what is trending today or this week. Defaults to today.
+    #[serde(default)]
+    window: TimeWindow,
+    /// The page of results to fetch.
+    page: Option<u32>,
+}
+
 /// Fetches the list of trending movies from TMDB.
-pub async fn fetch_trending() -> Result<Json<FrontendMovieList>, ApiFetchError> {
+pub async fn fetch_trending(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    params: axum::extract::Query<TrendingParams>,
+) -> Result<Json<FrontendMovieList>, ApiFetchError> {
     let client = get_tmdb_client();
-    tracing::info!("Fetching trending movies");
-    Ok(Json(MovieListTrendingRequest::new().fetch(&client).await?))
+    tracing::info!("Fetching trending movies ({})", params.window);
+
+    let mut request = language
+        .apply(MovieListTrendingRequest::new())
+        .time_window(params.window);
+
+    if let Some(page) = params.page {
+        request = request.page(validate_page(page)?);
+    }
+
+    let movies = request.fetch(&client).await?;
+    Ok(Json(language.localise(
+        apply_parental_filter(&client, &auth_session, movies).await?,
+    )))
 }
 
 /// Fetches the list of trending people from TMDB.
-pub async fn fetch_trending_people() -> Result<Json<FrontendPeopleList>, ApiFetchError> {
+pub async fn fetch_trending_people(
+    language: PreferredLanguage,
+    params: axum::extract::Query<TrendingParams>,
+) -> Result<Json<FrontendPeopleList>, ApiFetchError> {
     let client = get_tmdb_client();
-    tracing::info!("Fetching trending people");
-    Ok(Json(TrendingPeopleRequest::new().fetch(&client).await?))
+    tracing::info!("Fetching trending people ({})", params.window);
+
+    let mut request = language
+        .apply(TrendingPeopleRequest::new())
+        .time_window(params.window);
+
+    if let Some(page) = params.page {
+        request = request.page(validate_page(page)?);
+    }
+
+    let people = request.fetch(&client).await?;
+    Ok(Json(language.localise(people)))
 }
 
-/// Fetches the list of movies that are currently playing in theaters.
-pub async fn fetch_now_playing() -> Result<Json<FrontendMovieList>, ApiFetchError> {
+/// Fetches trending movies and people from TMDB in a single list, ranked by how much each is
+/// trending.
+pub async fn fetch_trending_all(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    params: axum::extract::Query<TrendingParams>,
+) -> Result<Json<FrontendSearchResults>, ApiFetchError> {
     let client = get_tmdb_client();
-    tracing::info!("Fetching now playing movies");
-    Ok(Json(
-        MovieListNowPlayingRequest::new().fetch(&client).await?,
-    ))
+    tracing::info!("Fetching everything trending ({})", params.window);
+
+    let mut request = language
+        .apply(TrendingAllRequest::new())
+        .time_window(params.window);
+
+    if let Some(page) = params.page {
+        request = request.page(validate_page(page)?);
+    }
+
+    let results = request.fetch(&client).await?;
+    Ok(Json(language.localise(
+        apply_parental_filter_to_search(&client, &auth_session, results).await?,
+    )))
+}
+
+/// Resolves the region a regional list should be for, preferring an explicit query parameter
+/// over the logged in user's saved region.
+///
+/// # Arguments
+/// * `region` - The value of the `region` query parameter, if set.
+/// * `auth_session` - The session of the user whose saved region to fall back to.
+///
+/// # Errors
+/// Returns [`ApiFetchError::InvalidQueryParam`] if the query parameter is not a valid country
+/// code.
+fn resolve_region(
+    region: Option<String>,
+    auth_session: &AuthSession<Backend>,
+) -> Result<Option<String>, ApiFetchError> {
+    if let Some(region) = &region {
+        validate_country("region", region)?;
+    }
+
+    Ok(region.or_else(|| auth_session.user.as_ref().and_then(|u| u.region.clone())))
+}
+
+/// Parameters for regional movie list queries.
+#[derive(Deserialize)]
+pub struct RegionalListParams {
+    /// The ISO 3166-1 code of the region to list movies for. Defaults to the logged in user's
+    /// saved region.
+    region: Option<String>,
+    /// The page of results to fetch.
+    page: Option<u32>,
+}
+
+/// Fetches the list of movies that are currently playing in theaters, in a region if one is set
+/// or saved.
+pub async fn fetch_now_playing(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    params: axum::extract::Query<RegionalListParams>,
+) -> Result<Json<FrontendMovieList>, ApiFetchError> {
+    let client = get_tmdb_client();
+    let params = params.0;
+
+    let mut request = language.apply(MovieListNowPlayingRequest::new());
+
+    if let Some(page) = params.page {
+        request = request.page(validate_page(page)?);
+    }
+
+    if let Some(region) = resolve_region(params.region, &auth_session)? {
+        tracing::info!("Fetching now playing movies in region {}", region);
+        request = request.region(region);
+    } else {
+        tracing::info!("Fetching now playing movies");
+    }
+
+    let movies = request.fetch(&client).await?;
+    Ok(Json(language.localise(
+        apply_parental_filter(&client, &auth_session, movies).await?,
+    )))
+}
+
+/// Fetches the list of movies that are most popular on TMDB right now, in a region if one is set
+/// or saved.
+pub async fn fetch_popular(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    params: axum::extract::Query<RegionalListParams>,
+) -> Result<Json<FrontendMovieList>, ApiFetchError> {
+    let client = get_tmdb_client();
+    let params = params.0;
+
+    let mut request = language.apply(MovieListPopularRequest::new());
+
+    if let Some(page) = params.page {
+        request = request.page(validate_page(page)?);
+    }
+
+    if let Some(region) = resolve_region(params.region, &auth_session)? {
+        tracing::info!("Fetching popular movies in region {}", region);
+        request = request.region(region);
+    } else {
+        tracing::info!("Fetching popular movies");
+    }
+
+    let movies = request.fetch(&client).await?;
+    Ok(Json(language.localise(
+        apply_parental_filter(&client, &auth_session, movies).await?,
+    )))
+}
+
+/// Fetches the list of movies with the highest TMDB vote averages, in a region if one is set or
+/// saved.
+pub async fn fetch_top_rated(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    params: axum::extract::Query<RegionalListParams>,
+) -> Result<Json<FrontendMovieList>, ApiFetchError> {
+    let client = get_tmdb_client();
+    let params = params.0;
+
+    let mut request = language.apply(MovieListTopRatedRequest::new());
+
+    if let Some(page) = params.page {
+        request = request.page(validate_page(page)?);
+    }
+
+    if let Some(region) = resolve_region(params.region, &auth_session)? {
+        tracing::info!("Fetching top rated movies in region {}", region);
+        request = request.region(region);
+    } else {
+        tracing::info!("Fetching top rated movies");
+    }
+
+    let movies = request.fetch(&client).await?;
+    Ok(Json(language.localise(
+        apply_parental_filter(&client, &auth_session, movies).await?,
+    )))
+}
+
+/// Parameters for movie details queries.
+#[derive(Deserialize)]
+pub struct MovieDetailsParams {
+    /// How many recommended and similar movies to embed in the details. Related movies are not
+    /// fetched at all if this is not set.
+    related: Option<usize>,
+    /// The ISO 3166-1 code of the region to show watch providers for. Defaults to the logged in
+    /// user's saved region.
+    region: Option<String>,
 }
 
 /// Fetches detailed information about a specific movie.
@@ -76,16 +387,56 @@ pub async fn fetch_now_playing() -> Result<Json<FrontendMovieList>, ApiFetchErro
 pub async fn fetch_movie_details(
     State(pool): State<PgPool>,
     auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
     Path(movie_id): Path<u64>,
+    params: axum::extract::Query<MovieDetailsParams>,
 ) -> Result<Json<FrontendMovieDetails>, ApiFetchError> {
     let client = get_tmdb_client();
     tracing::info!("Fetching details for movie ID: {}", movie_id);
 
+    let related = params.related.map(|n| n.min(MAX_RELATED_MOVIES));
+
+    if let Some(region) = &params.region {
+        validate_country("region", region)?;
+    }
+    let region = params
+        .0
+        .region
+        .or_else(|| auth_session.user.as_ref().and_then(|u| u.region.clone()));
+
+    let mut append = vec!["credits", "videos", "keywords", "external_ids"];
+    if related.is_some() {
+        append.extend(["recommendations", "similar"]);
+    }
+    if region.is_some() {
+        append.extend(["watch/providers", "release_dates"]);
+    }
+
+    let mut request = language
+        .apply(MovieDetailsRequest::new())
+        .append_to_response(append.join(","));
+
+    // TMDB only returns videos in the requested language, so English and untagged videos are
+    // also included in case there is no localised trailer
+    if let Some(language) = &language.0 {
+        let iso_639_1 = language.split('-').next().unwrap_or(language);
+        request = request.include_video_language(format!("{},en,null", iso_639_1));
+    }
+
     // first, we fetch TMDB data
-    let mut movie_details = MovieDetailsRequest::new()
-        .append_to_response("credits")
-        .fetch(&client, movie_id)
-        .await?;
+    let response = request.fetch_details(&client, movie_id).await?;
+    if let Err(e) = metadata::save_movie(&pool, &response, language.0.as_deref()).await {
+        tracing::error!("Failed to save movie {}: {}", movie_id, e);
+    }
+    let mut movie_details = to_frontend_details(&client, response, language.0.clone()).await;
+
+    if let Some(limit) = related {
+        movie_details.truncate_related(limit);
+    }
+
+    if let Some(region) = region {
+        movie_details.select_region(&region);
+    }
 
     let user_id = auth_session.user.map(|u| u.id);
 
@@ -121,7 +472,70 @@ pub async fn fetch_movie_details(
     //
     movie_details.set_user_interaction(is_liked, in_watchlist);
 
-    Ok(Json(movie_details))
+    Ok(Json(language.localise(movie_details)))
+}
+
+/// Fetches a collection of movies, such as a franchise, with every movie in release order. If
+/// the user is logged in, their interactions with each movie and their progress through the
+/// collection are included.
+///
+/// # Arguments
+/// * `collection_id` - The ID of the collection to fetch.
+pub async fn fetch_collection(
+    State(pool): State<PgPool>,
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    Path(collection_id): Path<u64>,
+) -> Result<Json<FrontendCollection>, ApiFetchError> {
+    let client = get_tmdb_client();
+    tracing::info!("Fetching collection ID: {}", collection_id);
+
+    let mut collection = language
+        .apply(CollectionDetailsRequest::new())
+        .fetch(&client, collection_id)
+        .await?;
+
+    if let Some(user) = auth_session.user {
+        let movie_ids: Vec<i64> = collection
+            .movie_ids()
+            .into_iter()
+            .map(|id| id as i64)
+            .collect();
+
+        let rows: Vec<(i64, Option<i32>, bool, bool)> = sqlx::query_as(
+            "SELECT m.id, r.rating,
+                EXISTS(SELECT 1 FROM movie_likes WHERE user_id = $1 AND movie_id = m.id),
+                EXISTS(SELECT 1 FROM movie_watchlist WHERE user_id = $1 AND movie_id = m.id)
+            FROM UNNEST($2::BIGINT[]) AS m(id)
+            LEFT JOIN movie_ratings r ON r.user_id = $1 AND r.movie_id = m.id",
+        )
+        .bind(user.id)
+        .bind(&movie_ids)
+        .fetch_all(&pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Database error: {}", e);
+            Vec::new()
+        });
+
+        let interactions: HashMap<u64, CollectionInteraction> = rows
+            .into_iter()
+            .map(|(movie_id, rating, is_liked, in_watchlist)| {
+                (
+                    movie_id as u64,
+                    CollectionInteraction {
+                        rating,
+                        is_liked,
+                        in_watchlist,
+                    },
+                )
+            })
+            .collect();
+
+        collection.set_user_interactions(&interactions);
+    }
+
+    Ok(Json(language.localise(collection)))
 }
 
 /// Fetches detailed information about a specific person, including their credits and external IDs.
@@ -129,69 +543,527 @@ pub async fn fetch_movie_details(
 /// # Arguments
 /// * `person_id` - The ID of the person to fetch details for.
 pub async fn fetch_person_details(
+    State(pool): State<PgPool>,
+    language: PreferredLanguage,
     Path(person_id): Path<u64>,
 ) -> Result<Json<FrontendPersonDetails>, ApiFetchError> {
     let client = get_tmdb_client();
     tracing::info!("Fetching details for person ID: {}", person_id);
-    Ok(Json(
-        PersonDetailsRequest::new()
-            .append_to_response("credits,external_ids")
-            .fetch(&client, person_id)
-            .await?,
-    ))
+    let response = language
+        .apply(PersonDetailsRequest::new())
+        .append_to_response("credits,external_ids")
+        .fetch_details(&client, person_id)
+        .await?;
+    if let Err(e) = metadata::save_person(&pool, &response, language.0.as_deref()).await {
+        tracing::error!("Failed to save person {}: {}", person_id, e);
+    }
+    let person = FrontendPersonDetails::from(response);
+    Ok(Json(language.localise(person)))
+}
+
+/// Parameters for image gallery queries.
+#[derive(Deserialize)]
+pub struct ImageGalleryParams {
+    /// A comma separated list of ISO 639-1 codes of the languages to include images in. `null`
+    /// includes images without text.
+    include_image_language: Option<String>,
+}
+
+/// Works out which languages to include images in. If none are given, images in the preferred
+/// language, in English and without text are included, or every image if there is no preferred
+/// language.
+///
+/// # Arguments
+/// * `param` - The languages given in the query parameter, if any.
+/// * `language` - The language the request is being made in.
+///
+/// # Errors
+/// Returns [`ApiFetchError::InvalidQueryParam`] if any of the languages given is not a valid
+/// ISO 639-1 code or `null`.
+fn image_languages(
+    param: Option<String>,
+    language: &PreferredLanguage,
+) -> Result<Option<String>, ApiFetchError> {
+    if let Some(languages) = param {
+        let valid = languages
+            .split(',')
+            .all(|l| l == "null" || (l.len() == 2 && l.chars().all(|c| c.is_ascii_lowercase())));
+        if !valid {
+            return Err(ApiFetchError::InvalidQueryParam {
+                param: "include_image_language",
+                reason: format!("`{}` is not a list of language codes", languages),
+            });
+        }
+        return Ok(Some(languages));
+    }
+
+    Ok(language.0.as_ref().map(|language| {
+        let iso_639_1 = language.split('-').next().unwrap_or(language);
+        format!("{},en,null", iso_639_1)
+    }))
+}
+
+/// Fetches every poster, backdrop and logo of a movie.
+///
+/// # Arguments
+/// * `movie_id` - The ID of the movie to fetch images for.
+pub async fn fetch_movie_images(
+    language: PreferredLanguage,
+    Path(movie_id): Path<u64>,
+    params: axum::extract::Query<ImageGalleryParams>,
+) -> Result<Json<FrontendMovieImages>, ApiFetchError> {
+    let client = get_tmdb_client();
+    tracing::info!("Fetching images for movie ID: {}", movie_id);
+
+    let mut request = MovieImagesRequest::new();
+    if let Some(languages) = image_languages(params.0.include_image_language, &language)? {
+        request = request.include_image_language(languages);
+    }
+
+    Ok(Json(request.fetch(&client, movie_id).await?))
+}
+
+/// Fetches every profile picture of a person.
+///
+/// # Arguments
+/// * `person_id` - The ID of the person to fetch images for.
+pub async fn fetch_person_images(
+    language: PreferredLanguage,
+    Path(person_id): Path<u64>,
+    params: axum::extract::Query<ImageGalleryParams>,
+) -> Result<Json<FrontendPersonImages>, ApiFetchError> {
+    let client = get_tmdb_client();
+    tracing::info!("Fetching images for person ID: {}", person_id);
+
+    let mut request = PersonImagesRequest::new();
+    if let Some(languages) = image_languages(params.0.include_image_language, &language)? {
+        request = request.include_image_language(languages);
+    }
+
+    Ok(Json(request.fetch(&client, person_id).await?))
+}
+
+/// How far ahead upcoming movies are listed.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpcomingWindow {
+    /// Movies releasing in the next 7 days.
+    Week,
+    /// Movies releasing in the next month.
+    Month,
+    /// Movies releasing in the next 6 months.
+    #[default]
+    SixMonths,
+}
+
+impl UpcomingWindow {
+    /// Returns the last date included in the window.
+    ///
+    /// # Arguments
+    /// * `today` - The first date included in the window.
+    fn end(self, today: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            UpcomingWindow::Week => today.checked_add_days(Days::new(6)),
+            UpcomingWindow::Month => today.checked_add_months(Months::new(1)),
+            UpcomingWindow::SixMonths => today.checked_add_months(Months::new(6)),
+        }
+    }
+}
+
+/// Parameters for upcoming movie queries.
+#[derive(Deserialize)]
+pub struct UpcomingParams {
+    /// The ISO 3166-1 code of the region to list theatrical releases in. Defaults to the logged
+    /// in user's saved region.
+    region: Option<String>,
+    /// How far ahead to list movies. Defaults to 6 months.
+    window: Option<UpcomingWindow>,
+    /// The page of results to fetch.
+    page: Option<u32>,
+}
+
+/// Fetches upcoming movies from TMDB, releasing from today until the end of the requested window.
+///
+/// If a region is set or saved, movies are matched by their theatrical release dates in that
+/// region, so movies that have already come out elsewhere are still listed if they have not
+/// reached local cinemas yet. Otherwise movies are matched by their primary release date.
+pub async fn fetch_upcoming_movies(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    params: axum::extract::Query<UpcomingParams>,
+) -> Result<Json<FrontendMovieList>, ApiFetchError> {
+    let client = get_tmdb_client();
+    let params = params.0;
+
+    let today = Utc::now().date_naive();
+    let end = params.window.unwrap_or_default().end(today);
+
+    let mut request = language
+        .apply(DiscoverMoviesRequest::new())
+        .include_adult(false)
+        .include_video(false)
+        .sort_by(SortBy::PopularityDesc);
+
+    if let Some(page) = params.page {
+        request = request.page(validate_page(page)?);
+    }
+
+    if let Some(region) = resolve_region(params.region, &auth_session)? {
+        tracing::info!("Fetching upcoming movies in region {}", region);
+        request = request
+            .region(region)
+            .release_date_range(Some(today), end)
+            .with_release_type(&[ReleaseType::TheatricalLimited, ReleaseType::Theatrical]);
+    } else {
+        tracing::info!("Fetching upcoming movies");
+        request = request.primary_release_date_range(Some(today), end);
+    }
+
+    if let Some(filter) = load_parental_filter(&client, auth_session.user.as_ref()).await? {
+        request = filter.restrict(request);
+    }
+
+    Ok(Json(language.localise(request.fetch(&client).await?)))
 }
 
-/// Fetches upcoming movies from TMDB. This includes anything releasing tomorrow and up to 6
-/// months in the future.
-pub async fn fetch_upcoming_movies() -> Result<Json<FrontendMovieList>, ApiFetchError> {
+/// Parameters for watch provider list queries.
+#[derive(Deserialize)]
+pub struct WatchProviderParams {
+    /// The ISO 3166-1 code of the region to list watch providers for.
+    region: Option<String>,
+}
+
+/// Fetches the watch providers that offer movies, optionally limited to a single region.
+pub async fn fetch_watch_providers(
+    language: PreferredLanguage,
+    params: axum::extract::Query<WatchProviderParams>,
+) -> Result<Json<FrontendWatchProviderList>, ApiFetchError> {
     let client = get_tmdb_client();
-    tracing::info!("Fetching upcoming movies");
+    tracing::info!("Fetching movie watch providers");
+
+    let mut request = language.apply(WatchProviderListRequest::new());
+
+    if let Some(region) = params.0.region {
+        validate_country("region", &region)?;
+        request = request.watch_region(region);
+    }
+
+    Ok(Json(request.fetch(&client).await?))
+}
+
+/// Parameters for certification list queries.
+#[derive(Deserialize)]
+pub struct CertificationParams {
+    /// The ISO 3166-1 code of the region to list certifications for. Defaults to the logged in
+    /// user's saved region.
+    region: Option<String>,
+}
+
+/// Fetches the age certifications used for movies in a region, from least to most restrictive.
+pub async fn fetch_certifications(
+    auth_session: AuthSession<Backend>,
+    params: axum::extract::Query<CertificationParams>,
+) -> Result<Json<FrontendCertificationList>, ApiFetchError> {
+    let client = get_tmdb_client();
+
+    let Some(region) = params
+        .0
+        .region
+        .or_else(|| auth_session.user.and_then(|u| u.region))
+    else {
+        return Err(ApiFetchError::InvalidQueryParam {
+            param: "region",
+            reason: "required when no region has been saved".to_owned(),
+        });
+    };
+    validate_country("region", &region)?;
+
+    tracing::info!("Fetching movie certifications for region {}", region);
+
+    let mut certifications = MovieCertificationsRequest::new()
+        .fetch_certification_map(&client)
+        .await?;
+    let certifications = certifications.remove(&region).unwrap_or_default();
+
+    Ok(Json(FrontendCertificationList::new(region, certifications)))
+}
+
+/// Fetches the list of official movie genres from TMDB.
+pub async fn fetch_genres(
+    language: PreferredLanguage,
+) -> Result<Json<FrontendGenreList>, ApiFetchError> {
+    let client = get_tmdb_client();
+    tracing::info!("Fetching movie genres");
     Ok(Json(
-        DiscoverMoviesRequest::new()
-            .primary_release_date_range(
-                Some(Utc::now().date_naive()),
-                Utc::now().date_naive().checked_add_months(Months::new(6)),
-            )
-            .include_adult(false)
-            .include_video(false)
-            .language("en-US")
-            .sort_by(SortBy::PopularityDesc)
+        language
+            .apply(MovieGenresRequest::new())
             .fetch(&client)
             .await?,
     ))
 }
 
-/// Parameters for search queries.
+/// The most related movies that can be embedded in a movie's details. This is the size of a
+/// single page of TMDB results.
+const MAX_RELATED_MOVIES: usize = 20;
+
+/// Parameters for paginated queries.
 #[derive(Deserialize)]
-pub struct SearchParams {
-    /// The search query string.
-    query: String,
+pub struct PageParams {
+    /// The page of results to fetch.
+    page: Option<u32>,
 }
 
-/// Searches for movies matching the given query string.
-pub async fn search_movies(
-    params: axum::extract::Query<SearchParams>,
+/// Fetches a page of the most popular movies matching a filter from TMDB's discover endpoint,
+/// leaving out adult movies and videos, and applying the logged in user's parental filter.
+///
+/// # Arguments
+/// * `auth_session` - The session of the user, whose parental filter is applied.
+/// * `language` - The language to fetch the movies in.
+/// * `page` - The page of results to fetch.
+/// * `add_filter` - Adds the filter the movies must match to the request.
+///
+/// # Errors
+/// Returns an [`ApiFetchError`] if the page is out of range, or fetching the movies or the
+/// parental filter fails.
+async fn discover_popular_movies(
+    auth_session: &AuthSession<Backend>,
+    language: &PreferredLanguage,
+    page: Option<u32>,
+    add_filter: impl FnOnce(DiscoverMoviesRequest) -> DiscoverMoviesRequest,
 ) -> Result<Json<FrontendMovieList>, ApiFetchError> {
     let client = get_tmdb_client();
 
+    let mut request = add_filter(language.apply(DiscoverMoviesRequest::new()))
+        .include_adult(false)
+        .include_video(false)
+        .sort_by(SortBy::PopularityDesc);
+
+    if let Some(page) = page {
+        request = request.page(validate_page(page)?);
+    }
+
+    if let Some(filter) = load_parental_filter(&client, auth_session.user.as_ref()).await? {
+        request = filter.restrict(request);
+    }
+
+    Ok(Json(language.localise(request.fetch(&client).await?)))
+}
+
+/// Fetches the most popular movies in a specific genre.
+///
+/// # Arguments
+/// * `genre_id` - The ID of the genre to fetch movies for.
+pub async fn fetch_movies_by_genre(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    Path(genre_id): Path<u64>,
+    params: axum::extract::Query<PageParams>,
+) -> Result<Json<FrontendMovieList>, ApiFetchError> {
+    tracing::info!("Fetching movies for genre ID: {}", genre_id);
+
+    discover_popular_movies(&auth_session, &language, params.page, |request| {
+        request.with_genres(&[genre_id], true)
+    })
+    .await
+}
+
+/// Fetches details about a specific production company.
+///
+/// # Arguments
+/// * `company_id` - The ID of the company to fetch details for.
+pub async fn fetch_company_details(
+    Path(company_id): Path<u64>,
+) -> Result<Json<FrontendCompanyDetails>, ApiFetchError> {
+    let client = get_tmdb_client();
+    tracing::info!("Fetching details for company ID: {}", company_id);
     Ok(Json(
-        SearchMoviesRequest::new()
-            .query(params.query.clone())
-            .fetch(&client)
+        CompanyDetailsRequest::new()
+            .fetch(&client, company_id)
             .await?,
     ))
 }
 
-/// Searches for people matching the given query string.
-pub async fn search_people(
-    params: axum::extract::Query<SearchParams>,
-) -> Result<Json<FrontendPeopleList>, ApiFetchError> {
+/// Fetches the most popular movies made by a specific production company.
+///
+/// # Arguments
+/// * `company_id` - The ID of the company to fetch movies for.
+pub async fn fetch_company_movies(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    Path(company_id): Path<u64>,
+    params: axum::extract::Query<PageParams>,
+) -> Result<Json<FrontendMovieList>, ApiFetchError> {
+    tracing::info!("Fetching movies for company ID: {}", company_id);
+
+    discover_popular_movies(&auth_session, &language, params.page, |request| {
+        request.with_companies(&[company_id], true)
+    })
+    .await
+}
+
+/// Fetches the most popular movies tagged with a specific keyword.
+///
+/// # Arguments
+/// * `keyword_id` - The ID of the keyword to fetch movies for.
+pub async fn fetch_keyword_movies(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    Path(keyword_id): Path<u64>,
+    params: axum::extract::Query<PageParams>,
+) -> Result<Json<FrontendMovieList>, ApiFetchError> {
+    tracing::info!("Fetching movies for keyword ID: {}", keyword_id);
+
+    discover_popular_movies(&auth_session, &language, params.page, |request| {
+        request.with_keywords(&[keyword_id], true)
+    })
+    .await
+}
+
+/// Fetches movies TMDB recommends based on a specific movie.
+///
+/// # Arguments
+/// * `movie_id` - The ID of the movie to fetch recommendations for.
+pub async fn fetch_movie_recommendations(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    Path(movie_id): Path<u64>,
+    params: axum::extract::Query<PageParams>,
+) -> Result<Json<FrontendMovieList>, ApiFetchError> {
+    let client = get_tmdb_client();
+    tracing::info!("Fetching recommendations for movie ID: {}", movie_id);
+
+    let mut request = language.apply(MovieRecommendationsRequest::new());
+
+    if let Some(page) = params.page {
+        request = request.page(validate_page(page)?);
+    }
+
+    let movies = request.fetch(&client, movie_id).await?;
+    Ok(Json(language.localise(
+        apply_parental_filter(&client, &auth_session, movies).await?,
+    )))
+}
+
+/// Fetches movies similar to a specific movie.
+///
+/// # Arguments
+/// * `movie_id` - The ID of the movie to fetch similar movies for.
+pub async fn fetch_similar_movies(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    Path(movie_id): Path<u64>,
+    params: axum::extract::Query<PageParams>,
+) -> Result<Json<FrontendMovieList>, ApiFetchError> {
     let client = get_tmdb_client();
+    tracing::info!("Fetching similar movies for movie ID: {}", movie_id);
+
+    let mut request = language.apply(SimilarMoviesRequest::new());
+
+    if let Some(page) = params.page {
+        request = request.page(validate_page(page)?);
+    }
+
+    let movies = request.fetch(&client, movie_id).await?;
+    Ok(Json(language.localise(
+        apply_parental_filter(&client, &auth_session, movies).await?,
+    )))
+}
+
+/// Fetches reviews of a movie written by TMDB users, oldest first, as TMDB pages through them in
+/// that order. These are external reviews, so new movies have some discussion before the
+/// Cinescore community has reviewed them. Reviews are not filtered by language, as TMDB only has
+/// reviews in a few languages.
+///
+/// # Arguments
+/// * `movie_id` - The ID of the movie to fetch reviews for.
+pub async fn fetch_movie_reviews(
+    Path(movie_id): Path<u64>,
+    params: axum::extract::Query<PageParams>,
+) -> Result<Json<FrontendReviewList>, ApiFetchError> {
+    let client = get_tmdb_client();
+    tracing::info!("Fetching reviews for movie ID: {}", movie_id);
+
+    let mut request = MovieReviewsRequest::new();
+
+    if let Some(page) = params.page {
+        request = request.page(validate_page(page)?);
+    }
+
+    Ok(Json(request.fetch(&client, movie_id).await?))
+}
+
+/// Discovers movies using any combination of TMDB's discover filters. Parameters are
+/// validated before being forwarded to TMDB.
+pub async fn discover_movies(
+    State(pool): State<PgPool>,
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    params: axum::extract::Query<DiscoverParams>,
+) -> Result<Json<FrontendMovieList>, Response> {
+    let client = get_tmdb_client();
+    tracing::info!("Fetching discover movies");
+
+    let services = if params.on_my_services.unwrap_or(false) {
+        let Some(user) = &auth_session.user else {
+            return Err(StatusCode::UNAUTHORIZED.into_response());
+        };
+
+        match load_watch_services(&pool, user).await {
+            Ok(Some(services)) => Some(services),
+            Ok(None) => {
+                return Err(ApiFetchError::InvalidQueryParam {
+                    param: "on_my_services",
+                    reason: "a region and at least one watch provider must be saved first"
+                        .to_owned(),
+                }
+                .into_response());
+            }
+            Err(e) => {
+                tracing::error!("Database error: {}", e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut params = params.0;
+    let language = language.or_explicit(params.language.take());
+    params.language = language.0.clone();
+
+    let filter = load_parental_filter(&client, auth_session.user.as_ref())
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    // the parental filter can only restrict certifications in its own country
+    if let Some(filter) = &filter
+        && let Some(country) = &params.certification_country
+        && country != filter.country()
+    {
+        return Err(ApiFetchError::InvalidQueryParam {
+            param: "certification_country",
+            reason: format!(
+                "must be `{}` while the parental filter is on",
+                filter.country()
+            ),
+        }
+        .into_response());
+    }
+
+    let mut request = params
+        .into_request(services)
+        .map_err(IntoResponse::into_response)?;
+
+    if let Some(filter) = filter {
+        request = filter.restrict(request);
+    }
 
     Ok(Json(
-        SearchPeopleRequest::new()
-            .query(params.query.clone())
-            .fetch(&client)
-            .await?,
+        language.localise(
+            request
+                .fetch(&client)
+                .await
+                .map_err(IntoResponse::into_response)?,
+        ),
     ))
 }
@@ -0,0 +1,372 @@
+//! Parsing and validation of query parameters, including the filters of the discover endpoint,
+//! which are checked before anything is forwarded to TMDB.
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::{
+    pagination::validate_page,
+    preferences::WatchServices,
+    tmdb::{
+        client::ApiFetchError,
+        queries::{
+            movie_lists::DiscoverMoviesRequest,
+            traits::{
+                CertificationQueryParam, IncludeAdultQueryParam, IncludeVideoQueryParam,
+                LanguageQueryParam, PageQueryParam, PrimaryReleaseDateQueryParam,
+                PrimaryReleaseYearQueryParam, RegionQueryParam, RuntimeQueryParam, SortBy,
+                SortByQueryParam, VoteAverageQueryParam, VoteCountQueryParam,
+                WatchMonetizationTypesQueryParam, WatchProvidersQueryParam, WithCastQueryParam,
+                WithCompaniesQueryParam, WithCrewQueryParam, WithGenresQueryParam,
+                WithKeywordsQueryParam, WithOriginalLanguageQueryParam, WithoutGenresQueryParam,
+                YearQueryParam,
+            },
+        },
+    },
+};
+
+/// Parameters for the discover endpoint. Parameter names mirror the ones used by TMDB's own
+/// discover endpoint. Lists of IDs are separated by `,` (match all) or `|` (match any).
+#[derive(Deserialize)]
+pub struct DiscoverParams {
+    /// The page of results to fetch.
+    page: Option<u32>,
+    /// The language to return results in (e.g., `en-US`).
+    pub language: Option<String>,
+    /// The ISO 3166-1 country code to use for release dates.
+    region: Option<String>,
+    /// The order to return results in (e.g., `popularity.desc`).
+    sort_by: Option<String>,
+    /// Whether to include adult movies.
+    include_adult: Option<bool>,
+    /// Only include movies released in this year.
+    year: Option<u16>,
+    /// Only include movies whose primary release was in this year.
+    primary_release_year: Option<u16>,
+    /// Only include movies whose primary release was on or after this date.
+    #[serde(rename = "primary_release_date.gte")]
+    primary_release_date_gte: Option<NaiveDate>,
+    /// Only include movies whose primary release was on or before this date.
+    #[serde(rename = "primary_release_date.lte")]
+    primary_release_date_lte: Option<NaiveDate>,
+    /// Only include movies with these genre IDs.
+    with_genres: Option<String>,
+    /// Exclude movies with any of these genre IDs, separated by `,`.
+    without_genres: Option<String>,
+    /// Only include movies featuring these people as cast members.
+    with_cast: Option<String>,
+    /// Only include movies with these people as crew members.
+    with_crew: Option<String>,
+    /// Only include movies made by these production companies.
+    with_companies: Option<String>,
+    /// Only include movies tagged with these keyword IDs.
+    with_keywords: Option<String>,
+    /// Only include movies with an average score of at least this.
+    #[serde(rename = "vote_average.gte")]
+    vote_average_gte: Option<f32>,
+    /// Only include movies with an average score of at most this.
+    #[serde(rename = "vote_average.lte")]
+    vote_average_lte: Option<f32>,
+    /// Only include movies with at least this many votes.
+    #[serde(rename = "vote_count.gte")]
+    vote_count_gte: Option<u32>,
+    /// Only include movies at least this many minutes long.
+    #[serde(rename = "with_runtime.gte")]
+    runtime_gte: Option<u32>,
+    /// Only include movies at most this many minutes long.
+    #[serde(rename = "with_runtime.lte")]
+    runtime_lte: Option<u32>,
+    /// Only include movies originally made in this ISO 639-1 language.
+    with_original_language: Option<String>,
+    /// Only include movies available on these watch provider IDs. Requires `watch_region`.
+    with_watch_providers: Option<String>,
+    /// The ISO 3166-1 country code to check watch provider availability in. Requires
+    /// `with_watch_providers`.
+    watch_region: Option<String>,
+    /// Only include movies with this certification. Requires `certification_country`.
+    certification: Option<String>,
+    /// The ISO 3166-1 country code the certification belongs to. Must be the parental filter's
+    /// country if the logged in user has one.
+    pub certification_country: Option<String>,
+    /// Only include movies available to stream on the logged in user's saved watch providers,
+    /// in their saved region. Cannot be combined with `with_watch_providers`.
+    pub on_my_services: Option<bool>,
+}
+
+/// Parses a list of IDs separated by either `,` (match all) or `|` (match any).
+///
+/// # Arguments
+/// * `param` - The name of the query parameter being parsed, used in errors.
+/// * `value` - The raw value of the query parameter.
+///
+/// # Errors
+/// Returns [`ApiFetchError::InvalidQueryParam`] if the separators are mixed or an ID is not a
+/// number.
+fn parse_id_list(param: &'static str, value: &str) -> Result<(Vec<u64>, bool), ApiFetchError> {
+    let match_all = !value.contains('|');
+
+    if !match_all && value.contains(',') {
+        return Err(ApiFetchError::InvalidQueryParam {
+            param,
+            reason: "cannot mix `,` and `|` separators".to_owned(),
+        });
+    }
+
+    let separator = if match_all { ',' } else { '|' };
+    let ids = value
+        .split(separator)
+        .map(|id| {
+            id.trim()
+                .parse::<u64>()
+                .map_err(|_| ApiFetchError::InvalidQueryParam {
+                    param,
+                    reason: format!("`{}` is not a valid ID", id),
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((ids, match_all))
+}
+
+/// Checks that a value is a language code in the form TMDB expects, either `xx` or `xx-XX`.
+///
+/// # Arguments
+/// * `param` - The name of the query parameter being checked, used in errors.
+/// * `value` - The raw value of the query parameter.
+///
+/// # Errors
+/// Returns [`ApiFetchError::InvalidQueryParam`] if the value is not a valid language code.
+pub fn validate_language(param: &'static str, value: &str) -> Result<(), ApiFetchError> {
+    let (language, country) = match value.split_once('-') {
+        Some((language, country)) => (language, Some(country)),
+        None => (value, None),
+    };
+
+    let valid_language = language.len() == 2 && language.chars().all(|c| c.is_ascii_lowercase());
+
+    if valid_language && country.is_none_or(|c| validate_country(param, c).is_ok()) {
+        Ok(())
+    } else {
+        Err(ApiFetchError::InvalidQueryParam {
+            param,
+            reason: format!("`{}` is not a valid language code", value),
+        })
+    }
+}
+
+/// Checks that a value is an ISO 3166-1 country code, such as `US`.
+///
+/// # Arguments
+/// * `param` - The name of the query parameter being checked, used in errors.
+/// * `value` - The raw value of the query parameter.
+///
+/// # Errors
+/// Returns [`ApiFetchError::InvalidQueryParam`] if the value is not a valid country code.
+pub fn validate_country(param: &'static str, value: &str) -> Result<(), ApiFetchError> {
+    if value.len() == 2 && value.chars().all(|c| c.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(ApiFetchError::InvalidQueryParam {
+            param,
+            reason: format!("`{}` is not a valid country code", value),
+        })
+    }
+}
+
+/// Checks that the lower bound of a range is not greater than the upper bound.
+///
+/// # Arguments
+/// * `param` - The name of the query parameter being checked, used in errors.
+/// * `gte` - The optional lower bound.
+/// * `lte` - The optional upper bound.
+///
+/// # Errors
+/// Returns [`ApiFetchError::InvalidQueryParam`] if both bounds are set and `gte` is greater
+/// than `lte`.
+fn validate_range<T: PartialOrd>(
+    param: &'static str,
+    gte: Option<T>,
+    lte: Option<T>,
+) -> Result<(), ApiFetchError> {
+    match (gte, lte) {
+        (Some(gte), Some(lte)) if gte > lte => Err(ApiFetchError::InvalidQueryParam {
+            param,
+            reason: "lower bound is greater than upper bound".to_owned(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+impl DiscoverParams {
+    /// Validates the parameters and builds a [`DiscoverMoviesRequest`] from them.
+    ///
+    /// # Arguments
+    /// * `services` - The logged in user's streaming services, used when `on_my_services` is
+    ///   set.
+    ///
+    /// # Errors
+    /// Returns [`ApiFetchError::InvalidQueryParam`] if any parameter fails validation.
+    pub fn into_request(
+        self,
+        services: Option<WatchServices>,
+    ) -> Result<DiscoverMoviesRequest, ApiFetchError> {
+        let mut request = DiscoverMoviesRequest::new()
+            .include_adult(self.include_adult.unwrap_or(false))
+            .include_video(false);
+
+        if let Some(page) = self.page {
+            request = request.page(validate_page(page)?);
+        }
+
+        if let Some(language) = self.language {
+            validate_language("language", &language)?;
+            request = request.language(language);
+        }
+
+        if let Some(region) = self.region {
+            validate_country("region", &region)?;
+            request = request.region(region);
+        }
+
+        let sort_by =
+            match self.sort_by {
+                Some(sort_by) => sort_by.parse::<SortBy>().map_err(|reason| {
+                    ApiFetchError::InvalidQueryParam {
+                        param: "sort_by",
+                        reason,
+                    }
+                })?,
+                None => SortBy::PopularityDesc,
+            };
+        request = request.sort_by(sort_by);
+
+        if let Some(year) = self.year {
+            request = request.year(year);
+        }
+
+        if let Some(year) = self.primary_release_year {
+            request = request.primary_release_year(year.to_string());
+        }
+
+        validate_range(
+            "primary_release_date",
+            self.primary_release_date_gte,
+            self.primary_release_date_lte,
+        )?;
+        request = request.primary_release_date_range(
+            self.primary_release_date_gte,
+            self.primary_release_date_lte,
+        );
+
+        if let Some(genres) = self.with_genres {
+            let (ids, match_all) = parse_id_list("with_genres", &genres)?;
+            request = request.with_genres(&ids, match_all);
+        }
+
+        if let Some(genres) = self.without_genres {
+            let (ids, match_all) = parse_id_list("without_genres", &genres)?;
+            if !match_all {
+                return Err(ApiFetchError::InvalidQueryParam {
+                    param: "without_genres",
+                    reason: "must be separated by `,`".to_owned(),
+                });
+            }
+            request = request.without_genres(&ids);
+        }
+
+        if let Some(cast) = self.with_cast {
+            let (ids, match_all) = parse_id_list("with_cast", &cast)?;
+            request = request.with_cast(&ids, match_all);
+        }
+
+        if let Some(crew) = self.with_crew {
+            let (ids, match_all) = parse_id_list("with_crew", &crew)?;
+            request = request.with_crew(&ids, match_all);
+        }
+
+        if let Some(companies) = self.with_companies {
+            let (ids, match_all) = parse_id_list("with_companies", &companies)?;
+            request = request.with_companies(&ids, match_all);
+        }
+
+        if let Some(keywords) = self.with_keywords {
+            let (ids, match_all) = parse_id_list("with_keywords", &keywords)?;
+            request = request.with_keywords(&ids, match_all);
+        }
+
+        for (param, score) in [
+            ("vote_average.gte", self.vote_average_gte),
+            ("vote_average.lte", self.vote_average_lte),
+        ] {
+            if score.is_some_and(|score| !(0.0..=10.0).contains(&score)) {
+                return Err(ApiFetchError::InvalidQueryParam {
+                    param,
+                    reason: "must be between 0 and 10".to_owned(),
+                });
+            }
+        }
+        validate_range("vote_average", self.vote_average_gte, self.vote_average_lte)?;
+        request = request.vote_average_range(self.vote_average_gte, self.vote_average_lte);
+
+        if let Some(count) = self.vote_count_gte {
+            request = request.vote_count_gte(count);
+        }
+
+        validate_range("with_runtime", self.runtime_gte, self.runtime_lte)?;
+        request = request.runtime_range(self.runtime_gte, self.runtime_lte);
+
+        if let Some(language) = self.with_original_language {
+            validate_language("with_original_language", &language)?;
+            request = request.with_original_language(language);
+        }
+
+        if let Some(services) = services {
+            if self.with_watch_providers.is_some() {
+                return Err(ApiFetchError::InvalidQueryParam {
+                    param: "on_my_services",
+                    reason: "cannot be combined with `with_watch_providers`".to_owned(),
+                });
+            }
+
+            request = request
+                .with_watch_providers(&services.provider_ids, false, services.region)
+                .with_watch_monetization_types(&["flatrate", "free", "ads"]);
+        }
+
+        match (self.with_watch_providers, self.watch_region) {
+            (Some(providers), Some(region)) => {
+                let (ids, match_all) = parse_id_list("with_watch_providers", &providers)?;
+                validate_country("watch_region", &region)?;
+                request = request.with_watch_providers(&ids, match_all, region);
+            }
+            (Some(_), None) => {
+                return Err(ApiFetchError::InvalidQueryParam {
+                    param: "watch_region",
+                    reason: "required when `with_watch_providers` is set".to_owned(),
+                });
+            }
+            (None, Some(_)) => {
+                return Err(ApiFetchError::InvalidQueryParam {
+                    param: "with_watch_providers",
+                    reason: "required when `watch_region` is set".to_owned(),
+                });
+            }
+            (None, None) => (),
+        }
+
+        match (self.certification, self.certification_country) {
+            (Some(certification), Some(country)) => {
+                validate_country("certification_country", &country)?;
+                request = request.certification(certification, country);
+            }
+            (Some(_), None) => {
+                return Err(ApiFetchError::InvalidQueryParam {
+                    param: "certification_country",
+                    reason: "required when `certification` is set".to_owned(),
+                });
+            }
+            _ => (),
+        }
+
+        Ok(request)
+    }
+}
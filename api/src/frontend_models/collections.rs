@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::tmdb::models::movie::{Collection, CollectionDetails};
+
+use super::{
+    genres::ResolveGenres,
+    images::ImageKind,
+    localisation::{Fallback, Localise},
+    movies::MovieListing,
+};
+
+/// Represents a link from a movie to the collection it belongs to, formatted for the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrontendCollectionSummary {
+    /// Unique identifier for the collection.
+    id: u64,
+    /// The name of the collection.
+    name: String,
+    /// URL to the collection's poster image.
+    poster: String,
+    /// `srcset` of every width the collection's poster comes in, if there is one.
+    #[serde(rename = "posterSrcset")]
+    poster_srcset: Option<String>,
+    /// URL to the collection's backdrop image.
+    backdrop: String,
+    /// `srcset` of every width the collection's backdrop comes in, if there is one.
+    #[serde(rename = "backdropSrcset")]
+    backdrop_srcset: Option<String>,
+}
+
+/// Represents a collection of movies, such as a franchise, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendCollection {
+    /// Unique identifier for the collection.
+    id: u64,
+    /// The name of the collection.
+    name: String,
+    /// A brief description or overview of the collection.
+    overview: String,
+    /// URL to the collection's poster image.
+    poster: String,
+    /// `srcset` of every width the collection's poster comes in, if there is one.
+    #[serde(rename = "posterSrcset")]
+    poster_srcset: Option<String>,
+    /// URL to the collection's backdrop image.
+    backdrop: String,
+    /// `srcset` of every width the collection's backdrop comes in, if there is one.
+    #[serde(rename = "backdropSrcset")]
+    backdrop_srcset: Option<String>,
+    /// The movies in the collection, in release order. Unreleased movies without a release date
+    /// come last.
+    parts: Vec<FrontendCollectionPart>,
+    /// How many of the movies in the collection the user has watched.
+    progress: FrontendCollectionProgress,
+}
+
+/// Represents a single movie in a collection, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendCollectionPart {
+    /// The movie itself.
+    #[serde(flatten)]
+    movie: MovieListing,
+    /// Whether the user has watched the movie.
+    watched: bool,
+    /// The user's rating of the movie, if they have rated it.
+    rating: Option<i32>,
+}
+
+/// Represents how much of a collection a user has watched, formatted for the frontend.
+#[derive(Debug, Default, Serialize)]
+pub struct FrontendCollectionProgress {
+    /// How many of the movies the user has watched.
+    watched: usize,
+    /// How many movies are in the collection.
+    total: usize,
+}
+
+/// A user's interactions with a single movie in a collection.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CollectionInteraction {
+    /// The user's rating of the movie, if they have rated it.
+    pub rating: Option<i32>,
+    /// Whether the user has liked the movie.
+    pub is_liked: bool,
+    /// Whether the movie is in the user's watchlist.
+    pub in_watchlist: bool,
+}
+
+impl CollectionInteraction {
+    /// Whether the interactions mean the user has watched the movie. Rating or liking a movie
+    /// counts as having watched it, while adding it to the watchlist does not.
+    fn watched(&self) -> bool {
+        self.rating.is_some() || self.is_liked
+    }
+}
+
+impl From<Collection> for FrontendCollectionSummary {
+    /// Converts a [`Collection`] into a [`FrontendCollectionSummary`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`Collection`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendCollectionSummary`] instance with all fields mapped from the source.
+    fn from(value: Collection) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            poster: ImageKind::Poster.url(value.poster_path.as_deref()),
+            poster_srcset: ImageKind::Poster.srcset(value.poster_path.as_deref()),
+            backdrop: ImageKind::Backdrop.url(value.backdrop_path.as_deref()),
+            backdrop_srcset: ImageKind::Backdrop.srcset(value.backdrop_path.as_deref()),
+        }
+    }
+}
+
+impl From<CollectionDetails> for FrontendCollection {
+    /// Converts a [`CollectionDetails`] into a [`FrontendCollection`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`CollectionDetails`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendCollection`] instance with all fields mapped from the source.
+    fn from(value: CollectionDetails) -> Self {
+        let mut parts = value.parts;
+        parts.sort_by_key(|part| (part.base.release_date.is_none(), part.base.release_date));
+
+        Self {
+            id: value.base.id,
+            name: value.base.name,
+            overview: value
+                .overview
+                .unwrap_or_else(|| Fallback::NoOverview.to_owned_text()),
+            poster: ImageKind::Poster.url(value.base.poster_path.as_deref()),
+            poster_srcset: ImageKind::Poster.srcset(value.base.poster_path.as_deref()),
+            backdrop: ImageKind::Backdrop.url(value.base.backdrop_path.as_deref()),
+            backdrop_srcset: ImageKind::Backdrop.srcset(value.base.backdrop_path.as_deref()),
+            progress: FrontendCollectionProgress {
+                watched: 0,
+                total: parts.len(),
+            },
+            parts: parts
+                .into_iter()
+                .map(|part| FrontendCollectionPart {
+                    movie: MovieListing::from(part),
+                    watched: false,
+                    rating: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ResolveGenres for FrontendCollection {
+    fn resolve_genres(&mut self, genres: &HashMap<u64, String>) {
+        for part in &mut self.parts {
+            part.movie.resolve_genres(genres);
+        }
+    }
+}
+
+impl Localise for FrontendCollection {
+    fn localise(&mut self, language: &str) {
+        Fallback::NoOverview.localise(&mut self.overview, language);
+
+        for part in &mut self.parts {
+            part.movie.localise(language);
+        }
+    }
+}
+
+impl FrontendCollection {
+    /// The IDs of the movies in the collection.
+    pub fn movie_ids(&self) -> Vec<u64> {
+        self.parts.iter().map(|part| part.movie.id()).collect()
+    }
+
+    /// Sets the user's interactions with each movie in the collection and updates their
+    /// progress through it.
+    ///
+    /// # Arguments
+    ///
+    /// * `interactions` - The user's interactions, keyed by movie ID. Movies without an entry
+    ///   have not been interacted with.
+    pub fn set_user_interactions(&mut self, interactions: &HashMap<u64, CollectionInteraction>) {
+        for part in &mut self.parts {
+            let interaction = interactions
+                .get(&part.movie.id())
+                .copied()
+                .unwrap_or_default();
+
+            part.movie
+                .set_user_interaction(interaction.is_liked, interaction.in_watchlist);
+            part.watched = interaction.watched();
+            part.rating = interaction.rating;
+        }
+
+        self.progress.watched = self.parts.iter().filter(|part| part.watched).count();
+    }
+}
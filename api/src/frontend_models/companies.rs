@@ -0,0 +1,144 @@
+use serde::Serialize;
+
+use crate::tmdb::models::{
+    common::{ProductionCompany, ProductionCountry},
+    company::{CompanyDetails, ParentCompany},
+};
+
+use super::{common::non_empty, images::ImageKind};
+
+/// Represents a production company, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendCompany {
+    /// Unique identifier for the company.
+    id: u64,
+    /// Name of the company.
+    name: String,
+    /// URL to the company's logo.
+    logo: String,
+    /// `srcset` of every width the company's logo comes in, if there is one.
+    #[serde(rename = "logoSrcset")]
+    logo_srcset: Option<String>,
+    /// The ISO 3166-1 code of the company's country of origin, if known.
+    #[serde(rename = "originCountry")]
+    origin_country: Option<String>,
+}
+
+/// Represents a country involved in producing a movie, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendProductionCountry {
+    /// ISO 3166-1 country code.
+    code: String,
+    /// Name of the country.
+    name: String,
+}
+
+/// Represents detailed information about a production company, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendCompanyDetails {
+    /// Unique identifier for the company.
+    id: u64,
+    /// Name of the company.
+    name: String,
+    /// Description of the company, if any.
+    description: Option<String>,
+    /// Where the company is headquartered, if known.
+    headquarters: Option<String>,
+    /// Homepage URL of the company, if any.
+    homepage: Option<String>,
+    /// URL to the company's logo.
+    logo: String,
+    /// `srcset` of every width the company's logo comes in, if there is one.
+    #[serde(rename = "logoSrcset")]
+    logo_srcset: Option<String>,
+    /// The ISO 3166-1 code of the company's country of origin, if known.
+    #[serde(rename = "originCountry")]
+    origin_country: Option<String>,
+    /// The company that owns this company, if any.
+    parent: Option<FrontendCompany>,
+}
+
+impl From<ProductionCompany> for FrontendCompany {
+    /// Converts a [`ProductionCompany`] into a [`FrontendCompany`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`ProductionCompany`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendCompany`] instance with all fields mapped from the source.
+    fn from(value: ProductionCompany) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            logo: ImageKind::Logo.url(value.logo_path.as_deref()),
+            logo_srcset: ImageKind::Logo.srcset(value.logo_path.as_deref()),
+            origin_country: non_empty(value.origin_country),
+        }
+    }
+}
+
+impl From<ParentCompany> for FrontendCompany {
+    /// Converts a [`ParentCompany`] into a [`FrontendCompany`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`ParentCompany`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendCompany`] instance with all fields mapped from the source.
+    fn from(value: ParentCompany) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            logo: ImageKind::Logo.url(value.logo_path.as_deref()),
+            logo_srcset: ImageKind::Logo.srcset(value.logo_path.as_deref()),
+            origin_country: None,
+        }
+    }
+}
+
+impl From<ProductionCountry> for FrontendProductionCountry {
+    /// Converts a [`ProductionCountry`] into a [`FrontendProductionCountry`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`ProductionCountry`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendProductionCountry`] instance with all fields mapped from the source.
+    fn from(value: ProductionCountry) -> Self {
+        Self {
+            code: value.iso_3166_1,
+            name: value.name,
+        }
+    }
+}
+
+impl From<CompanyDetails> for FrontendCompanyDetails {
+    /// Converts a [`CompanyDetails`] into a [`FrontendCompanyDetails`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`CompanyDetails`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendCompanyDetails`] instance with all fields mapped from the source.
+    fn from(value: CompanyDetails) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            description: non_empty(value.description),
+            headquarters: non_empty(value.headquarters),
+            homepage: non_empty(value.homepage),
+            logo: ImageKind::Logo.url(value.logo_path.as_deref()),
+            logo_srcset: ImageKind::Logo.srcset(value.logo_path.as_deref()),
+            origin_country: non_empty(value.origin_country),
+            parent: value.parent_company.map(FrontendCompany::from),
+        }
+    }
+}
@@ -1,32 +1,108 @@
+use std::{cmp::Reverse, collections::HashSet};
+
+use chrono::{Datelike, NaiveDate};
 use serde::Serialize;
 
 use crate::tmdb::models::{
-    movie::{MovieCreditCast, MovieCreditCrew, MovieCredits},
+    movie::{BaseMovie, MovieCreditCast, MovieCreditCrew, MovieCredits},
     person::{PersonCreditCast, PersonCreditCrew, PersonCredits},
 };
 
-use super::common::get_image_url;
+use super::images::ImageKind;
+
+/// How many credits are picked for a person's "known for" list.
+const KNOWN_FOR_LIMIT: usize = 8;
 
 /// Container for movie credits
 #[derive(Debug, Serialize)]
 pub struct FrontendMovieCredits {
-    /// A list of cast members in a movie's credits
+    /// A list of cast members in a movie's credits, in billing order
     cast: Vec<FrontendMovieCastMember>,
-    /// A list of crew members in a movie's credits
+    /// A list of crew members in a movie's credits. Each person is listed once, with every job
+    /// they held.
     crew: Vec<FrontendMovieCrewMember>,
+    /// The people in the key crew roles.
+    #[serde(rename = "keyRoles")]
+    key_roles: FrontendKeyRoles,
+}
+
+/// The people in the crew roles most often shown alongside a movie.
+#[derive(Debug, Default, Serialize)]
+pub struct FrontendKeyRoles {
+    /// The movie's director(s).
+    directors: Vec<FrontendMovieCrewMember>,
+    /// The people who wrote the movie or the work it is based on.
+    writers: Vec<FrontendMovieCrewMember>,
+    /// The composer(s) of the movie's score.
+    composers: Vec<FrontendMovieCrewMember>,
+    /// The movie's cinematographer(s).
+    cinematographers: Vec<FrontendMovieCrewMember>,
+}
+
+/// A job a crew member held on a movie.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrontendCrewJob {
+    /// Department the job is in (e.g., Directing, Writing).
+    department: String,
+    /// The job (e.g., Director, Screenplay).
+    job: String,
 }
 
-/// Container for person credits (filmography)
+/// Container for person credits (filmography). Credits are sorted newest first, with unreleased
+/// movies that have no release date yet at the top.
 #[derive(Debug, Serialize)]
 pub struct FrontendPersonCredits {
     /// A list of cast credits a person has.
     cast: Vec<FrontendPersonCastCredit>,
-    /// A list of crew credits a person has.
-    crew: Vec<FrontendPersonCrewCredit>,
+    /// A person's crew credits, grouped by department. Departments with the most credits come
+    /// first.
+    crew: Vec<FrontendDepartmentCredits>,
 }
 
-/// Common fields shared between all crew member types
+/// The crew credits a person has in one department.
+#[derive(Debug, Serialize)]
+pub struct FrontendDepartmentCredits {
+    /// The department (e.g., Directing, Writing).
+    department: String,
+    /// The person's credits in the department.
+    credits: Vec<FrontendPersonCrewCredit>,
+}
+
+/// Details of a movie in a person's filmography, shared between cast and crew credits.
 #[derive(Debug, Serialize)]
+pub struct CreditMovie {
+    /// Title of the movie
+    pub title: String,
+    /// Release date of the movie, if known.
+    #[serde(rename = "releaseDate")]
+    pub release_date: Option<NaiveDate>,
+    /// Year the movie was released, if known.
+    pub year: Option<i32>,
+    /// Average TMDB vote score of the movie.
+    #[serde(rename = "voteAverage")]
+    pub vote_average: f64,
+    /// URL to the movie's poster
+    #[serde(rename = "posterUrl")]
+    pub poster_url: String,
+    /// `srcset` of every width the movie's poster comes in, if there is one.
+    #[serde(rename = "posterSrcset")]
+    pub poster_srcset: Option<String>,
+}
+
+/// A movie a person is known for, along with the role they had in it.
+#[derive(Debug, Serialize)]
+pub struct FrontendKnownForCredit {
+    /// Unique identifier for the movie
+    id: u64,
+    /// The character played, or the job held, by the person.
+    role: String,
+    /// Details of the movie.
+    #[serde(flatten)]
+    movie: CreditMovie,
+}
+
+/// Common fields shared between all crew member types
+#[derive(Debug, Clone, Serialize)]
 pub struct CrewBase {
     /// Unique identifier for the crew member
     pub id: u64,
@@ -44,16 +120,21 @@ pub struct CastBase {
 }
 
 /// Represents a crew member in the context of a movie's credits
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FrontendMovieCrewMember {
-    /// Basic crew member details.
+    /// Basic crew member details. The department is the one of the crew member's first job.
     #[serde(flatten)]
     pub base: CrewBase,
+    /// Every job the crew member held on the movie.
+    pub jobs: Vec<FrontendCrewJob>,
     /// Name of the crew member
     pub name: String,
     /// URL to the crew member's profile picture
     #[serde(rename = "iconUrl")]
     pub icon_url: String,
+    /// `srcset` of every width the crew member's profile picture comes in, if there is one.
+    #[serde(rename = "iconSrcset")]
+    pub icon_srcset: Option<String>,
 }
 
 /// Represents a cast member in the context of a movie's credits
@@ -62,11 +143,16 @@ pub struct FrontendMovieCastMember {
     /// Basic cast member details.
     #[serde(flatten)]
     pub base: CastBase,
+    /// Position of the cast member in the billing order, starting at 0
+    pub order: u64,
     /// Name of the cast member
     pub name: String,
     /// URL to the cast member's profile picture
     #[serde(rename = "iconUrl")]
     pub icon_url: String,
+    /// `srcset` of every width the cast member's profile picture comes in, if there is one.
+    #[serde(rename = "iconSrcset")]
+    pub icon_srcset: Option<String>,
 }
 
 /// Represents a movie credit in a person's filmography (as crew)
@@ -75,11 +161,11 @@ pub struct FrontendPersonCrewCredit {
     /// Basic crew member details.
     #[serde(flatten)]
     pub base: CrewBase,
-    /// Title of the movie
-    pub title: String,
-    /// URL to the movie's poster
-    #[serde(rename = "posterUrl")]
-    pub poster_url: String,
+    /// The job the person held (e.g., Director, Screenplay).
+    pub job: String,
+    /// Details of the movie.
+    #[serde(flatten)]
+    pub movie: CreditMovie,
 }
 
 /// Represents a movie credit in a person's filmography (as cast)
@@ -88,31 +174,106 @@ pub struct FrontendPersonCastCredit {
     /// Basic cast member details.
     #[serde(flatten)]
     pub base: CastBase,
-    /// Title of the movie
-    pub title: String,
-    /// URL to the movie's poster
-    #[serde(rename = "posterUrl")]
-    pub poster_url: String,
+    /// Details of the movie.
+    #[serde(flatten)]
+    pub movie: CreditMovie,
 }
 
-impl From<MovieCreditCrew> for FrontendMovieCrewMember {
-    /// Converts a [`MovieCreditCrew`] into a [`FrontendMovieCrewMember`].
+impl From<&BaseMovie> for CreditMovie {
+    /// Converts a [`BaseMovie`] into a [`CreditMovie`].
     ///
     /// # Arguments
     ///
-    /// * `value` - The source [`MovieCreditCrew`] to convert from
+    /// * `value` - The source [`BaseMovie`] to convert from
     ///
     /// # Returns
     ///
-    /// A new [`FrontendMovieCrewMember`] instance with all fields mapped from the source.
-    fn from(value: MovieCreditCrew) -> Self {
+    /// A new [`CreditMovie`] instance with all fields mapped from the source.
+    fn from(value: &BaseMovie) -> Self {
         Self {
-            base: CrewBase {
-                id: value.base.id,
-                department: value.base.known_for_department.unwrap_or("N/A".to_owned()),
-            },
-            name: value.base.name,
-            icon_url: get_image_url(value.base.profile_path),
+            title: value.title.clone(),
+            release_date: value.release_date,
+            year: value.release_date.map(|date| date.year()),
+            vote_average: value.vote_average,
+            poster_url: ImageKind::Poster.url(value.poster_path.as_deref()),
+            poster_srcset: ImageKind::Poster.srcset(value.poster_path.as_deref()),
+        }
+    }
+}
+
+impl FrontendMovieCrewMember {
+    /// Converts a movie's crew credits into [`FrontendMovieCrewMember`]s. TMDB lists a person
+    /// once for every job they held, so these are merged into one member per person, in the
+    /// order each person first appears.
+    ///
+    /// # Arguments
+    ///
+    /// * `crew` - The source [`MovieCreditCrew`]s to convert from
+    fn from_credits(crew: Vec<MovieCreditCrew>) -> Vec<Self> {
+        let mut members: Vec<Self> = Vec::new();
+
+        for credit in crew {
+            let job = FrontendCrewJob {
+                department: credit.department,
+                job: credit.job,
+            };
+
+            match members.iter_mut().find(|m| m.base.id == credit.base.id) {
+                Some(member) => member.jobs.push(job),
+                None => members.push(Self {
+                    base: CrewBase {
+                        id: credit.base.id,
+                        department: job.department.clone(),
+                    },
+                    jobs: vec![job],
+                    name: credit.base.name,
+                    icon_url: ImageKind::Profile.url(credit.base.profile_path.as_deref()),
+                    icon_srcset: ImageKind::Profile.srcset(credit.base.profile_path.as_deref()),
+                }),
+            }
+        }
+
+        members
+    }
+
+    /// Whether the crew member held any of the given jobs.
+    ///
+    /// # Arguments
+    ///
+    /// * `jobs` - The jobs to look for
+    fn has_job(&self, jobs: &[&str]) -> bool {
+        self.jobs.iter().any(|job| jobs.contains(&job.job.as_str()))
+    }
+}
+
+impl FrontendKeyRoles {
+    /// Jobs that make someone a director.
+    const DIRECTOR_JOBS: &[&str] = &["Director"];
+    /// Jobs that make someone a writer, including of the work the movie is based on.
+    const WRITER_JOBS: &[&str] = &["Screenplay", "Writer", "Story", "Novel", "Author"];
+    /// Jobs that make someone a composer.
+    const COMPOSER_JOBS: &[&str] = &["Original Music Composer", "Music", "Composer"];
+    /// Jobs that make someone a cinematographer.
+    const CINEMATOGRAPHER_JOBS: &[&str] = &["Director of Photography", "Cinematography"];
+
+    /// Picks out the people in key roles from a movie's merged crew.
+    ///
+    /// # Arguments
+    ///
+    /// * `crew` - The movie's crew, with one member per person
+    fn from_crew(crew: &[FrontendMovieCrewMember]) -> Self {
+        let with_job = |jobs: &[&str]| {
+            crew.iter()
+                .filter(|member| member.has_job(jobs))
+                .cloned()
+                .collect()
+        };
+
+        Self {
+            directors: with_job(Self::DIRECTOR_JOBS),
+            writers: with_job(Self::WRITER_JOBS),
+            composers: with_job(Self::COMPOSER_JOBS),
+            cinematographers: with_job(Self::CINEMATOGRAPHER_JOBS),
         }
     }
 }
@@ -133,8 +294,10 @@ impl From<MovieCreditCast> for FrontendMovieCastMember {
                 id: value.base.id,
                 character: value.character,
             },
+            order: value.order,
             name: value.base.name,
-            icon_url: get_image_url(value.base.profile_path),
+            icon_url: ImageKind::Profile.url(value.base.profile_path.as_deref()),
+            icon_srcset: ImageKind::Profile.srcset(value.base.profile_path.as_deref()),
         }
     }
 }
@@ -151,12 +314,12 @@ impl From<PersonCreditCrew> for FrontendPersonCrewCredit {
     /// A new [`FrontendPersonCrewCredit`] instance with all fields mapped from the source.
     fn from(value: PersonCreditCrew) -> Self {
         Self {
+            movie: CreditMovie::from(&value.base),
             base: CrewBase {
                 id: value.base.id,
                 department: value.department,
             },
-            title: value.base.title,
-            poster_url: get_image_url(value.base.poster_path),
+            job: value.job,
         }
     }
 }
@@ -173,12 +336,11 @@ impl From<PersonCreditCast> for FrontendPersonCastCredit {
     /// A new [`FrontendPersonCastCredit`] instance with all fields mapped from the source.
     fn from(value: PersonCreditCast) -> Self {
         Self {
+            movie: CreditMovie::from(&value.base),
             base: CastBase {
                 id: value.base.id,
                 character: value.character,
             },
-            title: value.base.title,
-            poster_url: get_image_url(value.base.poster_path),
         }
     }
 }
@@ -193,19 +355,23 @@ impl From<MovieCredits> for FrontendMovieCredits {
     ///
     /// # Returns
     ///
-    /// A new [`FrontendMovieCredits`] instance with all fields mapped from the source.
+    /// A new [`FrontendMovieCredits`] instance with the cast in billing order, the crew merged
+    /// by person and the key roles picked out.
     fn from(value: MovieCredits) -> Self {
+        let mut cast: Vec<FrontendMovieCastMember> = value
+            .cast
+            .into_iter()
+            .map(FrontendMovieCastMember::from)
+            .collect();
+        cast.sort_by_key(|member| member.order);
+
+        let crew = FrontendMovieCrewMember::from_credits(value.crew);
+        let key_roles = FrontendKeyRoles::from_crew(&crew);
+
         Self {
-            cast: value
-                .cast
-                .into_iter()
-                .map(FrontendMovieCastMember::from)
-                .collect(),
-            crew: value
-                .crew
-                .into_iter()
-                .map(FrontendMovieCrewMember::from)
-                .collect(),
+            cast,
+            crew,
+            key_roles,
         }
     }
 }
@@ -219,19 +385,96 @@ impl From<PersonCredits> for FrontendPersonCredits {
     ///
     /// # Returns
     ///
-    /// A new [`FrontendPersonCredits`] instance with all fields mapped from the source.
+    /// A new [`FrontendPersonCredits`] instance with cast credits sorted and crew credits
+    /// sorted and grouped by department.
     fn from(value: PersonCredits) -> Self {
-        Self {
-            cast: value
-                .cast
-                .into_iter()
-                .map(FrontendPersonCastCredit::from)
-                .collect(),
-            crew: value
-                .crew
-                .into_iter()
-                .map(FrontendPersonCrewCredit::from)
-                .collect(),
+        let mut cast: Vec<FrontendPersonCastCredit> = value
+            .cast
+            .into_iter()
+            .map(FrontendPersonCastCredit::from)
+            .collect();
+        cast.sort_by_key(|credit| newest_first(&credit.movie));
+
+        let mut crew: Vec<FrontendDepartmentCredits> = Vec::new();
+        for credit in value.crew.into_iter().map(FrontendPersonCrewCredit::from) {
+            match crew
+                .iter_mut()
+                .find(|group| group.department == credit.base.department)
+            {
+                Some(group) => group.credits.push(credit),
+                None => crew.push(FrontendDepartmentCredits {
+                    department: credit.base.department.clone(),
+                    credits: vec![credit],
+                }),
+            }
+        }
+
+        for group in &mut crew {
+            group
+                .credits
+                .sort_by_key(|credit| newest_first(&credit.movie));
         }
+        crew.sort_by_key(|group| Reverse(group.credits.len()));
+
+        Self { cast, crew }
     }
 }
+
+/// Sort key that puts the newest movies first, with movies that have no release date yet
+/// before all of them.
+///
+/// # Arguments
+///
+/// * `movie` - The movie to build the sort key for
+fn newest_first(movie: &CreditMovie) -> Reverse<NaiveDate> {
+    Reverse(movie.release_date.unwrap_or(NaiveDate::MAX))
+}
+
+/// Scores how well known a movie is, from its popularity and how many votes it has. Vote count
+/// is dampened so that a huge back catalogue of votes does not drown out current popularity.
+///
+/// # Arguments
+///
+/// * `movie` - The movie to score
+fn known_for_score(movie: &BaseMovie) -> f64 {
+    movie.popularity * (1.0 + movie.vote_count as f64).log10()
+}
+
+/// Picks the movies a person is best known for, from their credits in the department they are
+/// known for. Actors are judged on their cast credits, everyone else on their crew credits in
+/// that department. Each movie is only listed once.
+///
+/// # Arguments
+///
+/// * `credits` - The person's credits
+/// * `department` - The department the person is known for (e.g., Acting, Directing)
+pub fn known_for(credits: &PersonCredits, department: &str) -> Vec<FrontendKnownForCredit> {
+    let mut candidates: Vec<(&BaseMovie, &str)> = if department == "Acting" {
+        credits
+            .cast
+            .iter()
+            .map(|credit| (&credit.base, credit.character.as_str()))
+            .collect()
+    } else {
+        credits
+            .crew
+            .iter()
+            .filter(|credit| credit.department == department)
+            .map(|credit| (&credit.base, credit.job.as_str()))
+            .collect()
+    };
+
+    candidates.sort_by(|(a, _), (b, _)| known_for_score(b).total_cmp(&known_for_score(a)));
+
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|(movie, _)| seen.insert(movie.id))
+        .take(KNOWN_FOR_LIMIT)
+        .map(|(movie, role)| FrontendKnownForCredit {
+            id: movie.id,
+            role: role.to_owned(),
+            movie: CreditMovie::from(movie),
+        })
+        .collect()
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::tmdb::models::common::{Genre, GenreList};
+
+/// Represents a single genre formatted for the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrontendGenre {
+    /// Unique identifier for the genre.
+    pub id: u64,
+    /// Name of the genre.
+    pub name: String,
+}
+
+/// Represents the list of all movie genres formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendGenreList {
+    /// A list of genres
+    genres: Vec<FrontendGenre>,
+}
+
+/// Implemented by frontend models containing movies that only know their genre IDs, so that the
+/// IDs can be resolved into genres once the genre list has been fetched.
+pub trait ResolveGenres {
+    /// Resolves genre IDs into genres. IDs that are not present in `genres` are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `genres` - A map of genre ID to genre name
+    fn resolve_genres(&mut self, genres: &HashMap<u64, String>);
+}
+
+impl From<Genre> for FrontendGenre {
+    /// Converts a [`Genre`] into a [`FrontendGenre`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`Genre`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendGenre`] instance with all fields mapped from the source.
+    fn from(value: Genre) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+        }
+    }
+}
+
+impl From<GenreList> for FrontendGenreList {
+    /// Converts a [`GenreList`] into a [`FrontendGenreList`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`GenreList`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendGenreList`] instance with all fields mapped from the source.
+    fn from(value: GenreList) -> Self {
+        Self {
+            genres: value.genres.into_iter().map(FrontendGenre::from).collect(),
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+
+use crate::tmdb::{
+    models::{
+        configuration::ImageConfiguration,
+        image::{Image, MovieImages, PersonImages},
+    },
+    queries::configuration::image_configuration,
+};
+
+/// The path images are proxied through, followed by the kind, size and TMDB path of the image.
+const PROXY_PATH: &str = "/api/v1/images";
+
+/// The path placeholder images are served from, followed by the kind of image.
+const PLACEHOLDER_PATH: &str = "/api/v1/images/placeholders";
+
+/// The kinds of image TMDB serves. Each kind is available in its own set of sizes and has its own
+/// placeholder for when an image is missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageKind {
+    /// A movie or collection poster, in portrait.
+    Poster,
+    /// A wide movie or collection backdrop.
+    Backdrop,
+    /// A photo of a person, in portrait.
+    Profile,
+    /// A company or watch provider logo.
+    Logo,
+}
+
+impl ImageKind {
+    /// The name of the kind, as used in proxy and placeholder URLs.
+    pub fn name(self) -> &'static str {
+        match self {
+            ImageKind::Poster => "poster",
+            ImageKind::Backdrop => "backdrop",
+            ImageKind::Profile => "profile",
+            ImageKind::Logo => "logo",
+        }
+    }
+
+    /// The width images of this kind are shown at when no size is asked for. This suits list
+    /// views, with [`ImageKind::srcset`] providing larger sizes for high density screens.
+    fn default_width(self) -> u32 {
+        match self {
+            ImageKind::Poster => 342,
+            ImageKind::Backdrop => 1280,
+            ImageKind::Profile => 185,
+            ImageKind::Logo => 154,
+        }
+    }
+
+    /// The sizes images of this kind are available in, according to the image configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The current image configuration
+    fn sizes(self, configuration: &ImageConfiguration) -> &[String] {
+        match self {
+            ImageKind::Poster => &configuration.poster_sizes,
+            ImageKind::Backdrop => &configuration.backdrop_sizes,
+            ImageKind::Profile => &configuration.profile_sizes,
+            ImageKind::Logo => &configuration.logo_sizes,
+        }
+    }
+
+    /// Whether images of this kind are available in a size, according to the current image
+    /// configuration. `original` is always available.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size to check (e.g., `w342`)
+    pub fn has_size(self, size: &str) -> bool {
+        size == "original" || self.sizes(&image_configuration()).iter().any(|s| s == size)
+    }
+
+    /// Builds the URL an image is proxied through in a given size.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size of the image (e.g., `w342`)
+    /// * `path` - The path of the image returned by TMDB, which starts with a `/`
+    fn proxy_url(self, size: &str, path: &str) -> String {
+        format!("{}/{}/{}{}", PROXY_PATH, self.name(), size, path)
+    }
+
+    /// The URL of the placeholder shown in place of a missing image of this kind.
+    pub fn placeholder_url(self) -> String {
+        format!("{}/{}", PLACEHOLDER_PATH, self.name())
+    }
+
+    /// Builds the URL of an image at the default width for its kind, or the placeholder URL if
+    /// there is no image.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the image returned by TMDB
+    pub fn url(self, path: Option<&str>) -> String {
+        self.sized_url(path, self.default_width())
+    }
+
+    /// Builds the URL of an image in the smallest size at least `width` pixels wide, or the
+    /// original size if none are wide enough. The placeholder URL is returned if there is no
+    /// image.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the image returned by TMDB
+    /// * `width` - The width the image will be shown at, in pixels
+    pub fn sized_url(self, path: Option<&str>, width: u32) -> String {
+        let Some(path) = path else {
+            return self.placeholder_url();
+        };
+
+        let configuration = image_configuration();
+        let size = widths(self.sizes(&configuration))
+            .filter(|(_, size_width)| *size_width >= width)
+            .min_by_key(|(_, size_width)| *size_width)
+            .map_or("original", |(size, _)| size);
+
+        self.proxy_url(size, path)
+    }
+
+    /// Builds a `srcset` listing every width an image is available in (e.g.,
+    /// `/api/v1/images/poster/w92/abc.jpg 92w, ...`), so browsers can pick the smallest one
+    /// that suits the screen. Returns `None` if there is no image, as placeholders only come in
+    /// one size.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the image returned by TMDB
+    pub fn srcset(self, path: Option<&str>) -> Option<String> {
+        let path = path?;
+        let configuration = image_configuration();
+
+        let srcset = widths(self.sizes(&configuration))
+            .map(|(size, width)| format!("{} {}w", self.proxy_url(size, path), width))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Some(srcset)
+    }
+}
+
+/// Picks out the sizes that are widths (e.g., `w342`), along with the width in pixels. Heights
+/// (e.g., `h632`) and `original` are skipped, as their width is not known up front.
+///
+/// # Arguments
+///
+/// * `sizes` - The sizes from the image configuration
+fn widths(sizes: &[String]) -> impl Iterator<Item = (&str, u32)> {
+    sizes.iter().filter_map(|size| {
+        size.strip_prefix('w')
+            .and_then(|width| width.parse().ok())
+            .map(|width| (size.as_str(), width))
+    })
+}
+
+/// Represents one image in a gallery, formatted for the frontend.
+#[allow(clippy::doc_markdown)]
+#[derive(Debug, Serialize)]
+pub struct FrontendImage {
+    /// The TMDB path of the image, which identifies it when choosing a preferred poster.
+    path: String,
+    /// URL of the image at the default width for its kind.
+    url: String,
+    /// Every width the image is available in, for the `srcset` attribute.
+    srcset: Option<String>,
+    /// The width of the original image, in pixels.
+    width: u32,
+    /// The height of the original image, in pixels.
+    height: u32,
+    /// The width of the image divided by its height.
+    #[serde(rename = "aspectRatio")]
+    aspect_ratio: f64,
+    /// ISO 639-1 code of the language of any text in the image. `None` for images without text.
+    language: Option<String>,
+    /// Average vote score.
+    #[serde(rename = "voteAverage")]
+    vote_average: f64,
+    /// Total number of votes received.
+    #[serde(rename = "voteCount")]
+    vote_count: u64,
+}
+
+impl FrontendImage {
+    /// Converts an [`Image`] into a [`FrontendImage`] of the given kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of image, which decides the sizes its URLs are built for
+    /// * `value` - The source [`Image`] to convert from
+    fn from_image(kind: ImageKind, value: Image) -> Self {
+        Self {
+            url: kind.url(Some(&value.file_path)),
+            srcset: kind.srcset(Some(&value.file_path)),
+            path: value.file_path,
+            width: value.width,
+            height: value.height,
+            aspect_ratio: value.aspect_ratio,
+            language: value.iso_639_1,
+            vote_average: value.vote_average,
+            vote_count: value.vote_count,
+        }
+    }
+
+    /// Converts every [`Image`] in a list into a [`FrontendImage`] of the given kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the images
+    /// * `images` - The source images to convert from
+    fn from_images(kind: ImageKind, images: Vec<Image>) -> Vec<Self> {
+        images
+            .into_iter()
+            .map(|image| Self::from_image(kind, image))
+            .collect()
+    }
+}
+
+/// Represents the gallery of a movie's images, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendMovieImages {
+    /// The movie ID.
+    id: u64,
+    /// The movie's posters.
+    posters: Vec<FrontendImage>,
+    /// The movie's backdrops.
+    backdrops: Vec<FrontendImage>,
+    /// The movie's logos.
+    logos: Vec<FrontendImage>,
+}
+
+/// Converts [`MovieImages`] into a [`FrontendMovieImages`] for frontend representation.
+impl From<MovieImages> for FrontendMovieImages {
+    /// Converts [`MovieImages`] into a [`FrontendMovieImages`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`MovieImages`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendMovieImages`] instance with every image's URLs built for its kind.
+    fn from(value: MovieImages) -> Self {
+        Self {
+            id: value.id,
+            posters: FrontendImage::from_images(ImageKind::Poster, value.posters),
+            backdrops: FrontendImage::from_images(ImageKind::Backdrop, value.backdrops),
+            logos: FrontendImage::from_images(ImageKind::Logo, value.logos),
+        }
+    }
+}
+
+/// Represents the gallery of a person's images, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendPersonImages {
+    /// The person ID.
+    id: u64,
+    /// The person's profile pictures.
+    profiles: Vec<FrontendImage>,
+}
+
+/// Converts [`PersonImages`] into a [`FrontendPersonImages`] for frontend representation.
+impl From<PersonImages> for FrontendPersonImages {
+    /// Converts [`PersonImages`] into a [`FrontendPersonImages`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`PersonImages`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendPersonImages`] instance with every image's URLs built for its kind.
+    fn from(value: PersonImages) -> Self {
+        Self {
+            id: value.id,
+            profiles: FrontendImage::from_images(ImageKind::Profile, value.profiles),
+        }
+    }
+}
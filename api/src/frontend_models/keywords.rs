@@ -0,0 +1,69 @@
+use serde::Serialize;
+
+use crate::tmdb::models::{common::PaginatedSearchResult, keyword::Keyword};
+
+/// Represents a keyword used to tag movies, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendKeyword {
+    /// Unique identifier for the keyword.
+    id: u64,
+    /// The keyword itself.
+    name: String,
+}
+
+/// Represents a list of keywords formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendKeywordList {
+    /// A list of keywords
+    keywords: Vec<FrontendKeyword>,
+    /// The current page of results.
+    page: u64,
+    /// The total number of pages available.
+    #[serde(rename = "totalPages")]
+    total_pages: u64,
+    /// The total number of results available.
+    #[serde(rename = "totalResults")]
+    total_results: u64,
+}
+
+impl From<Keyword> for FrontendKeyword {
+    /// Converts a [`Keyword`] into a [`FrontendKeyword`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`Keyword`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendKeyword`] instance with all fields mapped from the source.
+    fn from(value: Keyword) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+        }
+    }
+}
+
+impl From<PaginatedSearchResult<Keyword>> for FrontendKeywordList {
+    /// Converts a [`PaginatedSearchResult<Keyword>`] into a [`FrontendKeywordList`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`PaginatedSearchResult<Keyword>`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendKeywordList`] instance with all fields mapped from the source.
+    fn from(value: PaginatedSearchResult<Keyword>) -> Self {
+        Self {
+            keywords: value
+                .results
+                .into_iter()
+                .map(FrontendKeyword::from)
+                .collect(),
+            page: value.page,
+            total_pages: value.total_pages,
+            total_results: value.total_results,
+        }
+    }
+}
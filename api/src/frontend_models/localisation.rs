@@ -0,0 +1,73 @@
+/// Text shown in place of details TMDB does not have for a movie or person.
+///
+/// Conversions from TMDB models always write fallback text in English, so that it can be
+/// recognised and swapped for the text in another language by [`Localise`] once the language of
+/// the request is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fallback {
+    /// A movie or collection has no overview.
+    NoOverview,
+    /// A short detail, such as a department or place of birth, is unknown.
+    NotAvailable,
+}
+
+/// Implemented by frontend models containing fallback text, so that it can be shown in the
+/// language content was requested in.
+pub trait Localise {
+    /// Replaces any fallback text with the text in the given language. Languages without
+    /// translations are left in English.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The language content was requested in (e.g., `fr` or `fr-CA`)
+    fn localise(&mut self, language: &str);
+}
+
+impl Fallback {
+    /// Returns the fallback text in the given language, or in English if there is no
+    /// translation for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The language to return the text in (e.g., `fr` or `fr-CA`), if any
+    pub fn text(self, language: Option<&str>) -> &'static str {
+        // regional variants share a translation
+        let language = language
+            .and_then(|language| language.split('-').next())
+            .unwrap_or("en");
+
+        match (self, language) {
+            (Fallback::NoOverview, "de") => "Keine Beschreibung verfügbar",
+            (Fallback::NoOverview, "es") => "No hay descripción disponible",
+            (Fallback::NoOverview, "fr") => "Aucun résumé disponible",
+            (Fallback::NoOverview, "it") => "Nessuna trama disponibile",
+            (Fallback::NoOverview, "ja") => "あらすじはありません",
+            (Fallback::NoOverview, "nl") => "Geen beschrijving beschikbaar",
+            (Fallback::NoOverview, "pt") => "Nenhuma sinopse disponível",
+            (Fallback::NoOverview, _) => "No overview provided",
+            (Fallback::NotAvailable, "de") => "k. A.",
+            (Fallback::NotAvailable, "es" | "fr" | "it" | "pt") => "N/D",
+            (Fallback::NotAvailable, "ja") => "不明",
+            (Fallback::NotAvailable, "nl") => "n.v.t.",
+            (Fallback::NotAvailable, _) => "N/A",
+        }
+    }
+
+    /// Returns the fallback text in English, as an owned string for use in conversions.
+    pub fn to_owned_text(self) -> String {
+        self.text(None).to_owned()
+    }
+
+    /// Swaps `field` for the fallback text in the given language if it currently holds the
+    /// English fallback text.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The field that may hold fallback text
+    /// * `language` - The language to show the fallback text in
+    pub fn localise(self, field: &mut String, language: &str) {
+        if field == self.text(None) {
+            *field = self.text(Some(language)).to_owned();
+        }
+    }
+}
@@ -5,14 +5,51 @@
 /// Common types and utilities used across multiple frontend models.
 pub mod common;
 
+/// Models for collections of movies, such as franchises.
+pub mod collections;
+
+/// Models for production companies and countries.
+pub mod companies;
+
 /// Data structures for representing movie credits, including cast and crew.
 pub mod credits;
 
+/// Models related to movie genres.
+pub mod genres;
+
+/// Models for keywords used to tag movies.
+pub mod keywords;
+
+/// Builders for TMDB image URLs in every size, placeholders for missing images, and image
+/// galleries.
+pub mod images;
+
+/// Fallback text for missing details, and translations of it.
+pub mod localisation;
+
 /// Models related to movies, such as movie lists and movie details.
 pub mod movies;
 
+/// Models for personalised movie recommendations.
+pub mod recommendations;
+
+/// Models for movie release dates and age certifications.
+pub mod release_dates;
+
 /// Data structures for representing people, including actors and directors.
 pub mod people;
 
+/// Models for movie reviews, from Cinescore and elsewhere.
+pub mod reviews;
+
+/// Models for search results that mix movies, people, collections and users.
+pub mod search;
+
 /// Models related to social media links and external IDs.
 pub mod socials;
+
+/// Models for videos attached to movies, such as trailers.
+pub mod videos;
+
+/// Models for where movies can be streamed, rented or bought.
+pub mod watch_providers;
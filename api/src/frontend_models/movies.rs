@@ -1,22 +1,57 @@
+use std::collections::HashMap;
+
 use chrono::NaiveDate;
 use serde::Serialize;
 
-use crate::tmdb::models::{
-    common::{Language, PaginatedSearchResult},
-    movie::{MovieDetails, SearchMovie},
+use crate::{
+    metadata::CachedMovie,
+    tmdb::models::{
+        common::{Language, PaginatedSearchResult},
+        movie::{MovieDetails, SearchMovie},
+        release_dates::CountryReleaseDates,
+        watch_providers::WatchProviderAvailability,
+    },
+};
+
+use super::{
+    collections::FrontendCollectionSummary,
+    companies::{FrontendCompany, FrontendProductionCountry},
+    credits::FrontendMovieCredits,
+    genres::{FrontendGenre, ResolveGenres},
+    images::ImageKind,
+    keywords::FrontendKeyword,
+    localisation::{Fallback, Localise},
+    release_dates::{release_timeline, FrontendRelease},
+    socials::FrontendSocials,
+    videos::{sort_trailers, trailers_from_videos, FrontendTrailer},
+    watch_providers::FrontendWatchProviders,
 };
 
-use super::{common::get_image_url, credits::FrontendMovieCredits};
+/// The width posters are shown at on movie pages, which is larger than in lists.
+const DETAILS_POSTER_WIDTH: u32 = 500;
 
 /// Represents a list of movies formatted for the frontend.
 #[derive(Debug, Serialize)]
 pub struct FrontendMovieList {
     /// A list of movies
     movies: Vec<MovieListing>,
+    /// The current page of results.
+    page: u64,
+    /// The total number of pages available. An upper bound if `totals_are_upper_bounds` is set.
+    #[serde(rename = "totalPages")]
+    total_pages: u64,
+    /// The total number of results available. An upper bound if `totals_are_upper_bounds` is set.
+    #[serde(rename = "totalResults")]
+    total_results: u64,
+    /// Whether movies were removed from the page after it was fetched, such as by a parental
+    /// filter. If so, pages may hold fewer movies than usual, and the totals are upper bounds
+    /// rather than exact counts.
+    #[serde(rename = "totalsAreUpperBounds")]
+    totals_are_upper_bounds: bool,
 }
 
 /// Represents an individual movie entry in the frontend.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MovieListing {
     /// Unique identifier for the movie.
     id: u64,
@@ -24,6 +59,9 @@ pub struct MovieListing {
     title: String,
     /// URL to the movie's poster image.
     poster: String,
+    /// `srcset` of every width the movie's poster comes in, if there is one.
+    #[serde(rename = "posterSrcset")]
+    poster_srcset: Option<String>,
     /// A brief description or overview of the movie.
     description: String,
     /// The overall score or rating of the movie.
@@ -38,6 +76,11 @@ pub struct MovieListing {
     /// Release date of the movie.
     #[serde(rename = "releaseDate")]
     release_date: Option<NaiveDate>,
+    /// Genres of the movie. Only populated once the genre IDs have been resolved.
+    genres: Vec<FrontendGenre>,
+    /// Genre IDs of the movie, used to resolve [`MovieListing::genres`].
+    #[serde(skip)]
+    genre_ids: Vec<u64>,
 }
 
 /// Represents detailed movie information formatted for the frontend.
@@ -46,8 +89,13 @@ pub struct FrontendMovieDetails {
     /// URL to the movie's backdrop image.
     #[serde(rename = "backdropUrl")]
     backdrop_url: String,
+    /// `srcset` of every width the movie's backdrop comes in, if there is one.
+    #[serde(rename = "backdropSrcset")]
+    backdrop_srcset: Option<String>,
     /// The budget of the movie in dollars.
     budget: u64,
+    /// URL of the movie's official website, if it has one.
+    homepage: Option<String>,
     /// Unique identifier for the movie.
     id: u64,
     /// The IMDb identifier of the movie.
@@ -62,6 +110,9 @@ pub struct FrontendMovieDetails {
     /// URL to the movie's poster image.
     #[serde(rename = "posterUrl")]
     poster_url: String,
+    /// `srcset` of every width the movie's poster comes in, if there is one.
+    #[serde(rename = "posterSrcset")]
+    poster_srcset: Option<String>,
     /// The title of the movie.
     title: String,
     /// The release date of the movie.
@@ -76,8 +127,51 @@ pub struct FrontendMovieDetails {
     spoken_languages: Vec<Language>,
     /// The tagline of the movie.
     tagline: String,
+    /// Status of the movie (e.g., Released, Post Production).
+    status: String,
+    /// The movie's age certification in the user's region. Only populated once a region has
+    /// been selected.
+    certification: Option<String>,
+    /// The movie's releases in the user's region, earliest first. Only populated once a region
+    /// has been selected.
+    #[serde(rename = "releaseTimeline")]
+    release_timeline: Vec<FrontendRelease>,
+    /// The movie's releases in every region, used to populate
+    /// [`FrontendMovieDetails::certification`] and [`FrontendMovieDetails::release_timeline`].
+    #[serde(skip)]
+    regional_release_dates: Vec<CountryReleaseDates>,
+    /// Genres of the movie.
+    genres: Vec<FrontendGenre>,
+    /// Keywords the movie is tagged with. Empty if keywords were not requested.
+    keywords: Vec<FrontendKeyword>,
+    /// The collection the movie belongs to, if any.
+    collection: Option<FrontendCollectionSummary>,
+    /// The companies that produced the movie.
+    #[serde(rename = "productionCompanies")]
+    production_companies: Vec<FrontendCompany>,
+    /// The countries the movie was produced in.
+    #[serde(rename = "productionCountries")]
+    production_countries: Vec<FrontendProductionCountry>,
     /// Optional movie credits (cast and crew).
     credits: Option<FrontendMovieCredits>,
+    /// Links to the movie's pages on other websites, such as its IMDb page and Wikidata item.
+    /// `None` if external IDs were not requested.
+    #[allow(clippy::doc_markdown)]
+    socials: Option<FrontendSocials>,
+    /// Optional movies recommended by TMDB based on this movie.
+    recommendations: Option<Vec<MovieListing>>,
+    /// Optional movies TMDB considers similar to this movie.
+    similar: Option<Vec<MovieListing>>,
+    /// The movie's trailers on supported sites, best first. Empty if videos were not requested.
+    trailers: Vec<FrontendTrailer>,
+    /// Where the movie can be watched in the user's region. Only populated once a region has
+    /// been selected.
+    #[serde(rename = "watchProviders")]
+    watch_providers: Option<FrontendWatchProviders>,
+    /// Where the movie can be watched in every region, used to populate
+    /// [`FrontendMovieDetails::watch_providers`].
+    #[serde(skip)]
+    regional_watch_providers: HashMap<String, WatchProviderAvailability>,
     /// The overall score or rating of the movie.
     #[serde(rename = "overallScore")]
     overall_score: f32,
@@ -104,12 +198,47 @@ impl From<SearchMovie> for MovieListing {
         Self {
             id: value.base.id,
             title: value.base.title,
-            poster: get_image_url(value.base.poster_path),
+            poster: ImageKind::Poster.url(value.base.poster_path.as_deref()),
+            poster_srcset: ImageKind::Poster.srcset(value.base.poster_path.as_deref()),
+            description: value
+                .base
+                .overview
+                .unwrap_or_else(|| Fallback::NoOverview.to_owned_text()),
+            release_date: value.base.release_date,
+            genres: Vec::new(),
+            genre_ids: value.genre_ids,
+            // TODO: these 3
+            overall_score: 0.0,
+            is_liked: false,
+            in_watchlist: false,
+        }
+    }
+}
+
+/// Converts a [`MovieDetails`] into a [`MovieListing`] for frontend representation.
+impl From<MovieDetails> for MovieListing {
+    /// Converts a [`MovieDetails`] into a [`MovieListing`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`MovieDetails`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`MovieListing`] instance with all fields mapped from the source.
+    fn from(value: MovieDetails) -> Self {
+        Self {
+            id: value.base.id,
+            title: value.base.title,
+            poster: ImageKind::Poster.url(value.base.poster_path.as_deref()),
+            poster_srcset: ImageKind::Poster.srcset(value.base.poster_path.as_deref()),
             description: value
                 .base
                 .overview
-                .unwrap_or("No overview provided".to_owned()),
+                .unwrap_or_else(|| Fallback::NoOverview.to_owned_text()),
             release_date: value.base.release_date,
+            genre_ids: value.genres.iter().map(|genre| genre.id).collect(),
+            genres: value.genres.into_iter().map(FrontendGenre::from).collect(),
             // TODO: these 3
             overall_score: 0.0,
             is_liked: false,
@@ -118,6 +247,67 @@ impl From<SearchMovie> for MovieListing {
     }
 }
 
+impl From<CachedMovie> for MovieListing {
+    /// Converts a [`CachedMovie`] into a [`MovieListing`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`CachedMovie`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`MovieListing`] instance with all fields mapped from the source.
+    fn from(value: CachedMovie) -> Self {
+        let genres: Vec<FrontendGenre> = value
+            .genre_ids
+            .iter()
+            .zip(value.genre_names)
+            .map(|(id, name)| FrontendGenre {
+                id: *id as u64,
+                name,
+            })
+            .collect();
+
+        Self {
+            id: value.movie_id as u64,
+            title: value.title,
+            poster: ImageKind::Poster.url(value.poster_path.as_deref()),
+            poster_srcset: ImageKind::Poster.srcset(value.poster_path.as_deref()),
+            description: value
+                .overview
+                .unwrap_or_else(|| Fallback::NoOverview.to_owned_text()),
+            release_date: value.release_date,
+            genre_ids: genres.iter().map(|genre| genre.id).collect(),
+            genres,
+            overall_score: 0.0,
+            is_liked: false,
+            in_watchlist: false,
+        }
+    }
+}
+
+/// Converts a single page of [`MovieListing`]s into a [`FrontendMovieList`].
+impl From<Vec<MovieListing>> for FrontendMovieList {
+    /// Converts a [`Vec<MovieListing>`] into a [`FrontendMovieList`] containing a single page.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source movies to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendMovieList`] instance containing every movie on one page.
+    fn from(value: Vec<MovieListing>) -> Self {
+        Self {
+            page: 1,
+            total_pages: 1,
+            total_results: value.len() as u64,
+            totals_are_upper_bounds: false,
+            movies: value,
+        }
+    }
+}
+
 /// Converts a [`PaginatedSearchResult<SearchMovie>`] into a [`FrontendMovieList`].
 impl From<PaginatedSearchResult<SearchMovie>> for FrontendMovieList {
     /// Converts a [`PaginatedSearchResult<SearchMovie>`] into a [`FrontendMovieList`].
@@ -132,6 +322,10 @@ impl From<PaginatedSearchResult<SearchMovie>> for FrontendMovieList {
     fn from(value: PaginatedSearchResult<SearchMovie>) -> Self {
         Self {
             movies: value.results.into_iter().map(MovieListing::from).collect(),
+            page: value.page,
+            total_pages: value.total_pages,
+            total_results: value.total_results,
+            totals_are_upper_bounds: false,
         }
     }
 }
@@ -149,20 +343,70 @@ impl From<MovieDetails> for FrontendMovieDetails {
     /// A new [`FrontendMovieDetails`] instance with all fields mapped from the source.
     fn from(value: MovieDetails) -> Self {
         Self {
-            backdrop_url: get_image_url(value.base.backdrop_path),
+            backdrop_url: ImageKind::Backdrop.url(value.base.backdrop_path.as_deref()),
+            backdrop_srcset: ImageKind::Backdrop.srcset(value.base.backdrop_path.as_deref()),
             budget: value.budget,
+            homepage: Some(value.homepage).filter(|homepage| !homepage.is_empty()),
             id: value.base.id,
             imdb_id: value.imdb_id,
             original_language: value.base.original_language,
-            overview: value.base.overview.unwrap_or("N/A".to_owned()),
-            poster_url: get_image_url(value.base.poster_path),
+            overview: value
+                .base
+                .overview
+                .unwrap_or_else(|| Fallback::NotAvailable.to_owned_text()),
+            poster_url: ImageKind::Poster
+                .sized_url(value.base.poster_path.as_deref(), DETAILS_POSTER_WIDTH),
+            poster_srcset: ImageKind::Poster.srcset(value.base.poster_path.as_deref()),
             title: value.base.title,
             release_date: value.base.release_date,
             revenue: value.revenue,
             runtime: value.runtime,
             spoken_languages: value.spoken_languages,
             tagline: value.tagline,
+            status: value.status,
+            certification: None,
+            release_timeline: Vec::new(),
+            regional_release_dates: value
+                .release_dates
+                .map(|release_dates| release_dates.results)
+                .unwrap_or_default(),
+            genres: value.genres.into_iter().map(FrontendGenre::from).collect(),
+            keywords: value
+                .keywords
+                .map(|list| {
+                    list.keywords
+                        .into_iter()
+                        .map(FrontendKeyword::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            collection: value
+                .belongs_to_collection
+                .map(FrontendCollectionSummary::from),
+            production_companies: value
+                .production_companies
+                .into_iter()
+                .map(FrontendCompany::from)
+                .collect(),
+            production_countries: value
+                .production_countries
+                .into_iter()
+                .map(FrontendProductionCountry::from)
+                .collect(),
             credits: value.credits.map(FrontendMovieCredits::from),
+            socials: value.external_ids.map(FrontendSocials::from),
+            recommendations: value
+                .recommendations
+                .map(|list| list.results.into_iter().map(MovieListing::from).collect()),
+            similar: value
+                .similar
+                .map(|list| list.results.into_iter().map(MovieListing::from).collect()),
+            trailers: value.videos.map(trailers_from_videos).unwrap_or_default(),
+            watch_providers: None,
+            regional_watch_providers: value
+                .watch_providers
+                .map(|providers| providers.results)
+                .unwrap_or_default(),
             // TODO: these 3
             overall_score: 0.0,
             is_liked: false,
@@ -171,9 +415,186 @@ impl From<MovieDetails> for FrontendMovieDetails {
     }
 }
 
+impl ResolveGenres for MovieListing {
+    fn resolve_genres(&mut self, genres: &HashMap<u64, String>) {
+        self.genres = self
+            .genre_ids
+            .iter()
+            .filter_map(|id| {
+                genres.get(id).map(|name| FrontendGenre {
+                    id: *id,
+                    name: name.clone(),
+                })
+            })
+            .collect();
+    }
+}
+
+impl MovieListing {
+    /// The unique identifier of the movie.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Sets whether the user has liked the movie or added it to their watchlist.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_liked` - Whether the user has liked the movie
+    /// * `in_watchlist` - Whether the movie is in the user's watchlist
+    pub fn set_user_interaction(&mut self, is_liked: bool, in_watchlist: bool) {
+        self.is_liked = is_liked;
+        self.in_watchlist = in_watchlist;
+    }
+
+    /// Sets the movie's overall score from Cinescore ratings.
+    ///
+    /// # Arguments
+    ///
+    /// * `score` - The average rating the movie has been given, from 1 to 5
+    pub fn set_overall_score(&mut self, score: f32) {
+        self.overall_score = score;
+    }
+}
+
+impl FrontendMovieList {
+    /// Creates a page of movies from a list paginated by Cinescore rather than TMDB.
+    ///
+    /// # Arguments
+    ///
+    /// * `movies` - The movies on the page
+    /// * `page` - The current page of results
+    /// * `page_size` - The number of movies on a full page
+    /// * `total_results` - The total number of movies available
+    pub fn new(movies: Vec<MovieListing>, page: u64, page_size: u64, total_results: u64) -> Self {
+        Self {
+            movies,
+            page,
+            total_pages: total_results.div_ceil(page_size),
+            total_results,
+            totals_are_upper_bounds: false,
+        }
+    }
+
+    /// The IDs of the movies in the list.
+    pub fn movie_ids(&self) -> Vec<u64> {
+        self.movies.iter().map(|movie| movie.id).collect()
+    }
+
+    /// Removes every movie from the list that `keep` returns `false` for. The totals are left as
+    /// they are, since movies on other pages are not checked, so the list is marked as having
+    /// totals that are upper bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - Called with the ID of each movie, returning whether to keep it
+    pub fn retain_movies<F: FnMut(u64) -> bool>(&mut self, mut keep: F) {
+        self.movies.retain(|movie| keep(movie.id));
+        self.totals_are_upper_bounds = true;
+    }
+}
+
+impl ResolveGenres for FrontendMovieList {
+    fn resolve_genres(&mut self, genres: &HashMap<u64, String>) {
+        for movie in &mut self.movies {
+            movie.resolve_genres(genres);
+        }
+    }
+}
+
+impl ResolveGenres for FrontendMovieDetails {
+    fn resolve_genres(&mut self, genres: &HashMap<u64, String>) {
+        let related = self
+            .recommendations
+            .iter_mut()
+            .chain(self.similar.iter_mut());
+
+        for movie in related.flatten() {
+            movie.resolve_genres(genres);
+        }
+    }
+}
+
+impl Localise for MovieListing {
+    fn localise(&mut self, language: &str) {
+        Fallback::NoOverview.localise(&mut self.description, language);
+    }
+}
+
+impl Localise for FrontendMovieList {
+    fn localise(&mut self, language: &str) {
+        for movie in &mut self.movies {
+            movie.localise(language);
+        }
+    }
+}
+
+impl Localise for FrontendMovieDetails {
+    fn localise(&mut self, language: &str) {
+        Fallback::NotAvailable.localise(&mut self.overview, language);
+
+        let related = self
+            .recommendations
+            .iter_mut()
+            .chain(self.similar.iter_mut());
+
+        for movie in related.flatten() {
+            movie.localise(language);
+        }
+    }
+}
+
 impl FrontendMovieDetails {
     pub fn set_user_interaction(&mut self, is_liked: bool, in_watchlist: bool) {
         self.is_liked = is_liked;
         self.in_watchlist = in_watchlist;
     }
+
+    /// Selects the region whose watch providers, certification and releases are shown. Anything
+    /// the movie has no data for in the region is left empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The ISO 3166-1 code of the region
+    pub fn select_region(&mut self, region: &str) {
+        self.watch_providers = self
+            .regional_watch_providers
+            .remove(region)
+            .map(|availability| FrontendWatchProviders::new(region.to_owned(), availability));
+
+        let release_dates = self
+            .regional_release_dates
+            .iter()
+            .position(|release_dates| release_dates.iso_3166_1 == region)
+            .map(|index| self.regional_release_dates.swap_remove(index));
+
+        if let Some(release_dates) = release_dates {
+            self.certification = release_dates.certification().map(str::to_owned);
+            self.release_timeline = release_timeline(release_dates);
+        }
+    }
+
+    /// Re-sorts the movie's trailers to prefer ones in the given language.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The preferred language (e.g., `en` or `en-US`)
+    pub fn prefer_trailer_language(&mut self, language: &str) {
+        sort_trailers(&mut self.trailers, Some(language));
+    }
+
+    /// Limits the embedded recommended and similar movies to at most `limit` entries each.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum number of related movies to keep in each list
+    pub fn truncate_related(&mut self, limit: usize) {
+        for movies in self
+            .recommendations
+            .iter_mut()
+            .chain(self.similar.iter_mut())
+        {
+            movies.truncate(limit);
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Utc};
 use serde::Serialize;
 
 use crate::tmdb::models::{
@@ -6,35 +6,61 @@ use crate::tmdb::models::{
     person::{PersonDetails, SearchPerson},
 };
 
-use super::{common::get_image_url, credits::FrontendPersonCredits, socials::FrontendSocials};
+use super::{
+    common::non_empty,
+    credits::{known_for, FrontendKnownForCredit, FrontendPersonCredits},
+    images::ImageKind,
+    localisation::{Fallback, Localise},
+    socials::FrontendSocials,
+};
 
 /// Represents detailed information about a person in the entertainment industry.
 /// This struct contains comprehensive biographical and professional information,
 /// including their work history and social media presence.
 #[derive(Debug, Serialize)]
 pub struct FrontendPersonDetails {
+    /// Other names the person is known by, such as their name in other scripts.
+    #[serde(rename = "alsoKnownAs")]
+    pub also_known_as: Vec<String>,
     /// A biographical description of the person.
     pub biography: String,
     /// The person's date of birth in ISO 8601 format (YYYY-MM-DD).
     pub birthday: Option<NaiveDate>,
     /// The person's date of death in ISO 8601 format (YYYY-MM-DD), if applicable.
     pub deathday: Option<NaiveDate>,
+    /// The person's age, or the age they died at. `None` if their birthday is not known.
+    pub age: Option<u32>,
     /// The person's gender represented as a number:
     pub gender: Gender,
+    /// URL of the person's own website, if they have one.
+    pub homepage: Option<String>,
     /// Unique identifier for the person.
     pub id: u64,
+    /// The person's IMDb ID, if available.
+    #[allow(clippy::doc_markdown)]
+    #[serde(rename = "imdbId")]
+    pub imdb_id: Option<String>,
     /// The department or field they are primarily known for working in
     /// (e.g., "Acting", "Directing", "Production").
     #[serde(rename = "knownForDepartment")]
     pub known_for_department: String,
+    /// The movies the person is best known for, in the department they are known for. Empty if
+    /// credit information is not requested.
+    #[serde(rename = "knownFor")]
+    pub known_for: Vec<FrontendKnownForCredit>,
     /// The person's full name.
     pub name: String,
     /// The location where the person was born.
     #[serde(rename = "placeOfBirth")]
     pub place_of_birth: String,
+    /// The person's TMDB popularity score.
+    pub popularity: f64,
     /// URL to the person's profile image.
     #[serde(rename = "iconUrl")]
     pub icon_url: String,
+    /// `srcset` of every width the person's profile image comes in, if there is one.
+    #[serde(rename = "iconSrcset")]
+    pub icon_srcset: Option<String>,
     /// The person's movie credits, both as cast and crew member.
     /// May be None if credit information is not requested or available.
     pub credits: Option<FrontendPersonCredits>,
@@ -50,11 +76,19 @@ pub struct FrontendPersonDetails {
 pub struct FrontendPeopleList {
     /// Vector of brief person listings.
     pub people: Vec<FrontendPersonListing>,
+    /// The current page of results.
+    pub page: u64,
+    /// The total number of pages available.
+    #[serde(rename = "totalPages")]
+    pub total_pages: u64,
+    /// The total number of results available.
+    #[serde(rename = "totalResults")]
+    pub total_results: u64,
 }
 
 /// A brief overview of a person, typically used in search results or lists.
 /// Contains essential identifying information without full biographical details.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FrontendPersonListing {
     /// Unique identifier for the person.
     pub id: u64,
@@ -68,6 +102,9 @@ pub struct FrontendPersonListing {
     /// URL to the person's profile image.
     #[serde(rename = "iconUrl")]
     pub icon_url: String,
+    /// `srcset` of every width the person's profile image comes in, if there is one.
+    #[serde(rename = "iconSrcset")]
+    pub icon_srcset: Option<String>,
 }
 
 /// Conversion implementation to create frontend-ready person details
@@ -83,16 +120,35 @@ impl From<PersonDetails> for FrontendPersonDetails {
     ///
     /// A new [`FrontendPersonDetails`] instance with all fields mapped from the source.
     fn from(value: PersonDetails) -> Self {
+        let age = value.birthday.and_then(|birthday| {
+            let end = value.deathday.unwrap_or_else(|| Utc::now().date_naive());
+            end.years_since(birthday)
+        });
+        let known_for = value
+            .credits
+            .as_ref()
+            .map(|credits| known_for(credits, &value.known_for_department))
+            .unwrap_or_default();
+
         Self {
+            also_known_as: value.also_known_as,
             biography: value.biography,
             birthday: value.birthday,
             deathday: value.deathday,
+            age,
             gender: value.gender,
+            homepage: value.homepage.and_then(non_empty),
             id: value.id,
+            imdb_id: value.imdb_id.and_then(non_empty),
             known_for_department: value.known_for_department,
+            known_for,
             name: value.name,
-            place_of_birth: value.place_of_birth.unwrap_or("N/A".to_owned()),
-            icon_url: get_image_url(value.profile_path),
+            place_of_birth: value
+                .place_of_birth
+                .unwrap_or_else(|| Fallback::NotAvailable.to_owned_text()),
+            popularity: value.popularity,
+            icon_url: ImageKind::Profile.url(value.profile_path.as_deref()),
+            icon_srcset: ImageKind::Profile.srcset(value.profile_path.as_deref()),
             credits: value.credits.map(FrontendPersonCredits::from),
             socials: value.external_ids.map(FrontendSocials::from),
         }
@@ -116,8 +172,11 @@ impl From<SearchPerson> for FrontendPersonListing {
             id: value.id,
             name: value.name,
             gender: value.gender,
-            department: value.known_for_department.unwrap_or("N/A".to_owned()),
-            icon_url: get_image_url(value.profile_path),
+            department: value
+                .known_for_department
+                .unwrap_or_else(|| Fallback::NotAvailable.to_owned_text()),
+            icon_url: ImageKind::Profile.url(value.profile_path.as_deref()),
+            icon_srcset: ImageKind::Profile.srcset(value.profile_path.as_deref()),
         }
     }
 }
@@ -142,6 +201,29 @@ impl From<PaginatedSearchResult<SearchPerson>> for FrontendPeopleList {
                 .into_iter()
                 .map(FrontendPersonListing::from)
                 .collect(),
+            page: value.page,
+            total_pages: value.total_pages,
+            total_results: value.total_results,
+        }
+    }
+}
+
+impl Localise for FrontendPersonDetails {
+    fn localise(&mut self, language: &str) {
+        Fallback::NotAvailable.localise(&mut self.place_of_birth, language);
+    }
+}
+
+impl Localise for FrontendPersonListing {
+    fn localise(&mut self, language: &str) {
+        Fallback::NotAvailable.localise(&mut self.department, language);
+    }
+}
+
+impl Localise for FrontendPeopleList {
+    fn localise(&mut self, language: &str) {
+        for person in &mut self.people {
+            person.localise(language);
         }
     }
 }
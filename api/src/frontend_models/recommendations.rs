@@ -0,0 +1,81 @@
+use serde::Serialize;
+
+use crate::recommendations::RecommendationRow;
+
+use super::images::ImageKind;
+
+/// Represents a user's personalised recommendations formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendRecommendationList {
+    /// A list of recommended movies, best first
+    recommendations: Vec<FrontendRecommendation>,
+}
+
+/// Represents a single personalised recommendation in the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendRecommendation {
+    /// Unique identifier for the recommended movie.
+    id: u64,
+    /// The title of the recommended movie, if it could be looked up.
+    title: Option<String>,
+    /// URL to the recommended movie's poster image.
+    poster: String,
+    /// `srcset` of every width the recommended movie's poster comes in, if there is one.
+    #[serde(rename = "posterSrcset")]
+    poster_srcset: Option<String>,
+    /// How strongly the movie is recommended.
+    score: f64,
+    /// Which recommender produced the recommendation (`collaborative` or `content`).
+    source: String,
+    /// A short explanation of why the movie was recommended.
+    reason: Option<String>,
+    /// The ID of the movie the user liked that led to this recommendation.
+    #[serde(rename = "reasonMovieId")]
+    reason_movie_id: Option<u64>,
+}
+
+impl From<RecommendationRow> for FrontendRecommendation {
+    /// Converts a [`RecommendationRow`] into a [`FrontendRecommendation`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`RecommendationRow`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendRecommendation`] instance with all fields mapped from the source.
+    fn from(value: RecommendationRow) -> Self {
+        Self {
+            id: value.movie_id as u64,
+            title: value.title,
+            poster: ImageKind::Poster.url(value.poster_path.as_deref()),
+            poster_srcset: ImageKind::Poster.srcset(value.poster_path.as_deref()),
+            score: value.score,
+            source: value.source,
+            reason: value
+                .reason_title
+                .map(|title| format!("Because you liked {}", title)),
+            reason_movie_id: value.reason_movie_id.map(|id| id as u64),
+        }
+    }
+}
+
+impl From<Vec<RecommendationRow>> for FrontendRecommendationList {
+    /// Converts a list of [`RecommendationRow`]s into a [`FrontendRecommendationList`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source rows to convert from, best first
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendRecommendationList`] instance with all fields mapped from the source.
+    fn from(value: Vec<RecommendationRow>) -> Self {
+        Self {
+            recommendations: value
+                .into_iter()
+                .map(FrontendRecommendation::from)
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::tmdb::models::release_dates::{
+    Certification, CountryReleaseDates, ReleaseDate, ReleaseType,
+};
+
+use super::common::non_empty;
+
+/// Represents a single release of a movie in a region, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendRelease {
+    /// The kind of release (e.g., premiere, theatrical, digital).
+    #[serde(rename = "type")]
+    kind: &'static str,
+    /// When the movie was released.
+    date: DateTime<Utc>,
+    /// The age certification the movie was released with, if any.
+    certification: Option<String>,
+    /// ISO 639-1 code of the release's language, if it is language-specific.
+    language: Option<String>,
+    /// A note about the release (e.g., the festival it premiered at), if any.
+    note: Option<String>,
+}
+
+/// Represents an age certification used in a region, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendCertification {
+    /// The certification (e.g., PG-13).
+    certification: String,
+    /// What the certification means.
+    meaning: String,
+    /// The position of the certification from least to most restrictive.
+    order: u32,
+}
+
+/// Represents the age certifications used in a region, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendCertificationList {
+    /// The ISO 3166-1 code of the region.
+    region: String,
+    /// The certifications, from least to most restrictive.
+    certifications: Vec<FrontendCertification>,
+}
+
+impl From<ReleaseDate> for FrontendRelease {
+    /// Converts a [`ReleaseDate`] into a [`FrontendRelease`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`ReleaseDate`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendRelease`] instance with all fields mapped from the source.
+    fn from(value: ReleaseDate) -> Self {
+        Self {
+            kind: match value.release_type {
+                ReleaseType::Premiere => "premiere",
+                ReleaseType::TheatricalLimited => "theatricalLimited",
+                ReleaseType::Theatrical => "theatrical",
+                ReleaseType::Digital => "digital",
+                ReleaseType::Physical => "physical",
+                ReleaseType::Tv => "tv",
+            },
+            date: value.release_date,
+            certification: non_empty(value.certification),
+            language: non_empty(value.iso_639_1),
+            note: non_empty(value.note),
+        }
+    }
+}
+
+impl From<Certification> for FrontendCertification {
+    /// Converts a [`Certification`] into a [`FrontendCertification`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`Certification`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendCertification`] instance with all fields mapped from the source.
+    fn from(value: Certification) -> Self {
+        Self {
+            certification: value.certification,
+            meaning: value.meaning,
+            order: value.order,
+        }
+    }
+}
+
+impl FrontendCertificationList {
+    /// Creates a [`FrontendCertificationList`] from the certifications used in a region.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The ISO 3166-1 code of the region
+    /// * `certifications` - The certifications used in the region
+    pub fn new(region: String, mut certifications: Vec<Certification>) -> Self {
+        certifications.sort_by_key(|certification| certification.order);
+
+        Self {
+            region,
+            certifications: certifications
+                .into_iter()
+                .map(FrontendCertification::from)
+                .collect(),
+        }
+    }
+}
+
+/// Converts a movie's releases in a region into a timeline, earliest first.
+///
+/// # Arguments
+///
+/// * `value` - The movie's releases in the region
+pub fn release_timeline(value: CountryReleaseDates) -> Vec<FrontendRelease> {
+    let mut releases = value.release_dates;
+    releases.sort_by_key(|release| (release.release_date, release.release_type));
+    releases.into_iter().map(FrontendRelease::from).collect()
+}
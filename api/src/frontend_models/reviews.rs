@@ -0,0 +1,155 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::tmdb::models::{
+    common::PaginatedSearchResult,
+    review::{Review, ReviewAuthor},
+};
+
+use super::images::ImageKind;
+
+/// Where a review was written. Reviews from every source share the same shape, so they can be
+/// shown in a single feed, but the source lets the frontend label reviews from elsewhere.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewSource {
+    /// Written by a TMDB user.
+    Tmdb,
+}
+
+/// Represents a movie review formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendReview {
+    /// Unique identifier for the review, within its source.
+    id: String,
+    /// Where the review was written.
+    source: ReviewSource,
+    /// Whether the review was written somewhere other than Cinescore.
+    external: bool,
+    /// The review's author.
+    author: FrontendReviewAuthor,
+    /// The text of the review, in Markdown.
+    content: String,
+    /// When the review was written.
+    #[serde(rename = "createdAt")]
+    created_at: DateTime<Utc>,
+    /// When the review was last edited.
+    #[serde(rename = "updatedAt")]
+    updated_at: DateTime<Utc>,
+    /// URL of the review on the site it was written on, for external reviews.
+    url: Option<String>,
+}
+
+/// Represents the author of a review formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendReviewAuthor {
+    /// The author's display name, or their username if they have not set one.
+    name: String,
+    /// The author's username.
+    username: String,
+    /// URL to the author's avatar.
+    #[serde(rename = "avatarUrl")]
+    avatar_url: String,
+    /// The rating the author gave the movie, out of 10.
+    rating: Option<f64>,
+}
+
+/// Represents a page of reviews formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendReviewList {
+    /// The reviews on this page.
+    reviews: Vec<FrontendReview>,
+    /// The current page of results.
+    page: u64,
+    /// The total number of pages available.
+    #[serde(rename = "totalPages")]
+    total_pages: u64,
+    /// The total number of results available.
+    #[serde(rename = "totalResults")]
+    total_results: u64,
+}
+
+impl From<ReviewAuthor> for FrontendReviewAuthor {
+    /// Converts a [`ReviewAuthor`] into a [`FrontendReviewAuthor`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`ReviewAuthor`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendReviewAuthor`] instance with all fields mapped from the source.
+    fn from(value: ReviewAuthor) -> Self {
+        // older avatars are stored as a Gravatar URL behind a leading slash
+        let avatar_url = match value
+            .avatar_path
+            .as_deref()
+            .and_then(|p| p.strip_prefix('/'))
+        {
+            Some(url) if url.starts_with("http") => url.to_owned(),
+            _ => ImageKind::Profile.url(value.avatar_path.as_deref()),
+        };
+
+        Self {
+            name: if value.name.is_empty() {
+                value.username.clone()
+            } else {
+                value.name
+            },
+            username: value.username,
+            avatar_url,
+            rating: value.rating,
+        }
+    }
+}
+
+impl From<Review> for FrontendReview {
+    /// Converts a TMDB [`Review`] into a [`FrontendReview`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`Review`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendReview`] instance marked as an external TMDB review.
+    fn from(value: Review) -> Self {
+        Self {
+            id: value.id,
+            source: ReviewSource::Tmdb,
+            external: true,
+            author: FrontendReviewAuthor::from(value.author_details),
+            content: value.content,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            url: Some(value.url),
+        }
+    }
+}
+
+impl From<PaginatedSearchResult<Review>> for FrontendReviewList {
+    /// Converts a [`PaginatedSearchResult<Review>`] into a [`FrontendReviewList`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`PaginatedSearchResult<Review>`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendReviewList`] instance with every review converted, in the order TMDB
+    /// returned them.
+    fn from(value: PaginatedSearchResult<Review>) -> Self {
+        let reviews: Vec<FrontendReview> = value
+            .results
+            .into_iter()
+            .map(FrontendReview::from)
+            .collect();
+
+        Self {
+            reviews,
+            page: value.page,
+            total_pages: value.total_pages,
+            total_results: value.total_results,
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::tmdb::models::search::MultiSearchResult;
+
+use super::{
+    collections::FrontendCollectionSummary, genres::ResolveGenres, localisation::Localise,
+    movies::MovieListing, people::FrontendPersonListing,
+};
+
+/// Represents a single search result, tagged with the kind of thing it is, formatted for the
+/// frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FrontendSearchResult {
+    /// A movie
+    Movie(MovieListing),
+    /// A person
+    Person(FrontendPersonListing),
+    /// A collection of movies, such as a franchise
+    Collection(FrontendCollectionSummary),
+    /// A Cinescore user
+    User(FrontendUserListing),
+}
+
+/// Represents a Cinescore user in search results, formatted for the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrontendUserListing {
+    /// Unique identifier for the user.
+    pub id: Uuid,
+    /// The user's username.
+    pub username: String,
+}
+
+/// Represents a list of search results of every kind, formatted for the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrontendSearchResults {
+    /// The search results
+    results: Vec<FrontendSearchResult>,
+    /// The current page of results.
+    page: u64,
+    /// The total number of pages available. An upper bound if `totals_are_upper_bounds` is set.
+    #[serde(rename = "totalPages")]
+    total_pages: u64,
+    /// The total number of results available. An upper bound if `totals_are_upper_bounds` is set.
+    #[serde(rename = "totalResults")]
+    total_results: u64,
+    /// Whether movies were removed from the results after it was fetched, such as by a parental
+    /// filter. If so, pages may hold fewer results than usual, and the totals are upper bounds
+    /// rather than exact counts.
+    #[serde(rename = "totalsAreUpperBounds")]
+    totals_are_upper_bounds: bool,
+}
+
+impl FrontendSearchResult {
+    /// Converts a TMDB multi search result into a [`FrontendSearchResult`]. Returns `None` for
+    /// kinds of media Cinescore does not cover.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`MultiSearchResult`] to convert from
+    pub fn from_multi(value: MultiSearchResult) -> Option<Self> {
+        match value {
+            MultiSearchResult::Movie(movie) => Some(Self::Movie(MovieListing::from(movie))),
+            MultiSearchResult::Person(person) => {
+                Some(Self::Person(FrontendPersonListing::from(person)))
+            }
+            MultiSearchResult::Other => None,
+        }
+    }
+}
+
+impl FrontendSearchResults {
+    /// Creates a page of search results.
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - The search results on the page
+    /// * `page` - The current page of results
+    /// * `total_pages` - The total number of pages available
+    /// * `total_results` - The total number of results available
+    pub fn new(
+        results: Vec<FrontendSearchResult>,
+        page: u64,
+        total_pages: u64,
+        total_results: u64,
+    ) -> Self {
+        Self {
+            results,
+            page,
+            total_pages,
+            total_results,
+            totals_are_upper_bounds: false,
+        }
+    }
+
+    /// The IDs of the movies in the results.
+    pub fn movie_ids(&self) -> Vec<u64> {
+        self.results
+            .iter()
+            .filter_map(|result| match result {
+                FrontendSearchResult::Movie(movie) => Some(movie.id()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Removes every movie from the results that `keep` returns `false` for. Results that are
+    /// not movies are always kept. The totals are left as they are, since results on other pages
+    /// are not checked, so the results are marked as having totals that are upper bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - Called with the ID of each movie, returning whether to keep it
+    pub fn retain_movies<F: FnMut(u64) -> bool>(&mut self, mut keep: F) {
+        self.results.retain(|result| match result {
+            FrontendSearchResult::Movie(movie) => keep(movie.id()),
+            _ => true,
+        });
+        self.totals_are_upper_bounds = true;
+    }
+
+    /// Shortens the results to at most `limit` entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The most results to keep
+    pub fn truncate(&mut self, limit: usize) {
+        self.results.truncate(limit);
+    }
+}
+
+impl ResolveGenres for FrontendSearchResults {
+    fn resolve_genres(&mut self, genres: &HashMap<u64, String>) {
+        for result in &mut self.results {
+            if let FrontendSearchResult::Movie(movie) = result {
+                movie.resolve_genres(genres);
+            }
+        }
+    }
+}
+
+impl Localise for FrontendSearchResults {
+    fn localise(&mut self, language: &str) {
+        for result in &mut self.results {
+            match result {
+                FrontendSearchResult::Movie(movie) => movie.localise(language),
+                FrontendSearchResult::Person(person) => person.localise(language),
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Represents a single result of a search over Cinescore's own content, tagged with the kind of
+/// thing it is, formatted for the frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FrontendCommunitySearchResult {
+    /// A Cinescore user, matched by their username
+    User(FrontendCommunityUserMatch),
+}
+
+/// Represents a Cinescore user matching a community search, formatted for the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrontendCommunityUserMatch {
+    /// The matching user.
+    #[serde(flatten)]
+    pub user: FrontendUserListing,
+    /// The matching text, split into highlighted and plain parts.
+    pub snippet: Vec<FrontendHighlight>,
+    /// How closely the user matches the search. Higher is better.
+    pub rank: f32,
+}
+
+/// Represents part of a search snippet, formatted for the frontend. Snippets are split into parts
+/// rather than marked up so that user content never has to be rendered as HTML.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FrontendHighlight {
+    /// The text of this part of the snippet.
+    pub text: String,
+    /// Whether this part matched the search.
+    pub highlighted: bool,
+}
+
+/// Represents a page of community search results, formatted for the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrontendCommunitySearchResults {
+    /// The search results, best match first
+    results: Vec<FrontendCommunitySearchResult>,
+    /// The current page of results.
+    page: u64,
+    /// The total number of pages available.
+    #[serde(rename = "totalPages")]
+    total_pages: u64,
+    /// The total number of results available.
+    #[serde(rename = "totalResults")]
+    total_results: u64,
+}
+
+impl FrontendHighlight {
+    /// Splits a snippet produced by Postgres' `ts_headline` into plain and highlighted parts.
+    ///
+    /// # Arguments
+    ///
+    /// * `headline` - The snippet, with matches wrapped in `start` and `stop`
+    /// * `start` - The marker placed before each match
+    /// * `stop` - The marker placed after each match
+    pub fn split_headline(headline: &str, start: char, stop: char) -> Vec<Self> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut highlighted = false;
+
+        for c in headline.chars() {
+            let toggle = (c == start && !highlighted) || (c == stop && highlighted);
+
+            if !toggle {
+                text.push(c);
+                continue;
+            }
+
+            if !text.is_empty() {
+                parts.push(Self {
+                    text: std::mem::take(&mut text),
+                    highlighted,
+                });
+            }
+            highlighted = !highlighted;
+        }
+
+        if !text.is_empty() {
+            parts.push(Self { text, highlighted });
+        }
+
+        parts
+    }
+}
+
+impl FrontendCommunitySearchResults {
+    /// Creates a page of community search results.
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - The search results on the page
+    /// * `page` - The current page of results
+    /// * `page_size` - The number of results on a full page
+    /// * `total_results` - The total number of results available
+    pub fn new(
+        results: Vec<FrontendCommunitySearchResult>,
+        page: u64,
+        page_size: u64,
+        total_results: u64,
+    ) -> Self {
+        Self {
+            results,
+            page,
+            total_pages: total_results.div_ceil(page_size),
+            total_results,
+        }
+    }
+}
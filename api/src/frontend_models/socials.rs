@@ -2,20 +2,40 @@ use serde::Serialize;
 
 use crate::tmdb::models::socials::Socials;
 
-/// This struct contains information on a specific person's social media IDs
+/// This struct contains links to a person's or movie's profiles on social media and other
+/// websites. Each link is a full URL.
 #[allow(clippy::doc_markdown)]
 #[derive(Debug, Serialize)]
 pub struct FrontendSocials {
-    /// The person's IMDb ID, if available.
+    /// The IMDb page, if available.
     imdb: Option<String>,
-    /// The person's Facebook username or ID, if available.
+    /// The Wikidata item, if available.
+    wikidata: Option<String>,
+    /// The Facebook page, if available.
     facebook: Option<String>,
-    /// The person's Instagram username, if available.
+    /// The Instagram profile, if available.
     instagram: Option<String>,
-    /// The person's TikTok username, if available.
+    /// The TikTok profile, if available.
     tiktok: Option<String>,
-    /// The person's Twitter username, if available.
+    /// The Twitter profile, if available.
     twitter: Option<String>,
+    /// The YouTube channel, if available.
+    youtube: Option<String>,
+}
+
+/// Builds a profile URL from an ID, if there is one. TMDB returns an empty string rather than
+/// `null` for some missing IDs, and some usernames are saved with a leading `@`, so both are
+/// handled here.
+///
+/// # Arguments
+///
+/// * `id` - The ID or username on the site
+/// * `url` - Builds the URL from a cleaned up ID
+fn profile_url(id: Option<String>, url: impl FnOnce(&str) -> String) -> Option<String> {
+    id.as_deref()
+        .map(|id| id.trim().trim_start_matches('@'))
+        .filter(|id| !id.is_empty())
+        .map(url)
 }
 
 impl From<Socials> for FrontendSocials {
@@ -27,14 +47,39 @@ impl From<Socials> for FrontendSocials {
     ///
     /// # Returns
     ///
-    /// A new [`FrontendSocials`] instance with all fields mapped from the source.
+    /// A new [`FrontendSocials`] instance with a URL built for every ID in the source.
     fn from(value: Socials) -> Self {
         Self {
-            imdb: value.imdb_id,
-            facebook: value.facebook_id,
-            instagram: value.instagram_id,
-            tiktok: value.tiktok_id,
-            twitter: value.twitter_id,
+            // IMDb IDs start with `tt` for titles and `nm` for names
+            imdb: profile_url(value.imdb_id, |id| {
+                let kind = if id.starts_with("tt") {
+                    "title"
+                } else {
+                    "name"
+                };
+                format!("https://www.imdb.com/{}/{}/", kind, id)
+            }),
+            wikidata: profile_url(value.wikidata_id, |id| {
+                format!("https://www.wikidata.org/wiki/{}", id)
+            }),
+            facebook: profile_url(value.facebook_id, |id| {
+                format!("https://www.facebook.com/{}", id)
+            }),
+            instagram: profile_url(value.instagram_id, |id| {
+                format!("https://www.instagram.com/{}/", id)
+            }),
+            tiktok: profile_url(value.tiktok_id, |id| {
+                format!("https://www.tiktok.com/@{}", id)
+            }),
+            twitter: profile_url(value.twitter_id, |id| format!("https://x.com/{}", id)),
+            // channel IDs start with `UC`, anything else is a handle
+            youtube: profile_url(value.youtube_id, |id| {
+                if id.starts_with("UC") && id.len() == 24 {
+                    format!("https://www.youtube.com/channel/{}", id)
+                } else {
+                    format!("https://www.youtube.com/@{}", id)
+                }
+            }),
         }
     }
 }
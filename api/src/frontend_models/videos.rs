@@ -0,0 +1,114 @@
+use std::cmp::Reverse;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::tmdb::models::video::{Video, VideoList};
+
+/// Represents a trailer or teaser formatted for the frontend.
+#[allow(clippy::doc_markdown)]
+#[derive(Debug, Serialize)]
+pub struct FrontendTrailer {
+    /// Name of the trailer.
+    name: String,
+    /// The site hosting the trailer (e.g., YouTube).
+    site: String,
+    /// The kind of trailer (Trailer or Teaser).
+    #[serde(rename = "type")]
+    kind: String,
+    /// Whether the trailer was published by the studio behind the movie.
+    official: bool,
+    /// ISO 639-1 code of the trailer's language.
+    language: String,
+    /// When the trailer was published.
+    #[serde(rename = "publishedAt")]
+    published_at: DateTime<Utc>,
+    /// URL to watch the trailer on the hosting site.
+    url: String,
+    /// URL to embed the trailer in a page.
+    #[serde(rename = "embedUrl")]
+    embed_url: String,
+}
+
+/// Builds the watch and embed URLs for a video, if it is hosted on a supported site.
+///
+/// # Arguments
+///
+/// * `site` - The site hosting the video, as named by TMDB.
+/// * `key` - The video's key on the site.
+fn video_urls(site: &str, key: &str) -> Option<(String, String)> {
+    match site {
+        "YouTube" => Some((
+            format!("https://www.youtube.com/watch?v={}", key),
+            format!("https://www.youtube-nocookie.com/embed/{}", key),
+        )),
+        "Vimeo" => Some((
+            format!("https://vimeo.com/{}", key),
+            format!("https://player.vimeo.com/video/{}", key),
+        )),
+        _ => None,
+    }
+}
+
+impl FrontendTrailer {
+    /// Converts a [`Video`] into a [`FrontendTrailer`], if it is a trailer or teaser hosted on a
+    /// supported site.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`Video`] to convert from
+    fn from_video(value: Video) -> Option<Self> {
+        if value.video_type != "Trailer" && value.video_type != "Teaser" {
+            return None;
+        }
+
+        let (url, embed_url) = video_urls(&value.site, &value.key)?;
+
+        Some(Self {
+            name: value.name,
+            site: value.site,
+            kind: value.video_type,
+            official: value.official,
+            language: value.iso_639_1,
+            published_at: value.published_at,
+            url,
+            embed_url,
+        })
+    }
+}
+
+/// Converts a [`VideoList`] into a list of trailers, best first. See [`sort_trailers`].
+///
+/// # Arguments
+///
+/// * `videos` - The source [`VideoList`] to convert from
+pub fn trailers_from_videos(videos: VideoList) -> Vec<FrontendTrailer> {
+    let mut trailers: Vec<FrontendTrailer> = videos
+        .results
+        .into_iter()
+        .filter_map(FrontendTrailer::from_video)
+        .collect();
+
+    sort_trailers(&mut trailers, None);
+    trailers
+}
+
+/// Sorts trailers so that the best one comes first. Official trailers are preferred, then ones
+/// in the requested language, then full trailers over teasers, then the most recently published.
+///
+/// # Arguments
+///
+/// * `trailers` - The trailers to sort
+/// * `language` - The preferred language (e.g., `en` or `en-US`), if any
+pub fn sort_trailers(trailers: &mut [FrontendTrailer], language: Option<&str>) {
+    let language = language.map(|l| l.split('-').next().unwrap_or(l));
+
+    trailers.sort_by_key(|trailer| {
+        (
+            Reverse(trailer.official),
+            Reverse(language.is_some_and(|l| l == trailer.language)),
+            trailer.kind != "Trailer",
+            Reverse(trailer.published_at),
+        )
+    });
+}
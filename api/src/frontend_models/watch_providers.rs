@@ -0,0 +1,118 @@
+use serde::Serialize;
+
+use crate::tmdb::models::watch_providers::{
+    WatchProvider, WatchProviderAvailability, WatchProviderList,
+};
+
+use super::images::ImageKind;
+
+/// Represents a watch provider, such as a streaming service, formatted for the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrontendWatchProvider {
+    /// Unique identifier for the provider.
+    id: u64,
+    /// Name of the provider.
+    name: String,
+    /// URL to the provider's logo.
+    #[serde(rename = "logoUrl")]
+    logo_url: String,
+    /// `srcset` of every width the provider's logo comes in, if there is one.
+    #[serde(rename = "logoSrcset")]
+    logo_srcset: Option<String>,
+}
+
+/// Represents where a movie can be watched in a single region, formatted for the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct FrontendWatchProviders {
+    /// The ISO 3166-1 code of the region.
+    region: String,
+    /// A link to TMDB's page listing all the ways to watch the movie.
+    link: String,
+    /// Providers offering the movie as part of a subscription.
+    flatrate: Vec<FrontendWatchProvider>,
+    /// Providers offering the movie to rent.
+    rent: Vec<FrontendWatchProvider>,
+    /// Providers offering the movie to buy.
+    buy: Vec<FrontendWatchProvider>,
+    /// Providers offering the movie for free.
+    free: Vec<FrontendWatchProvider>,
+    /// Providers offering the movie for free with ads.
+    ads: Vec<FrontendWatchProvider>,
+}
+
+/// Represents the list of watch providers available in a region, formatted for the frontend.
+#[derive(Debug, Serialize)]
+pub struct FrontendWatchProviderList {
+    /// The watch providers, in the order TMDB suggests displaying them.
+    providers: Vec<FrontendWatchProvider>,
+}
+
+impl From<WatchProvider> for FrontendWatchProvider {
+    /// Converts a [`WatchProvider`] into a [`FrontendWatchProvider`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`WatchProvider`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendWatchProvider`] instance with all fields mapped from the source.
+    fn from(value: WatchProvider) -> Self {
+        Self {
+            id: value.provider_id,
+            name: value.provider_name,
+            logo_url: ImageKind::Logo.url(value.logo_path.as_deref()),
+            logo_srcset: ImageKind::Logo.srcset(value.logo_path.as_deref()),
+        }
+    }
+}
+
+/// Converts a list of [`WatchProvider`]s into [`FrontendWatchProvider`]s, in display order.
+///
+/// # Arguments
+///
+/// * `providers` - The source providers to convert from
+fn convert_providers(mut providers: Vec<WatchProvider>) -> Vec<FrontendWatchProvider> {
+    providers.sort_by_key(|provider| provider.display_priority);
+    providers
+        .into_iter()
+        .map(FrontendWatchProvider::from)
+        .collect()
+}
+
+impl FrontendWatchProviders {
+    /// Creates a [`FrontendWatchProviders`] from a movie's availability in a single region.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The ISO 3166-1 code of the region
+    /// * `value` - The movie's availability in the region
+    pub fn new(region: String, value: WatchProviderAvailability) -> Self {
+        Self {
+            region,
+            link: value.link,
+            flatrate: convert_providers(value.flatrate),
+            rent: convert_providers(value.rent),
+            buy: convert_providers(value.buy),
+            free: convert_providers(value.free),
+            ads: convert_providers(value.ads),
+        }
+    }
+}
+
+impl From<WatchProviderList> for FrontendWatchProviderList {
+    /// Converts a [`WatchProviderList`] into a [`FrontendWatchProviderList`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`WatchProviderList`] to convert from
+    ///
+    /// # Returns
+    ///
+    /// A new [`FrontendWatchProviderList`] instance with all fields mapped from the source.
+    fn from(value: WatchProviderList) -> Self {
+        Self {
+            providers: convert_providers(value.results),
+        }
+    }
+}
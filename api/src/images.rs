@@ -0,0 +1,525 @@
+//! Routes for serving images. TMDB images are proxied through the API and cached on disk, so
+//! browsers never load them from TMDB directly, and placeholders are shown in place of images
+//! TMDB does not have.
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        LazyLock,
+    },
+    time::{Duration, SystemTime},
+};
+
+use axum::{
+    extract::Path,
+    http::{
+        header::{ACCEPT, CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY},
+        HeaderMap, HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use tokio::sync::{Mutex, OnceCell};
+
+use crate::{
+    frontend_models::images::ImageKind,
+    tmdb::{cache::TtlCache, queries::configuration::image_configuration},
+};
+
+/// The `Cache-Control` header for responses that never change.
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+/// The `Cache-Control` header for placeholders served in place of an image that could not be
+/// fetched. These are only cached briefly, so the image is tried again soon.
+const FALLBACK: &str = "public, max-age=300";
+
+/// How long an image that could not be fetched is served as a placeholder before TMDB is tried
+/// again. Matches the max-age of [`FALLBACK`].
+const FAILED_FETCH_TTL: Duration = Duration::from_secs(300);
+
+/// The most failed fetches remembered at once.
+const FAILED_FETCHES_MAX_ENTRIES: usize = 10_000;
+
+/// How long to wait for TMDB to send an image.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// The directory images are cached in when `IMAGE_CACHE_DIR` is not set.
+const DEFAULT_CACHE_DIR: &str = "image_cache";
+
+/// The size the cache may grow to when `IMAGE_CACHE_MAX_MB` is not set, in megabytes.
+const DEFAULT_CACHE_MAX_MB: u64 = 1024;
+
+/// The HTTP client shared by every image fetch, so connections to TMDB are reused.
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+});
+
+/// Images that recently could not be fetched from TMDB, keyed by size and file name, so
+/// requests for missing images are not all passed on to TMDB.
+static FAILED_FETCHES: LazyLock<TtlCache<(String, String), ()>> =
+    LazyLock::new(|| TtlCache::new(FAILED_FETCH_TTL, FAILED_FETCHES_MAX_ENTRIES));
+
+/// The on-disk image cache, configured from the environment.
+static IMAGE_CACHE: LazyLock<ImageCache> = LazyLock::new(ImageCache::from_env);
+
+/// A directory of cached images, capped to a total size. Once the cap is exceeded the least
+/// recently used images are removed. Reading an image bumps its modification time, which is what
+/// recency is judged by.
+struct ImageCache {
+    /// The directory images are stored in.
+    dir: PathBuf,
+    /// The total size the cached images may take up, in bytes.
+    max_bytes: u64,
+    /// The total size an eviction shrinks the cache to, in bytes. This is below the cap so the
+    /// directory is not rescanned on every image stored once the cache is full.
+    target_bytes: u64,
+    /// The total size of the cached images in bytes. Counted from the directory on first use,
+    /// kept up to date as images are stored, and recounted whenever an eviction runs.
+    size: OnceCell<AtomicU64>,
+    /// Held while evicting, so only one eviction runs at a time.
+    eviction: Mutex<()>,
+}
+
+impl ImageCache {
+    /// Creates the cache from the `IMAGE_CACHE_DIR` and `IMAGE_CACHE_MAX_MB` environment
+    /// variables, falling back to the defaults if they are not set or invalid.
+    fn from_env() -> Self {
+        let dir = std::env::var("IMAGE_CACHE_DIR").unwrap_or_else(|_| DEFAULT_CACHE_DIR.into());
+        let max_mb = match std::env::var("IMAGE_CACHE_MAX_MB") {
+            Ok(v) => v.parse().unwrap_or_else(|_| {
+                tracing::warn!(
+                    "Invalid IMAGE_CACHE_MAX_MB `{}`, using {}",
+                    v,
+                    DEFAULT_CACHE_MAX_MB
+                );
+                DEFAULT_CACHE_MAX_MB
+            }),
+            Err(_) => DEFAULT_CACHE_MAX_MB,
+        };
+        let max_bytes = max_mb.saturating_mul(1024 * 1024);
+
+        ImageCache {
+            dir: PathBuf::from(dir),
+            max_bytes,
+            target_bytes: max_bytes / 10 * 9,
+            size: OnceCell::new(),
+            eviction: Mutex::new(()),
+        }
+    }
+
+    /// Reads a cached image, marking it as recently used. Returns `None` if it is not cached.
+    ///
+    /// # Arguments
+    /// * `key` - The cache key of the image.
+    async fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let path = self.dir.join(key);
+        let bytes = tokio::fs::read(&path).await.ok()?;
+
+        if let Ok(file) = tokio::fs::OpenOptions::new().write(true).open(&path).await {
+            let _ = file.into_std().await.set_modified(SystemTime::now());
+        }
+
+        Some(bytes)
+    }
+
+    /// Stores an image, then evicts the least recently used images if the cache is over its cap.
+    /// The image is written to a temporary file and renamed into place, so a partly written
+    /// image is never served.
+    ///
+    /// # Arguments
+    /// * `key` - The cache key of the image.
+    /// * `bytes` - The image.
+    async fn put(&self, key: &str, bytes: &[u8]) {
+        let size = self.size().await;
+        let path = self.dir.join(key);
+        let temp = self
+            .dir
+            .join(format!("{}.{}.tmp", key, uuid::Uuid::new_v4()));
+        let result = async {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            tokio::fs::write(&temp, bytes).await?;
+            let replaced = tokio::fs::metadata(&path).await.map_or(0, |m| m.len());
+            tokio::fs::rename(&temp, &path).await?;
+            Ok::<_, std::io::Error>(replaced)
+        }
+        .await;
+
+        let replaced = match result {
+            Ok(replaced) => replaced,
+            Err(e) => {
+                tracing::error!("Failed to cache image {}: {}", key, e);
+                let _ = tokio::fs::remove_file(&temp).await;
+                return;
+            }
+        };
+
+        let added = bytes.len() as u64;
+        let resize = |total: u64| total.saturating_add(added).saturating_sub(replaced);
+        let total = match size.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |total| {
+            Some(resize(total))
+        }) {
+            Ok(previous) | Err(previous) => resize(previous),
+        };
+
+        if total > self.max_bytes {
+            self.evict(size).await;
+        }
+    }
+
+    /// Returns the running total size of the cache, counting it from the directory on first use.
+    async fn size(&self) -> &AtomicU64 {
+        self.size
+            .get_or_init(|| async {
+                let total = self.scan().await.iter().map(|(_, len, _)| len).sum();
+                AtomicU64::new(total)
+            })
+            .await
+    }
+
+    /// Lists the cached images with their modification times and sizes. Temporary files of images
+    /// still being written are skipped.
+    async fn scan(&self) -> Vec<(SystemTime, u64, PathBuf)> {
+        let mut entries = Vec::new();
+        let Ok(mut dir) = tokio::fs::read_dir(&self.dir).await else {
+            return entries;
+        };
+
+        while let Ok(Some(entry)) = dir.next_entry().await {
+            let path = entry.path();
+            if path.extension().is_some_and(|extension| extension == "tmp") {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata().await else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            entries.push((modified, metadata.len(), path));
+        }
+
+        entries
+    }
+
+    /// Removes the least recently used images until the cache is back under its target size,
+    /// then stores the recounted size. Does nothing if another eviction is already running.
+    ///
+    /// # Arguments
+    /// * `size` - The running total size of the cache.
+    async fn evict(&self, size: &AtomicU64) {
+        let Ok(_guard) = self.eviction.try_lock() else {
+            return;
+        };
+
+        let mut entries = self.scan().await;
+        let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+
+        if total > self.max_bytes {
+            entries.sort_unstable_by_key(|(modified, _, _)| *modified);
+
+            for (_, len, path) in entries {
+                if total <= self.target_bytes {
+                    break;
+                }
+                match tokio::fs::remove_file(&path).await {
+                    Ok(()) => total = total.saturating_sub(len),
+                    Err(e) => tracing::warn!("Failed to evict cached image {:?}: {}", path, e),
+                }
+            }
+
+            tracing::debug!("Evicted cached images, cache is now {} bytes", total);
+        }
+
+        size.store(total, Ordering::Relaxed);
+    }
+}
+
+/// Serves a TMDB image through the API. Images are fetched from TMDB once and then served from
+/// the disk cache. As an image at a given size never changes, responses may be cached forever
+/// and are tagged so browsers can revalidate them cheaply. When built with the `webp` feature,
+/// images are transcoded to WebP for browsers that accept it, if that makes them smaller. If the
+/// image cannot be fetched, the placeholder for its kind is served instead.
+///
+/// # Arguments
+/// * `kind` - The kind of image, which decides the sizes available and the placeholder.
+/// * `size` - The size of the image (e.g., `w342` or `original`).
+/// * `file` - The file name of the image, as in the path returned by TMDB.
+/// * `headers` - The request headers, used for revalidation and content negotiation.
+pub async fn fetch_image(
+    Path((kind, size, file)): Path<(ImageKind, String, String)>,
+    headers: HeaderMap,
+) -> Response {
+    if !kind.has_size(&size) || !is_valid_file_name(&file) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    let webp = accepts_webp(&headers);
+    let key = cache_key(&size, &file, webp);
+    let etag = format!("\"{}\"", key);
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(CACHE_CONTROL, HeaderValue::from_static(IMMUTABLE));
+    if cfg!(feature = "webp") {
+        response_headers.insert(VARY, HeaderValue::from_static("Accept"));
+    }
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response_headers.insert(ETAG, value);
+    }
+
+    let not_modified = headers
+        .get(IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.split(',')
+                .any(|tag| tag.trim() == etag || tag.trim() == "*")
+        });
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, response_headers).into_response();
+    }
+
+    let bytes = match IMAGE_CACHE.get(&key).await {
+        Some(bytes) => bytes,
+        None => match fetch_and_cache(&key, &size, &file, webp).await {
+            Some(bytes) => bytes,
+            None => {
+                return (
+                    [(CONTENT_TYPE, "image/svg+xml"), (CACHE_CONTROL, FALLBACK)],
+                    placeholder(kind),
+                )
+                    .into_response();
+            }
+        },
+    };
+
+    response_headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type(&bytes)));
+
+    (response_headers, bytes).into_response()
+}
+
+/// Fetches an image from TMDB, transcodes it if asked to and stores it in the cache. Returns
+/// `None` if the image could not be fetched, or could not be fetched within the last
+/// [`FAILED_FETCH_TTL`].
+///
+/// # Arguments
+/// * `key` - The cache key of the image.
+/// * `size` - The size of the image.
+/// * `file` - The file name of the image.
+/// * `webp` - Whether to transcode the image to WebP.
+async fn fetch_and_cache(key: &str, size: &str, file: &str, webp: bool) -> Option<Vec<u8>> {
+    let failure_key = (size.to_string(), file.to_string());
+    if FAILED_FETCHES.get(&failure_key).is_some() {
+        return None;
+    }
+
+    let bytes = match fetch_from_tmdb(size, file).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Failed to fetch image {}/{}: {}", size, file, e);
+            FAILED_FETCHES.insert(failure_key, ());
+            return None;
+        }
+    };
+    let bytes = if webp { transcode(bytes).await? } else { bytes };
+
+    IMAGE_CACHE.put(key, &bytes).await;
+
+    Some(bytes)
+}
+
+/// Fetches an image from TMDB.
+///
+/// # Arguments
+/// * `size` - The size of the image.
+/// * `file` - The file name of the image.
+///
+/// # Errors
+/// Returns a [`reqwest::Error`] if the request fails or TMDB does not return the image.
+async fn fetch_from_tmdb(size: &str, file: &str) -> Result<Vec<u8>, reqwest::Error> {
+    let url = format!("{}{}/{}", image_configuration().secure_base_url, size, file);
+    tracing::debug!("Fetching image from url ({})", url);
+
+    let response = HTTP_CLIENT.get(url).send().await?.error_for_status()?;
+
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Checks that a file name looks like one TMDB gives out (e.g., `abc123.jpg`), so nothing else
+/// can be requested from TMDB or written to the cache directory.
+///
+/// # Arguments
+/// * `file` - The file name to check.
+fn is_valid_file_name(file: &str) -> bool {
+    !file.is_empty()
+        && !file.starts_with('.')
+        && file
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-')
+}
+
+/// Builds the cache key for an image, which is also its entity tag. Images never change at a given
+/// size, so the key only depends on what was asked for.
+///
+/// # Arguments
+/// * `size` - The size of the image.
+/// * `file` - The file name of the image.
+/// * `webp` - Whether the image is transcoded to WebP.
+fn cache_key(size: &str, file: &str, webp: bool) -> String {
+    let digest = Sha256::digest(format!("{}/{}/{}", size, file, webp));
+
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Works out the content type of an image from its first few bytes, as cached images may have
+/// been transcoded.
+///
+/// # Arguments
+/// * `bytes` - The image.
+fn content_type(bytes: &[u8]) -> &'static str {
+    match bytes {
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        _ if bytes.starts_with(b"<svg") || bytes.starts_with(b"<?xml") => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Whether the browser accepts WebP images. Always `false` when built without the `webp`
+/// feature.
+///
+/// # Arguments
+/// * `headers` - The request headers.
+fn accepts_webp(headers: &HeaderMap) -> bool {
+    cfg!(feature = "webp")
+        && headers
+            .get(ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains("image/webp"))
+}
+
+/// Transcodes an image to WebP. The original is returned if it cannot be decoded (e.g., SVG
+/// logos) or if the WebP would be larger, as only lossless WebP encoding is available. Returns
+/// `None` if the transcoding task fails.
+///
+/// # Arguments
+/// * `bytes` - The image.
+#[cfg(feature = "webp")]
+async fn transcode(bytes: Vec<u8>) -> Option<Vec<u8>> {
+    let result = tokio::task::spawn_blocking(move || {
+        let webp = image::load_from_memory(&bytes).and_then(|decoded| {
+            let mut webp = std::io::Cursor::new(Vec::new());
+            decoded.write_to(&mut webp, image::ImageFormat::WebP)?;
+            Ok(webp.into_inner())
+        });
+
+        match webp {
+            Ok(webp) if webp.len() < bytes.len() => webp,
+            Ok(_) => bytes,
+            Err(e) => {
+                tracing::debug!("Not transcoding image to WebP: {}", e);
+                bytes
+            }
+        }
+    })
+    .await;
+
+    match result {
+        Ok(bytes) => Some(bytes),
+        Err(e) => {
+            tracing::error!("Image transcoding task failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Returns the image unchanged, as WebP transcoding is not built in.
+///
+/// # Arguments
+/// * `bytes` - The image.
+#[cfg(not(feature = "webp"))]
+async fn transcode(bytes: Vec<u8>) -> Option<Vec<u8>> {
+    Some(bytes)
+}
+
+/// Placeholder for a missing poster: a film frame in a 2:3 portrait.
+const POSTER_PLACEHOLDER: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 500 750"><rect width="500" height="750" fill="#2a2a2e"/><rect x="170" y="295" width="160" height="160" rx="12" fill="none" stroke="#55555c" stroke-width="14"/><circle cx="250" cy="375" r="34" fill="#55555c"/></svg>"##;
+
+/// Placeholder for a missing backdrop: a play button in a 16:9 frame.
+const BACKDROP_PLACEHOLDER: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1280 720"><rect width="1280" height="720" fill="#2a2a2e"/><path d="M590 300 L700 360 L590 420 Z" fill="#55555c"/></svg>"##;
+
+/// Placeholder for a missing profile picture: a head and shoulders silhouette in a 2:3 portrait.
+const PROFILE_PLACEHOLDER: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 185 278"><rect width="185" height="278" fill="#2a2a2e"/><circle cx="92.5" cy="110" r="38" fill="#55555c"/><path d="M27 238 C27 185 158 185 158 238 Z" fill="#55555c"/></svg>"##;
+
+/// Placeholder for a missing logo: an empty square.
+const LOGO_PLACEHOLDER: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 154 154"><rect width="154" height="154" rx="16" fill="#2a2a2e"/><rect x="47" y="47" width="60" height="60" rx="8" fill="none" stroke="#55555c" stroke-width="8"/></svg>"##;
+
+/// Serves the placeholder for a kind of image. Placeholders never change, so browsers may cache
+/// them for as long as they like.
+///
+/// # Arguments
+/// * `kind` - The kind of image the placeholder stands in for.
+pub async fn fetch_placeholder(Path(kind): Path<ImageKind>) -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, "image/svg+xml"), (CACHE_CONTROL, IMMUTABLE)],
+        placeholder(kind),
+    )
+}
+
+/// The placeholder SVG for a kind of image.
+///
+/// # Arguments
+/// * `kind` - The kind of image the placeholder stands in for.
+fn placeholder(kind: ImageKind) -> &'static str {
+    match kind {
+        ImageKind::Poster => POSTER_PLACEHOLDER,
+        ImageKind::Backdrop => BACKDROP_PLACEHOLDER,
+        ImageKind::Profile => PROFILE_PLACEHOLDER,
+        ImageKind::Logo => LOGO_PLACEHOLDER,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_tmdb_file_names() {
+        assert!(is_valid_file_name("kqjL17yufvn9OVLyXYpvtyrFfak.jpg"));
+        assert!(is_valid_file_name("a-b_c.svg"));
+    }
+
+    #[test]
+    fn rejects_parent_directories() {
+        assert!(!is_valid_file_name(".."));
+        assert!(!is_valid_file_name("..jpg"));
+    }
+
+    #[test]
+    fn rejects_separators() {
+        assert!(!is_valid_file_name("a/b.jpg"));
+        assert!(!is_valid_file_name("../secret"));
+        assert!(!is_valid_file_name("a\\b.jpg"));
+    }
+
+    #[test]
+    fn rejects_encoded_separators() {
+        assert!(!is_valid_file_name("..%2Fsecret"));
+        assert!(!is_valid_file_name("a%2fb.jpg"));
+        assert!(!is_valid_file_name("a%5Cb.jpg"));
+    }
+
+    #[test]
+    fn rejects_leading_dots() {
+        assert!(!is_valid_file_name(".env"));
+        assert!(!is_valid_file_name(".jpg"));
+    }
+
+    #[test]
+    fn rejects_empty_names() {
+        assert!(!is_valid_file_name(""));
+    }
+}
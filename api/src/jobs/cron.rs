@@ -0,0 +1,371 @@
+//! A parser for the standard five field cron expressions used to schedule recurring jobs.
+//!
+//! Expressions are made up of minute, hour, day of month, month and day of week fields, and
+//! every time is in UTC. Each field can be `*`, a number, a range (`1-5`), a step over either
+//! (`*/15`, `0-30/10`) or a comma separated list of any of these. As with cron, if both the day of
+//! month and day of week are limited, a day matching either of them matches. A field starting
+//! with `*`, including a step such as `*/2`, does not count as limited.
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, TimeDelta, Timelike, Utc};
+
+/// The most years ahead the next run of a schedule is looked for, so that schedules which can
+/// never run (e.g., the 31st of February) give up rather than looping forever.
+const MAX_YEARS_AHEAD: i32 = 5;
+
+/// Represents errors that can be encountered while parsing a cron expression.
+#[derive(Debug, thiserror::Error)]
+pub enum CronError {
+    /// The expression does not have exactly five fields
+    #[error("Expected 5 fields in cron expression, found {0}")]
+    FieldCount(usize),
+    /// A field could not be parsed, or is outside the range it allows
+    #[error("Invalid {field} field `{value}` in cron expression")]
+    InvalidField {
+        /// The name of the field
+        field: &'static str,
+        /// The value of the field
+        value: String,
+    },
+}
+
+/// A field of a cron expression, stored as a bit set of the values it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Field {
+    /// Bit `n` is set if the field matches the value `n`.
+    values: u64,
+    /// Whether the field started with `*`, which matters when matching days.
+    any: bool,
+}
+
+impl Field {
+    /// Parses a field of a cron expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field, used in errors
+    /// * `value` - The text of the field
+    /// * `min` - The smallest value the field allows
+    /// * `max` - The largest value the field allows
+    fn parse(name: &'static str, value: &str, min: u32, max: u32) -> Result<Self, CronError> {
+        let invalid = || CronError::InvalidField {
+            field: name,
+            value: value.to_owned(),
+        };
+        let number = |text: &str| {
+            text.parse::<u32>()
+                .ok()
+                .filter(|n| (min..=max).contains(n))
+                .ok_or_else(invalid)
+        };
+
+        let mut values = 0;
+
+        for part in value.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+                None => (part, 1),
+            };
+
+            if step == 0 {
+                return Err(invalid());
+            }
+
+            let (start, end) = match range.split_once('-') {
+                _ if range == "*" => (min, max),
+                Some((start, end)) => (number(start)?, number(end)?),
+                None => {
+                    let start = number(range)?;
+                    // a step on a single number runs from it to the end of the range
+                    (start, if step > 1 { max } else { start })
+                }
+            };
+
+            if start > end {
+                return Err(invalid());
+            }
+
+            for n in (start..=end).step_by(step as usize) {
+                values |= 1 << n;
+            }
+        }
+
+        Ok(Self {
+            values,
+            any: value.starts_with('*'),
+        })
+    }
+
+    /// Whether the field matches a value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to check
+    fn matches(&self, value: u32) -> bool {
+        self.values & (1 << value) != 0
+    }
+}
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    /// The minutes of the hour, from 0 to 59.
+    minute: Field,
+    /// The hours of the day, from 0 to 23.
+    hour: Field,
+    /// The days of the month, from 1 to 31.
+    day_of_month: Field,
+    /// The months of the year, from 1 to 12.
+    month: Field,
+    /// The days of the week, from 0 (Sunday) to 6. 7 is also Sunday.
+    day_of_week: Field,
+    /// The original expression.
+    expression: String,
+}
+
+impl FromStr for Cron {
+    type Err = CronError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(CronError::FieldCount(fields.len()));
+        };
+
+        let mut day_of_week = Field::parse("day of week", day_of_week, 0, 7)?;
+        if day_of_week.matches(7) {
+            day_of_week.values |= 1;
+        }
+
+        Ok(Self {
+            minute: Field::parse("minute", minute, 0, 59)?,
+            hour: Field::parse("hour", hour, 0, 23)?,
+            day_of_month: Field::parse("day of month", day_of_month, 1, 31)?,
+            month: Field::parse("month", month, 1, 12)?,
+            day_of_week,
+            expression: expression.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for Cron {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+impl Cron {
+    /// Whether the schedule runs on a day.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The day to check
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = self.day_of_month.matches(date.day());
+        let day_of_week = self
+            .day_of_week
+            .matches(date.weekday().num_days_from_sunday());
+
+        match (self.day_of_month.any, self.day_of_week.any) {
+            (false, false) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+
+    /// Finds the next time the schedule runs, strictly after a given time. Returns `None` if the
+    /// schedule does not run in the next few years.
+    ///
+    /// # Arguments
+    ///
+    /// * `after` - The time to look for the next run after
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut time = after.with_second(0)?.with_nanosecond(0)? + TimeDelta::minutes(1);
+        let give_up = after.year() + MAX_YEARS_AHEAD;
+
+        while time.year() <= give_up {
+            let date = time.date_naive();
+
+            if !self.month.matches(time.month()) {
+                let first_of_month = date.with_day(1)?.checked_add_months(Months::new(1))?;
+                time = first_of_month.and_hms_opt(0, 0, 0)?.and_utc();
+            } else if !self.matches_day(date) {
+                time = date
+                    .checked_add_days(Days::new(1))?
+                    .and_hms_opt(0, 0, 0)?
+                    .and_utc();
+            } else if !self.hour.matches(time.hour()) {
+                time = time.with_minute(0)? + TimeDelta::hours(1);
+            } else if !self.minute.matches(time.minute()) {
+                time += TimeDelta::minutes(1);
+            } else {
+                return Some(time);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a time in RFC 3339 format.
+    fn time(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    /// Finds the next run of an expression after a time, in RFC 3339 format.
+    fn next(expression: &str, after: &str) -> Option<String> {
+        let cron: Cron = expression.parse().unwrap();
+        cron.next_after(time(after))
+            .map(|next| next.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+    }
+
+    #[test]
+    fn every_minute() {
+        assert_eq!(
+            next("* * * * *", "2026-10-19T02:09:13Z").as_deref(),
+            Some("2026-10-19T02:10:00Z")
+        );
+        // the next run is strictly after the given time
+        assert_eq!(
+            next("* * * * *", "2026-10-19T02:10:00Z").as_deref(),
+            Some("2026-10-19T02:11:00Z")
+        );
+    }
+
+    #[test]
+    fn steps() {
+        assert_eq!(
+            next("*/15 * * * *", "2026-10-19T02:09:13Z").as_deref(),
+            Some("2026-10-19T02:15:00Z")
+        );
+        assert_eq!(
+            next("0 */6 * * *", "2026-10-19T18:00:00Z").as_deref(),
+            Some("2026-10-20T00:00:00Z")
+        );
+        // a step on a single number runs from it to the end of the range
+        assert_eq!(
+            next("50/5 * * * *", "2026-10-19T02:56:00Z").as_deref(),
+            Some("2026-10-19T03:50:00Z")
+        );
+    }
+
+    #[test]
+    fn ranges_and_lists() {
+        // weekdays only, and 2026-10-24 is a Saturday
+        assert_eq!(
+            next("0 9 * * 1-5", "2026-10-23T09:00:00Z").as_deref(),
+            Some("2026-10-26T09:00:00Z")
+        );
+        assert_eq!(
+            next("5,10,40-45/5 * * * *", "2026-10-19T02:10:00Z").as_deref(),
+            Some("2026-10-19T02:40:00Z")
+        );
+        assert_eq!(
+            next("5,10,40-45/5 * * * *", "2026-10-19T02:40:00Z").as_deref(),
+            Some("2026-10-19T02:45:00Z")
+        );
+    }
+
+    #[test]
+    fn sunday_is_zero_or_seven() {
+        // 2026-10-25 is a Sunday
+        for expression in ["0 0 * * 0", "0 0 * * 7"] {
+            assert_eq!(
+                next(expression, "2026-10-19T00:00:00Z").as_deref(),
+                Some("2026-10-25T00:00:00Z")
+            );
+        }
+    }
+
+    #[test]
+    fn day_of_month_or_day_of_week() {
+        // both limited, so either the 13th or a Friday (2026-10-23) matches
+        assert_eq!(
+            next("0 0 13 * 5", "2026-10-19T00:00:00Z").as_deref(),
+            Some("2026-10-23T00:00:00Z")
+        );
+        assert_eq!(
+            next("0 0 13 * 5", "2026-11-12T00:00:00Z").as_deref(),
+            Some("2026-11-13T00:00:00Z")
+        );
+        // only the day of month is limited, so the day of week is ignored
+        assert_eq!(
+            next("0 0 13 * *", "2026-10-19T00:00:00Z").as_deref(),
+            Some("2026-11-13T00:00:00Z")
+        );
+        // a step over `*` does not count as limited, so both must match: the next odd day of
+        // the month that is a Friday (2026-10-23)
+        assert_eq!(
+            next("0 0 */2 * 5", "2026-10-19T00:00:00Z").as_deref(),
+            Some("2026-10-23T00:00:00Z")
+        );
+        assert_eq!(
+            next("0 0 */2 * 5", "2026-10-23T00:00:00Z").as_deref(),
+            Some("2026-11-13T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn month_and_year_rollover() {
+        assert_eq!(
+            next("30 4 * * *", "2026-12-31T05:00:00Z").as_deref(),
+            Some("2027-01-01T04:30:00Z")
+        );
+        assert_eq!(
+            next("0 0 1 * *", "2026-10-19T02:09:13Z").as_deref(),
+            Some("2026-11-01T00:00:00Z")
+        );
+        // months without a 31st are skipped
+        assert_eq!(
+            next("0 0 31 * *", "2026-10-31T00:00:00Z").as_deref(),
+            Some("2026-12-31T00:00:00Z")
+        );
+        assert_eq!(
+            next("0 0 29 2 *", "2026-10-19T00:00:00Z").as_deref(),
+            Some("2028-02-29T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn never_runs() {
+        assert_eq!(next("0 0 31 2 *", "2026-10-19T00:00:00Z"), None);
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        assert!(matches!(
+            "* * * *".parse::<Cron>(),
+            Err(CronError::FieldCount(4))
+        ));
+
+        for expression in [
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+            "1,,2 * * * *",
+        ] {
+            assert!(
+                matches!(
+                    expression.parse::<Cron>(),
+                    Err(CronError::InvalidField { .. })
+                ),
+                "{} should be invalid",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn displays_expression() {
+        let cron: Cron = "*/15 * * * *".parse().unwrap();
+        assert_eq!(cron.to_string(), "*/15 * * * *");
+    }
+}
@@ -0,0 +1,348 @@
+//! Background jobs, run by workers from a queue kept in Postgres.
+//!
+//! Jobs are added to the `jobs` table, either directly ([`enqueue`]) or when a recurring job in
+//! [`SCHEDULES`] is due, and claimed by workers started with [`JobRunner`]. Failed jobs are
+//! retried with exponential backoff. Workers can run inside the API process or on their own with
+//! `cinescore_api worker`, and any number of them can share the queue.
+use std::{fmt, str::FromStr, time::Duration};
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tokio::{sync::watch, task::JoinSet};
+
+use crate::{
+    charts,
+    discover::get_tmdb_client,
+    metadata::{self, MetadataError},
+    recommendations::{self, RecommendationError},
+    tmdb::client::TMDBClient,
+};
+
+use self::{cron::Cron, queue::Job};
+
+mod cron;
+mod queue;
+
+/// How long an idle worker waits before checking the queue again.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often a worker records that it is still running a job.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often recurring jobs are checked, and timed out jobs are queued again.
+const SCHEDULE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Every recurring job, and a cron expression in UTC for when it runs.
+const SCHEDULES: &[(JobKind, &str)] = &[
+    (JobKind::RefreshMetadata, "0 * * * *"),
+    (JobKind::SyncTmdbChanges, "30 4 * * *"),
+    (JobKind::RefreshCharts, "*/15 * * * *"),
+    (JobKind::PrecomputeRecommendations, "0 */6 * * *"),
+];
+
+/// What a job does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    /// Fetch stale movie and person snapshots again (see [`metadata::refresh`])
+    RefreshMetadata,
+    /// Fetch snapshots of movies and people that changed on TMDB again
+    /// (see [`metadata::sync_changes`])
+    SyncTmdbChanges,
+    /// Aggregate every movie's Cinescore ratings into the scores behind the charts (see
+    /// [`charts::refresh_charts`]). Aggregated scores are only kept in the charts, so this is the
+    /// only score aggregation job.
+    RefreshCharts,
+    /// Recompute every user's recommendations (see [`recommendations::precompute_all`])
+    PrecomputeRecommendations,
+}
+
+impl JobKind {
+    /// Every kind of job.
+    const ALL: [JobKind; 4] = [
+        JobKind::RefreshMetadata,
+        JobKind::SyncTmdbChanges,
+        JobKind::RefreshCharts,
+        JobKind::PrecomputeRecommendations,
+    ];
+
+    /// The representation of the kind stored in the database.
+    fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::RefreshMetadata => "refresh_metadata",
+            JobKind::SyncTmdbChanges => "sync_tmdb_changes",
+            JobKind::RefreshCharts => "refresh_charts",
+            JobKind::PrecomputeRecommendations => "precompute_recommendations",
+        }
+    }
+}
+
+impl fmt::Display for JobKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for JobKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == value)
+            .ok_or_else(|| {
+                let kinds: Vec<&str> = Self::ALL.iter().map(JobKind::as_str).collect();
+                format!(
+                    "unknown job `{}`, expected one of: {}",
+                    value,
+                    kinds.join(", ")
+                )
+            })
+    }
+}
+
+/// Represents errors that can be encountered while running a job.
+#[derive(Debug, thiserror::Error)]
+pub enum JobError {
+    /// An error from the database
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    /// An error while refreshing movie and person snapshots
+    #[error(transparent)]
+    Metadata(#[from] MetadataError),
+    /// An error while computing recommendations
+    #[error(transparent)]
+    Recommendation(#[from] RecommendationError),
+    /// The job is of a kind this version does not know how to run
+    #[error("Unknown job kind `{0}`")]
+    UnknownKind(String),
+}
+
+/// Adds a job to the queue, to run as soon as a worker is free.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `kind` - What the job does.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the job cannot be added.
+pub async fn enqueue(pool: &PgPool, kind: JobKind) -> Result<i64, sqlx::Error> {
+    enqueue_at(pool, kind, Utc::now()).await
+}
+
+/// Adds a job to the queue, to run once a given time has passed.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `kind` - What the job does.
+/// * `run_at` - The earliest time the job should run.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the job cannot be added.
+pub async fn enqueue_at(
+    pool: &PgPool,
+    kind: JobKind,
+    run_at: DateTime<Utc>,
+) -> Result<i64, sqlx::Error> {
+    queue::enqueue(pool, kind, run_at).await
+}
+
+/// Runs a job.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `client` - The `TMDBClient` instance used to make API requests.
+/// * `job` - The job to run.
+async fn run(pool: &PgPool, client: &TMDBClient, job: &Job) -> Result<(), JobError> {
+    let kind: JobKind = job
+        .kind
+        .parse()
+        .map_err(|_| JobError::UnknownKind(job.kind.clone()))?;
+
+    match kind {
+        JobKind::RefreshMetadata => metadata::refresh(pool, client).await?,
+        JobKind::SyncTmdbChanges => metadata::sync_changes(pool, client).await?,
+        JobKind::RefreshCharts => charts::refresh_charts(pool).await?,
+        JobKind::PrecomputeRecommendations => recommendations::precompute_all(pool, client).await?,
+    }
+
+    Ok(())
+}
+
+/// Runs a job, sending heartbeats every [`HEARTBEAT_INTERVAL`] until it finishes so that it is
+/// not mistaken for a job whose worker has died.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `client` - The `TMDBClient` instance used to make API requests.
+/// * `job` - The job to run.
+async fn run_with_heartbeat(pool: &PgPool, client: &TMDBClient, job: &Job) -> Result<(), JobError> {
+    let running = run(pool, client, job);
+    tokio::pin!(running);
+
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    heartbeat.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // the first tick completes straight away, and claiming the job already counts as one
+    heartbeat.tick().await;
+
+    loop {
+        tokio::select! {
+            result = &mut running => return result,
+            _ = heartbeat.tick() => match queue::heartbeat(pool, job).await {
+                Ok(true) => (),
+                Ok(false) => tracing::warn!(
+                    "{} job {} was taken over by another worker while running",
+                    job.kind,
+                    job.id
+                ),
+                Err(e) => tracing::error!("Failed to send heartbeat for job {}: {}", job.id, e),
+            },
+        }
+    }
+}
+
+/// Claims and runs jobs until told to shut down. A job that is running when the worker is told
+/// to shut down is finished first.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `shutdown` - Changes to `true` when the worker should shut down.
+async fn work(pool: PgPool, mut shutdown: watch::Receiver<bool>) {
+    let client = get_tmdb_client();
+
+    while !*shutdown.borrow() {
+        match queue::claim(&pool).await {
+            Ok(Some(job)) => {
+                tracing::info!(
+                    "Running {} job {} (attempt {})",
+                    job.kind,
+                    job.id,
+                    job.attempts
+                );
+
+                let result = match run_with_heartbeat(&pool, &client, &job).await {
+                    Ok(()) => {
+                        tracing::info!("Finished {} job {}", job.kind, job.id);
+                        queue::complete(&pool, &job).await
+                    }
+                    Err(e) => {
+                        if job.attempts >= job.max_attempts {
+                            tracing::error!("Giving up on {} job {}: {}", job.kind, job.id, e);
+                        } else {
+                            tracing::warn!("Retrying {} job {}: {}", job.kind, job.id, e);
+                        }
+                        queue::fail(&pool, &job, &e.to_string()).await
+                    }
+                };
+
+                match result {
+                    Ok(true) => (),
+                    Ok(false) => tracing::warn!(
+                        "Not saving result of {} job {}, as another worker has taken it over",
+                        job.kind,
+                        job.id
+                    ),
+                    Err(e) => tracing::error!("Failed to save result of job {}: {}", job.id, e),
+                }
+
+                continue;
+            }
+            Ok(None) => (),
+            Err(e) => tracing::error!("Failed to claim job: {}", e),
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(POLL_INTERVAL) => (),
+            _ = shutdown.changed() => (),
+        }
+    }
+}
+
+/// Queues recurring jobs when they are due, and queues timed out jobs again, until told to shut
+/// down.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `shutdown` - Changes to `true` when the scheduler should shut down.
+async fn schedule(pool: PgPool, mut shutdown: watch::Receiver<bool>) {
+    let schedules: Vec<(JobKind, Cron)> = SCHEDULES
+        .iter()
+        .filter_map(|(kind, cron)| match cron.parse() {
+            Ok(cron) => Some((*kind, cron)),
+            Err(e) => {
+                tracing::error!("Invalid schedule for {} jobs: {}", kind, e);
+                None
+            }
+        })
+        .collect();
+
+    if let Err(e) = queue::save_schedules(&pool, &schedules).await {
+        tracing::error!("Failed to save job schedules: {}", e);
+    }
+
+    let mut interval = tokio::time::interval(SCHEDULE_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    while !*shutdown.borrow() {
+        tokio::select! {
+            _ = interval.tick() => (),
+            _ = shutdown.changed() => continue,
+        }
+
+        if let Err(e) = queue::enqueue_due(&pool).await {
+            tracing::error!("Failed to queue scheduled jobs: {}", e);
+        }
+
+        if let Err(e) = queue::tidy(&pool).await {
+            tracing::error!("Failed to tidy job queue: {}", e);
+        }
+    }
+}
+
+/// Runs background jobs until it is shut down.
+pub struct JobRunner {
+    /// Tells the workers and scheduler to shut down.
+    shutdown: watch::Sender<bool>,
+    /// The workers and scheduler.
+    tasks: JoinSet<()>,
+}
+
+impl JobRunner {
+    /// Starts a number of workers, each running one job at a time, along with the scheduler
+    /// that queues recurring jobs.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `workers` - How many jobs can run at once.
+    pub fn start(pool: PgPool, workers: usize) -> Self {
+        let (shutdown, receiver) = watch::channel(false);
+        let mut tasks = JoinSet::new();
+
+        tasks.spawn(schedule(pool.clone(), receiver.clone()));
+        for _ in 0..workers {
+            tasks.spawn(work(pool.clone(), receiver.clone()));
+        }
+
+        tracing::info!("Started {} job workers", workers);
+
+        Self { shutdown, tasks }
+    }
+
+    /// Shuts the workers down, waiting for any jobs that are running to finish.
+    pub async fn shutdown(mut self) {
+        tracing::info!("Waiting for running jobs to finish");
+
+        let _ = self.shutdown.send(true);
+        while self.tasks.join_next().await.is_some() {}
+
+        tracing::info!("Job workers shut down");
+    }
+}
@@ -0,0 +1,310 @@
+//! The `jobs` and `job_schedules` tables, which make up the job queue.
+//!
+//! Workers claim jobs with `FOR UPDATE SKIP LOCKED`, so any number of workers, in any number of
+//! processes, can share the queue without two of them running the same job.
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{prelude::FromRow, PgPool};
+
+use super::{cron::Cron, JobKind};
+
+/// How long to wait before retrying a job that failed for the first time. The delay doubles
+/// with every attempt after that.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+
+/// The longest delay between retries.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+
+/// How long a running job can go without a heartbeat before it is assumed its worker has died
+/// and it is queued again. Workers send heartbeats far more often than this (see
+/// [`super::HEARTBEAT_INTERVAL`]).
+const HEARTBEAT_TIMEOUT_MINUTES: i32 = 5;
+
+/// How many days finished jobs are kept for.
+const FINISHED_JOB_RETENTION_DAYS: i32 = 7;
+
+/// A job claimed by a worker.
+#[derive(Debug, FromRow)]
+pub struct Job {
+    /// The ID of the job.
+    pub id: i64,
+    /// What the job does, as stored in the database.
+    pub kind: String,
+    /// How many times the job has been started, including this time. This also identifies the
+    /// claim, as a job that is claimed again after timing out has a higher count.
+    pub attempts: i32,
+    /// How many times the job is started before it is given up on.
+    pub max_attempts: i32,
+}
+
+/// Adds a job to the queue.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `kind` - What the job does.
+/// * `run_at` - The earliest time the job should run.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the job cannot be added.
+pub async fn enqueue(
+    pool: &PgPool,
+    kind: JobKind,
+    run_at: DateTime<Utc>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("INSERT INTO jobs (kind, run_at) VALUES ($1, $2) RETURNING id")
+        .bind(kind.as_str())
+        .bind(run_at)
+        .fetch_one(pool)
+        .await
+}
+
+/// Claims the job that has been due the longest, if any are due.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+pub async fn claim(pool: &PgPool) -> Result<Option<Job>, sqlx::Error> {
+    sqlx::query_as(
+        "UPDATE jobs
+        SET status = 'running',
+            attempts = attempts + 1,
+            locked_at = NOW(),
+            heartbeat_at = NOW(),
+            updated_at = NOW()
+        WHERE id = (
+            SELECT id FROM jobs
+            WHERE status = 'queued' AND run_at <= NOW()
+            ORDER BY run_at, id
+            FOR UPDATE SKIP LOCKED
+            LIMIT 1
+        )
+        RETURNING id, kind, attempts, max_attempts",
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Records that the worker running a job is still alive.
+///
+/// Returns whether the worker still holds the job. It does not if the job timed out and was
+/// queued again.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `job` - The job being run.
+pub async fn heartbeat(pool: &PgPool, job: &Job) -> Result<bool, sqlx::Error> {
+    let updated = sqlx::query(
+        "UPDATE jobs
+        SET heartbeat_at = NOW()
+        WHERE id = $1 AND status = 'running' AND attempts = $2",
+    )
+    .bind(job.id)
+    .bind(job.attempts)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(updated > 0)
+}
+
+/// Marks a job as having succeeded.
+///
+/// Returns whether the worker still held the job. If it did not, the job is left alone, as
+/// another attempt has taken it over.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `job` - The job that succeeded.
+pub async fn complete(pool: &PgPool, job: &Job) -> Result<bool, sqlx::Error> {
+    let updated = sqlx::query(
+        "UPDATE jobs
+        SET status = 'succeeded',
+            locked_at = NULL,
+            heartbeat_at = NULL,
+            last_error = NULL,
+            updated_at = NOW()
+        WHERE id = $1 AND status = 'running' AND attempts = $2",
+    )
+    .bind(job.id)
+    .bind(job.attempts)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(updated > 0)
+}
+
+/// Marks a job as having failed. The job is retried with exponential backoff, unless it has run
+/// out of attempts.
+///
+/// Returns whether the worker still held the job. If it did not, the job is left alone, as
+/// another attempt has taken it over.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `job` - The job that failed.
+/// * `error` - Why the job failed.
+pub async fn fail(pool: &PgPool, job: &Job, error: &str) -> Result<bool, sqlx::Error> {
+    let delay = RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(job.attempts.saturating_sub(1) as u32))
+        .min(MAX_RETRY_DELAY);
+
+    let updated = sqlx::query(
+        "UPDATE jobs
+        SET status = CASE WHEN attempts >= max_attempts THEN 'failed' ELSE 'queued' END,
+            run_at = NOW() + make_interval(secs => $3),
+            locked_at = NULL,
+            heartbeat_at = NULL,
+            last_error = $4,
+            updated_at = NOW()
+        WHERE id = $1 AND status = 'running' AND attempts = $2",
+    )
+    .bind(job.id)
+    .bind(job.attempts)
+    .bind(delay.as_secs_f64())
+    .bind(error)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(updated > 0)
+}
+
+/// Queues jobs again whose worker has stopped sending heartbeats, as it has most likely died
+/// partway through them, and deletes old finished jobs.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+pub async fn tidy(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let requeued = sqlx::query(
+        "UPDATE jobs
+        SET status = CASE WHEN attempts >= max_attempts THEN 'failed' ELSE 'queued' END,
+            locked_at = NULL,
+            heartbeat_at = NULL,
+            last_error = 'Worker stopped responding',
+            updated_at = NOW()
+        WHERE status = 'running' AND heartbeat_at < NOW() - make_interval(mins => $1)",
+    )
+    .bind(HEARTBEAT_TIMEOUT_MINUTES)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if requeued > 0 {
+        tracing::warn!("Requeued {} jobs whose worker stopped responding", requeued);
+    }
+
+    sqlx::query(
+        "DELETE FROM jobs
+        WHERE status IN ('succeeded', 'failed')
+            AND updated_at < NOW() - make_interval(days => $1)",
+    )
+    .bind(FINISHED_JOB_RETENTION_DAYS)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Saves the recurring jobs, and removes any that are no longer recurring. New schedules are due
+/// straight away, and schedules whose expression has changed are due at their next new run.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `schedules` - Every recurring job and when it runs.
+pub async fn save_schedules(
+    pool: &PgPool,
+    schedules: &[(JobKind, Cron)],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let now = Utc::now();
+
+    for (kind, cron) in schedules {
+        sqlx::query(
+            "INSERT INTO job_schedules (kind, cron, next_run_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (kind) DO UPDATE
+            SET cron = EXCLUDED.cron,
+                next_run_at = CASE
+                    WHEN job_schedules.cron = EXCLUDED.cron THEN job_schedules.next_run_at
+                    ELSE COALESCE($3, NOW())
+                END",
+        )
+        .bind(kind.as_str())
+        .bind(cron.to_string())
+        .bind(cron.next_after(now))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let kinds: Vec<&str> = schedules.iter().map(|(kind, _)| kind.as_str()).collect();
+
+    sqlx::query("DELETE FROM job_schedules WHERE kind <> ALL($1)")
+        .bind(kinds)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await
+}
+
+/// Queues every recurring job that is due, unless it is already queued or running, and works out
+/// when each will next be due.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+pub async fn enqueue_due(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let due: Vec<(String, String)> = sqlx::query_as(
+        "SELECT kind, cron FROM job_schedules
+        WHERE next_run_at <= NOW()
+        FOR UPDATE SKIP LOCKED",
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let now = Utc::now();
+
+    for (kind, cron) in due {
+        let next_run_at = match cron.parse::<Cron>() {
+            Ok(cron) => cron.next_after(now),
+            Err(e) => {
+                tracing::error!("Invalid schedule for {} jobs: {}", kind, e);
+                None
+            }
+        };
+
+        sqlx::query(
+            "INSERT INTO jobs (kind)
+            SELECT $1
+            WHERE NOT EXISTS (
+                SELECT 1 FROM jobs WHERE kind = $1 AND status IN ('queued', 'running')
+            )",
+        )
+        .bind(&kind)
+        .execute(&mut *tx)
+        .await?;
+
+        // a schedule that never runs again is left due, so it is logged on every check
+        sqlx::query(
+            "UPDATE job_schedules SET next_run_at = COALESCE($2, next_run_at) WHERE kind = $1",
+        )
+        .bind(&kind)
+        .bind(next_run_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tracing::debug!("Queued scheduled {} job", kind);
+    }
+
+    tx.commit().await
+}
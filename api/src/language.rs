@@ -0,0 +1,114 @@
+//! Resolves the language TMDB content is requested in. A language saved in the user's
+//! preferences wins, followed by the request's `Accept-Language` header. If neither gives a
+//! language TMDB understands, requests are sent without one and TMDB falls back to English.
+use std::convert::Infallible;
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header::ACCEPT_LANGUAGE, request::Parts},
+};
+use axum_login::AuthSession;
+
+use crate::{
+    auth::Backend, filters::validate_language, frontend_models::localisation::Localise,
+    tmdb::queries::traits::LanguageQueryParam,
+};
+
+/// The language a request's TMDB content should be in, if one could be resolved.
+#[derive(Debug, Clone, Default)]
+pub struct PreferredLanguage(pub Option<String>);
+
+impl<S: Send + Sync> FromRequestParts<S> for PreferredLanguage {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let saved = AuthSession::<Backend>::from_request_parts(parts, state)
+            .await
+            .ok()
+            .and_then(|auth_session| auth_session.user)
+            .and_then(|user| user.language);
+
+        if saved.is_some() {
+            return Ok(Self(saved));
+        }
+
+        let accepted = parts
+            .headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_accept_language);
+
+        Ok(Self(accepted))
+    }
+}
+
+impl PreferredLanguage {
+    /// Returns the language to use when a request can also name one explicitly, such as with a
+    /// `language` query parameter. The explicit language wins if it is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `explicit` - The language named by the request, which should already be validated
+    pub fn or_explicit(self, explicit: Option<String>) -> Self {
+        Self(explicit.or(self.0))
+    }
+
+    /// Sets the language of a TMDB request, if one was resolved.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to set the language of
+    pub fn apply<R: LanguageQueryParam>(&self, request: R) -> R {
+        match &self.0 {
+            Some(language) => request.language(language.clone()),
+            None => request,
+        }
+    }
+
+    /// Shows any fallback text in a frontend model in the resolved language.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The frontend model to localise
+    pub fn localise<T: Localise>(&self, mut value: T) -> T {
+        if let Some(language) = &self.0 {
+            value.localise(language);
+        }
+
+        value
+    }
+}
+
+/// Picks the most preferred language from an `Accept-Language` header that TMDB understands,
+/// normalised into the `xx` or `xx-XX` form it expects.
+///
+/// # Arguments
+///
+/// * `header` - The value of the header (e.g., `fr-CH, fr;q=0.9, en;q=0.8, *;q=0.5`)
+fn parse_accept_language(header: &str) -> Option<String> {
+    let mut languages: Vec<(String, f32)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';').map(str::trim);
+            let tag = parts.next()?;
+
+            let quality = parts
+                .find_map(|part| part.strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+
+            let language = match tag.split_once('-') {
+                Some((language, region)) => {
+                    format!("{}-{}", language.to_lowercase(), region.to_uppercase())
+                }
+                None => tag.to_lowercase(),
+            };
+
+            (quality > 0.0 && validate_language("Accept-Language", &language).is_ok())
+                .then_some((language, quality))
+        })
+        .collect();
+
+    // stable, so equally preferred languages keep the order they were listed in
+    languages.sort_by(|a, b| b.1.total_cmp(&a.1));
+    languages.into_iter().next().map(|(language, _)| language)
+}
@@ -2,9 +2,14 @@
 use axum::{routing::get, Router};
 use axum_login::AuthManagerLayerBuilder;
 use discover::{
-    fetch_movie_details, fetch_now_playing, fetch_person_details, fetch_trending,
-    fetch_trending_people, fetch_upcoming_movies, search_movies, search_people,
+    discover_movies, fetch_certifications, fetch_collection, fetch_company_details,
+    fetch_company_movies, fetch_genres, fetch_keyword_movies, fetch_movie_details,
+    fetch_movie_images, fetch_movie_recommendations, fetch_movie_reviews, fetch_movies_by_genre,
+    fetch_now_playing, fetch_person_details, fetch_person_images, fetch_popular,
+    fetch_similar_movies, fetch_top_rated, fetch_trending, fetch_trending_all,
+    fetch_trending_people, fetch_upcoming_movies, fetch_watch_providers,
 };
+use search::{search_autocomplete, search_keywords, search_movies, search_multi, search_people};
 use sqlx::PgPool;
 use tower_http::trace::TraceLayer;
 use tower_sessions::{cookie::time::Duration, Expiry, SessionManagerLayer};
@@ -13,10 +18,24 @@ use tower_sessions_sqlx_store::PostgresStore;
 use crate::auth::Backend;
 
 mod auth;
+mod charts;
+mod community;
 mod discover;
+mod filters;
 mod frontend_models;
+mod images;
 mod interactions;
+pub mod jobs;
+mod language;
+mod metadata;
+mod pagination;
+mod posters;
+mod preferences;
+mod recommendations;
+mod search;
 mod tmdb;
+mod trending;
+mod watchlist;
 
 pub async fn build_router(pool: PgPool) -> Router {
     tracing::info!("Creating session manager...");
@@ -35,26 +54,74 @@ pub async fn build_router(pool: PgPool) -> Router {
     let backend = Backend::new(pool.clone());
     let auth_layer = AuthManagerLayerBuilder::new(backend, session_layer).build();
 
+    tracing::info!("Starting background jobs...");
+
+    tmdb::queries::configuration::spawn_image_configuration_job(discover::get_tmdb_client());
+
     tracing::info!("Initializing API routes...");
 
     Router::new()
+        .route("/api/v1/discover", get(discover_movies))
         .route("/api/v1/discover/trending", get(fetch_trending))
+        .route("/api/v1/discover/trending_all", get(fetch_trending_all))
+        .route(
+            "/api/v1/discover/trending_community",
+            get(trending::fetch_community_trending),
+        )
         .route(
             "/api/v1/discover/trending_people",
             get(fetch_trending_people),
         )
         .route("/api/v1/discover/now_playing", get(fetch_now_playing))
         .route("/api/v1/discover/upcoming", get(fetch_upcoming_movies))
+        .route("/api/v1/discover/popular", get(fetch_popular))
+        .route("/api/v1/discover/top_rated", get(fetch_top_rated))
+        .route("/api/v1/discover/genre/{id}", get(fetch_movies_by_genre))
+        .route("/api/v1/certifications", get(fetch_certifications))
+        .route("/api/v1/collections/{id}", get(fetch_collection))
+        .route("/api/v1/companies/{id}", get(fetch_company_details))
+        .route("/api/v1/companies/{id}/movies", get(fetch_company_movies))
+        .route("/api/v1/genres", get(fetch_genres))
+        .route(
+            "/api/v1/images/placeholders/{kind}",
+            get(images::fetch_placeholder),
+        )
+        .route(
+            "/api/v1/images/{kind}/{size}/{file}",
+            get(images::fetch_image),
+        )
+        .route("/api/v1/watch_providers", get(fetch_watch_providers))
+        .route("/api/v1/keywords/{id}/movies", get(fetch_keyword_movies))
         .route("/api/v1/movies/{id}", get(fetch_movie_details))
+        .route(
+            "/api/v1/movies/{id}/recommendations",
+            get(fetch_movie_recommendations),
+        )
+        .route("/api/v1/movies/{id}/similar", get(fetch_similar_movies))
+        .route("/api/v1/movies/{id}/images", get(fetch_movie_images))
+        .route("/api/v1/movies/{id}/reviews", get(fetch_movie_reviews))
         .route("/api/v1/people/{id}", get(fetch_person_details))
+        .route("/api/v1/people/{id}/images", get(fetch_person_images))
         .route("/api/v1/search/movies", get(search_movies))
         .route("/api/v1/search/people", get(search_people))
+        .route("/api/v1/search/keywords", get(search_keywords))
+        .route("/api/v1/search/multi", get(search_multi))
+        .route("/api/v1/search/autocomplete", get(search_autocomplete))
+        .route("/api/v1/search/community", get(community::search_community))
         .with_state(pool.clone())
         .nest(
             "/api/v1/interactions",
             interactions::build_router(pool.clone()),
         )
         .nest("/api/v1/auth", auth::build_router(pool.clone()))
+        .nest("/api/v1/charts", charts::build_router(pool.clone()))
+        .nest(
+            "/api/v1/me",
+            recommendations::build_router(pool.clone())
+                .merge(preferences::build_router(pool.clone()))
+                .merge(posters::build_router(pool.clone()))
+                .merge(watchlist::build_router(pool.clone())),
+        )
         .layer(auth_layer)
         .layer(TraceLayer::new_for_http())
 }
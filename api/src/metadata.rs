@@ -0,0 +1,583 @@
+//! A local copy of the core details of movies and people from TMDB.
+//!
+//! Interactions only store TMDB IDs, so showing a list of movies would otherwise take a TMDB
+//! request per movie. Instead, whenever the details of a movie or person are fetched for a page
+//! or a list, a snapshot of them is saved in the `movies` or `people` table ([`save_movie`],
+//! [`save_person`]). Lists are shown from these snapshots ([`load_movies`]), as are content-based
+//! recommendations ([`load_movies_with_credits`]), and the tables can be joined against in
+//! queries. Background jobs fetch snapshots again once they go stale ([`refresh`]) or change on
+//! TMDB ([`sync_changes`]), and fill in any movie that has been interacted with but never
+//! fetched.
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use sqlx::{prelude::FromRow, PgPool};
+
+use crate::{
+    discover::fetch_movie_details_batch,
+    language::PreferredLanguage,
+    tmdb::{
+        client::{ApiFetchError, TMDBClient},
+        models::{
+            change::ChangedItem, common::PaginatedSearchResult, movie::MovieDetails,
+            person::PersonDetails,
+        },
+        queries::{
+            changes::{MovieChangesRequest, PersonChangesRequest},
+            movie_details::MovieDetailsRequest,
+            people_details::PersonDetailsRequest,
+            traits::{AppendToResponseQueryParam, LanguageQueryParam, PageQueryParam},
+        },
+    },
+};
+
+/// How many days a snapshot is kept before it is fetched again.
+const MAX_AGE_DAYS: i32 = 7;
+
+/// The most snapshots of each kind fetched in a single refresh, so a large backlog is spread
+/// across several refreshes rather than flooding TMDB.
+const REFRESH_BATCH_SIZE: i64 = 200;
+
+/// The most pages of changes fetched from TMDB when syncing. Each page lists 100 movies or
+/// people.
+const MAX_CHANGE_PAGES: u32 = 50;
+
+/// How many of a movie's top-billed cast members are kept in its snapshot.
+const TOP_BILLED_CAST: u64 = 5;
+
+/// Snapshots are saved per language. Details fetched without a language are saved under an
+/// empty one.
+///
+/// # Arguments
+///
+/// * `language` - The language the details were fetched in, if any.
+fn language_key(language: Option<&str>) -> &str {
+    language.unwrap_or_default()
+}
+
+/// A snapshot of a movie's details.
+#[derive(Debug, Clone, FromRow)]
+pub struct CachedMovie {
+    /// The TMDB ID of the movie.
+    pub movie_id: i64,
+    /// The title of the movie.
+    pub title: String,
+    /// A brief overview of the movie.
+    pub overview: Option<String>,
+    /// The release date of the movie.
+    pub release_date: Option<NaiveDate>,
+    /// The path to the movie's poster.
+    pub poster_path: Option<String>,
+    /// The runtime of the movie in minutes.
+    pub runtime: i32,
+    /// The IDs of the movie's genres.
+    pub genre_ids: Vec<i64>,
+    /// The names of the movie's genres, in the same order as [`CachedMovie::genre_ids`].
+    pub genre_names: Vec<String>,
+    /// The names of the movie's directors, if it was fetched along with its credits.
+    pub directors: Option<Vec<String>>,
+    /// The IDs of the movie's directors, if it was fetched along with its credits.
+    pub director_ids: Option<Vec<i64>>,
+    /// The IDs of the movie's top-billed cast members, if it was fetched along with its credits.
+    pub cast_ids: Option<Vec<i64>>,
+}
+
+impl From<&MovieDetails> for CachedMovie {
+    /// Takes a snapshot of a [`MovieDetails`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`MovieDetails`] to take a snapshot of
+    ///
+    /// # Returns
+    ///
+    /// A new [`CachedMovie`] instance with its directors and cast set if the source has credits.
+    fn from(value: &MovieDetails) -> Self {
+        Self {
+            movie_id: value.base.id as i64,
+            title: value.base.title.clone(),
+            overview: value.base.overview.clone(),
+            release_date: value.base.release_date,
+            poster_path: value.base.poster_path.clone(),
+            runtime: value.runtime as i32,
+            genre_ids: value.genres.iter().map(|genre| genre.id as i64).collect(),
+            genre_names: value
+                .genres
+                .iter()
+                .map(|genre| genre.name.clone())
+                .collect(),
+            directors: value.credits.as_ref().map(|credits| {
+                credits
+                    .crew
+                    .iter()
+                    .filter(|member| member.job == "Director")
+                    .map(|member| member.base.name.clone())
+                    .collect()
+            }),
+            director_ids: value.credits.as_ref().map(|credits| {
+                credits
+                    .crew
+                    .iter()
+                    .filter(|member| member.job == "Director")
+                    .map(|member| member.base.id as i64)
+                    .collect()
+            }),
+            cast_ids: value.credits.as_ref().map(|credits| {
+                credits
+                    .cast
+                    .iter()
+                    .filter(|member| member.order < TOP_BILLED_CAST)
+                    .map(|member| member.base.id as i64)
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// A snapshot of a person's details.
+#[derive(Debug, Clone, FromRow)]
+pub struct CachedPerson {
+    /// The TMDB ID of the person.
+    pub person_id: i64,
+    /// The person's full name.
+    pub name: String,
+    /// The path to the person's profile image.
+    pub profile_path: Option<String>,
+    /// The department the person is best known for.
+    pub known_for_department: String,
+    /// The person's birth date.
+    pub birthday: Option<NaiveDate>,
+    /// The person's death date.
+    pub deathday: Option<NaiveDate>,
+    /// The person's popularity score.
+    pub popularity: f64,
+}
+
+impl From<&PersonDetails> for CachedPerson {
+    /// Takes a snapshot of a [`PersonDetails`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`PersonDetails`] to take a snapshot of
+    ///
+    /// # Returns
+    ///
+    /// A new [`CachedPerson`] instance with all fields copied from the source.
+    fn from(value: &PersonDetails) -> Self {
+        Self {
+            person_id: value.id as i64,
+            name: value.name.clone(),
+            profile_path: value.profile_path.clone(),
+            known_for_department: value.known_for_department.clone(),
+            birthday: value.birthday,
+            deathday: value.deathday,
+            popularity: value.popularity,
+        }
+    }
+}
+
+/// Saves a snapshot of a movie's details. Directors and cast are kept from the previous snapshot
+/// if the details were fetched without credits.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `details` - The movie's details.
+/// * `language` - The language the details were fetched in, if any.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the snapshot cannot be saved.
+pub async fn save_movie(
+    pool: &PgPool,
+    details: &MovieDetails,
+    language: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let movie = CachedMovie::from(details);
+
+    sqlx::query(
+        "INSERT INTO movies (movie_id, language, title, overview, release_date, poster_path,
+            runtime, genre_ids, genre_names, directors, director_ids, cast_ids)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        ON CONFLICT (movie_id, language) DO UPDATE
+        SET title = EXCLUDED.title,
+            overview = EXCLUDED.overview,
+            release_date = EXCLUDED.release_date,
+            poster_path = EXCLUDED.poster_path,
+            runtime = EXCLUDED.runtime,
+            genre_ids = EXCLUDED.genre_ids,
+            genre_names = EXCLUDED.genre_names,
+            directors = COALESCE(EXCLUDED.directors, movies.directors),
+            director_ids = COALESCE(EXCLUDED.director_ids, movies.director_ids),
+            cast_ids = COALESCE(EXCLUDED.cast_ids, movies.cast_ids),
+            fetched_at = NOW()",
+    )
+    .bind(movie.movie_id)
+    .bind(language_key(language))
+    .bind(&movie.title)
+    .bind(&movie.overview)
+    .bind(movie.release_date)
+    .bind(&movie.poster_path)
+    .bind(movie.runtime)
+    .bind(&movie.genre_ids)
+    .bind(&movie.genre_names)
+    .bind(&movie.directors)
+    .bind(&movie.director_ids)
+    .bind(&movie.cast_ids)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Saves a snapshot of a person's details.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `details` - The person's details.
+/// * `language` - The language the details were fetched in, if any.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the snapshot cannot be saved.
+pub async fn save_person(
+    pool: &PgPool,
+    details: &PersonDetails,
+    language: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let person = CachedPerson::from(details);
+
+    sqlx::query(
+        "INSERT INTO people (person_id, language, name, profile_path, known_for_department,
+            birthday, deathday, popularity)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (person_id, language) DO UPDATE
+        SET name = EXCLUDED.name,
+            profile_path = EXCLUDED.profile_path,
+            known_for_department = EXCLUDED.known_for_department,
+            birthday = EXCLUDED.birthday,
+            deathday = EXCLUDED.deathday,
+            popularity = EXCLUDED.popularity,
+            fetched_at = NOW()",
+    )
+    .bind(person.person_id)
+    .bind(language_key(language))
+    .bind(&person.name)
+    .bind(&person.profile_path)
+    .bind(&person.known_for_department)
+    .bind(person.birthday)
+    .bind(person.deathday)
+    .bind(person.popularity)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Loads several movies, from their snapshots where there are any and from TMDB otherwise.
+/// Movies fetched from TMDB are saved, so they have a snapshot next time. Stale snapshots are
+/// still used, since the refresh job will fetch them again soon. Movies that fail to fetch are
+/// left out rather than failing the whole list.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `client` - The `TMDBClient` instance used to fetch movies without a snapshot.
+/// * `movie_ids` - The IDs of the movies to load, in the order they should be returned.
+/// * `language` - The language to load each movie in.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the snapshots cannot be loaded or saved.
+pub async fn load_movies(
+    pool: &PgPool,
+    client: &TMDBClient,
+    movie_ids: &[u64],
+    language: &PreferredLanguage,
+) -> Result<Vec<CachedMovie>, sqlx::Error> {
+    load_movie_snapshots(pool, client, movie_ids, language, false).await
+}
+
+/// Loads several movies along with their directors and cast, like [`load_movies`]. Movies whose
+/// snapshot was taken without credits are fetched from TMDB again, with their credits this time.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `client` - The `TMDBClient` instance used to fetch movies without a snapshot.
+/// * `movie_ids` - The IDs of the movies to load, in the order they should be returned.
+/// * `language` - The language to load each movie in.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the snapshots cannot be loaded or saved.
+pub async fn load_movies_with_credits(
+    pool: &PgPool,
+    client: &TMDBClient,
+    movie_ids: &[u64],
+    language: &PreferredLanguage,
+) -> Result<Vec<CachedMovie>, sqlx::Error> {
+    load_movie_snapshots(pool, client, movie_ids, language, true).await
+}
+
+/// Loads several movies from their snapshots, fetching and saving any without one.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `client` - The `TMDBClient` instance used to fetch movies without a snapshot.
+/// * `movie_ids` - The IDs of the movies to load, in the order they should be returned.
+/// * `language` - The language to load each movie in.
+/// * `with_credits` - Whether snapshots taken without credits count as missing.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the snapshots cannot be loaded or saved.
+async fn load_movie_snapshots(
+    pool: &PgPool,
+    client: &TMDBClient,
+    movie_ids: &[u64],
+    language: &PreferredLanguage,
+    with_credits: bool,
+) -> Result<Vec<CachedMovie>, sqlx::Error> {
+    let ids: Vec<i64> = movie_ids.iter().map(|id| *id as i64).collect();
+
+    let mut movies: HashMap<u64, CachedMovie> = sqlx::query_as::<_, CachedMovie>(
+        "SELECT movie_id, title, overview, release_date, poster_path, runtime, genre_ids,
+            genre_names, directors, director_ids, cast_ids
+        FROM movies
+        WHERE movie_id = ANY($1) AND language = $2",
+    )
+    .bind(&ids)
+    .bind(language_key(language.0.as_deref()))
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .filter(|movie| !with_credits || movie.director_ids.is_some())
+    .map(|movie| (movie.movie_id as u64, movie))
+    .collect();
+
+    let missing: Vec<u64> = movie_ids
+        .iter()
+        .filter(|id| !movies.contains_key(id))
+        .copied()
+        .collect();
+
+    if !missing.is_empty() {
+        tracing::debug!("Fetching {} movies without a snapshot", missing.len());
+
+        let append = with_credits.then_some("credits");
+        for details in fetch_movie_details_batch(client, missing, append, language).await {
+            save_movie(pool, &details, language.0.as_deref()).await?;
+            movies.insert(details.base.id, CachedMovie::from(&details));
+        }
+    }
+
+    Ok(movie_ids
+        .iter()
+        .filter_map(|id| movies.remove(id))
+        .collect())
+}
+
+/// Represents errors that can be encountered while refreshing snapshots.
+#[derive(Debug, thiserror::Error)]
+pub enum MetadataError {
+    /// An error from the database
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    /// An error from the TMDB API
+    #[error(transparent)]
+    Tmdb(#[from] ApiFetchError),
+}
+
+/// Fetches movies again and saves new snapshots of them. Movies that fail to fetch are skipped.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `client` - The `TMDBClient` instance used to make API requests.
+/// * `movies` - The ID of each movie and the language to fetch it in.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if a snapshot cannot be saved.
+async fn refetch_movies(
+    pool: &PgPool,
+    client: &TMDBClient,
+    movies: Vec<(i64, String)>,
+) -> Result<(), sqlx::Error> {
+    for (movie_id, language) in movies {
+        let mut request = MovieDetailsRequest::new().append_to_response("credits");
+        if !language.is_empty() {
+            request = request.language(language.clone());
+        }
+
+        match request.fetch_details(client, movie_id as u64).await {
+            Ok(details) => save_movie(pool, &details, Some(&language)).await?,
+            Err(e) => tracing::warn!("Failed to refresh movie {}: {}", movie_id, e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetches people again and saves new snapshots of them. People who fail to fetch are skipped.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `client` - The `TMDBClient` instance used to make API requests.
+/// * `people` - The ID of each person and the language to fetch them in.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if a snapshot cannot be saved.
+async fn refetch_people(
+    pool: &PgPool,
+    client: &TMDBClient,
+    people: Vec<(i64, String)>,
+) -> Result<(), sqlx::Error> {
+    for (person_id, language) in people {
+        let mut request = PersonDetailsRequest::new();
+        if !language.is_empty() {
+            request = request.language(language.clone());
+        }
+
+        match request.fetch_details(client, person_id as u64).await {
+            Ok(details) => save_person(pool, &details, Some(&language)).await?,
+            Err(e) => tracing::warn!("Failed to refresh person {}: {}", person_id, e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetches stale snapshots again, along with movies that have been interacted with but never
+/// fetched in TMDB's default language.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `client` - The `TMDBClient` instance used to make API requests.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the snapshots to refresh cannot be found or saved.
+pub async fn refresh(pool: &PgPool, client: &TMDBClient) -> Result<(), sqlx::Error> {
+    let movies: Vec<(i64, String)> = sqlx::query_as(
+        "(SELECT movie_id, language
+            FROM movies
+            WHERE fetched_at < NOW() - make_interval(days => $1)
+            ORDER BY fetched_at
+            LIMIT $2)
+        UNION
+        (SELECT interactions.movie_id, ''
+            FROM (
+                SELECT movie_id FROM movie_ratings
+                UNION SELECT movie_id FROM movie_likes
+                UNION SELECT movie_id FROM movie_watchlist
+            ) interactions
+            WHERE NOT EXISTS (
+                SELECT 1 FROM movies
+                WHERE movies.movie_id = interactions.movie_id AND movies.language = ''
+            )
+            LIMIT $2)",
+    )
+    .bind(MAX_AGE_DAYS)
+    .bind(REFRESH_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    let people: Vec<(i64, String)> = sqlx::query_as(
+        "SELECT person_id, language
+        FROM people
+        WHERE fetched_at < NOW() - make_interval(days => $1)
+        ORDER BY fetched_at
+        LIMIT $2",
+    )
+    .bind(MAX_AGE_DAYS)
+    .bind(REFRESH_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    tracing::info!(
+        "Refreshing {} movie and {} person snapshots",
+        movies.len(),
+        people.len()
+    );
+
+    refetch_movies(pool, client, movies).await?;
+    refetch_people(pool, client, people).await?;
+
+    Ok(())
+}
+
+/// Fetches every page of changes from one of TMDB's change lists, up to [`MAX_CHANGE_PAGES`].
+///
+/// # Arguments
+///
+/// * `fetch_page` - Fetches a page of changes
+async fn fetch_changed_ids<F, Fut>(fetch_page: F) -> Result<Vec<i64>, ApiFetchError>
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = Result<PaginatedSearchResult<ChangedItem>, ApiFetchError>>,
+{
+    let mut ids = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let changes = fetch_page(page).await?;
+        ids.extend(changes.results.iter().map(|item| item.id as i64));
+
+        if u64::from(page) >= changes.total_pages || page >= MAX_CHANGE_PAGES {
+            return Ok(ids);
+        }
+
+        page += 1;
+    }
+}
+
+/// Fetches snapshots again for every movie and person that has changed on TMDB in the last day,
+/// in every language they have been saved in.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `client` - The `TMDBClient` instance used to make API requests.
+///
+/// # Errors
+///
+/// Returns a [`MetadataError`] if the changes cannot be fetched, or the snapshots to refresh
+/// cannot be found or saved.
+pub async fn sync_changes(pool: &PgPool, client: &TMDBClient) -> Result<(), MetadataError> {
+    let movie_ids =
+        fetch_changed_ids(|page| MovieChangesRequest::new().page(page).fetch_changes(client))
+            .await?;
+    let person_ids =
+        fetch_changed_ids(|page| PersonChangesRequest::new().page(page).fetch_changes(client))
+            .await?;
+
+    let movies: Vec<(i64, String)> =
+        sqlx::query_as("SELECT movie_id, language FROM movies WHERE movie_id = ANY($1)")
+            .bind(movie_ids)
+            .fetch_all(pool)
+            .await?;
+
+    let people: Vec<(i64, String)> =
+        sqlx::query_as("SELECT person_id, language FROM people WHERE person_id = ANY($1)")
+            .bind(person_ids)
+            .fetch_all(pool)
+            .await?;
+
+    tracing::info!(
+        "Syncing {} movie and {} person snapshots changed on TMDB",
+        movies.len(),
+        people.len()
+    );
+
+    refetch_movies(pool, client, movies).await?;
+    refetch_people(pool, client, people).await?;
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! Pagination shared by every list, whether it is paged by TMDB or by Cinescore's database.
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use sqlx::{
+    error::BoxDynError,
+    postgres::{PgArguments, PgRow},
+    Arguments, FromRow, PgPool,
+};
+
+use crate::tmdb::client::ApiFetchError;
+
+/// The highest page TMDB will return results for.
+const MAX_PAGE: u32 = 500;
+
+/// Checks that a requested page is one TMDB will return results for.
+///
+/// # Arguments
+/// * `page` - The requested page.
+///
+/// # Errors
+/// Returns [`ApiFetchError::InvalidQueryParam`] if the page is out of range.
+pub fn validate_page(page: u32) -> Result<u32, ApiFetchError> {
+    if (1..=MAX_PAGE).contains(&page) {
+        Ok(page)
+    } else {
+        Err(ApiFetchError::InvalidQueryParam {
+            param: "page",
+            reason: format!("must be between 1 and {}", MAX_PAGE),
+        })
+    }
+}
+
+/// The number of results on each page of lists served from the database.
+pub const PAGE_SIZE: u64 = 20;
+
+/// A page of rows from the database.
+pub struct DatabasePage<T> {
+    /// The rows on the page.
+    pub rows: Vec<T>,
+    /// The page, starting from 1.
+    pub page: u64,
+    /// The total number of rows across every page.
+    pub total_results: u64,
+}
+
+/// Fetches a page of [`PAGE_SIZE`] rows from the database, along with the total number of rows.
+/// The total is counted by a separate query rather than from the rows returned, so pages past the
+/// end still report the real totals. Both queries run at the same time.
+///
+/// # Arguments
+/// * `pool` - The database pool.
+/// * `list_query` - The query listing the rows, without a `LIMIT` or `OFFSET`, which are added.
+/// * `count_query` - The query counting every row `list_query` would list.
+/// * `binds` - Binds the parameters both queries share, in order.
+/// * `page` - The requested page, defaulting to the first.
+///
+/// # Errors
+/// Returns a 400 response if the page is out of range, or a 500 response if either query fails.
+pub async fn fetch_page<T, B>(
+    pool: &PgPool,
+    list_query: &str,
+    count_query: &str,
+    binds: B,
+    page: Option<u32>,
+) -> Result<DatabasePage<T>, Response>
+where
+    T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    B: Fn(&mut PgArguments) -> Result<(), BoxDynError>,
+{
+    let page = u64::from(validate_page(page.unwrap_or(1)).map_err(IntoResponse::into_response)?);
+
+    let database_error = |e: sqlx::Error| {
+        tracing::error!("Database error: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    };
+
+    let mut count_arguments = PgArguments::default();
+    binds(&mut count_arguments).map_err(|e| database_error(sqlx::Error::Encode(e)))?;
+
+    let mut list_arguments = count_arguments.clone();
+    let limit = list_arguments.len() + 1;
+    list_arguments
+        .add(PAGE_SIZE as i64)
+        .and_then(|()| list_arguments.add(((page - 1) * PAGE_SIZE) as i64))
+        .map_err(|e| database_error(sqlx::Error::Encode(e)))?;
+
+    let list_query = format!("{} LIMIT ${} OFFSET ${}", list_query, limit, limit + 1);
+    let rows = sqlx::query_as_with::<_, T, _>(&list_query, list_arguments).fetch_all(pool);
+    let total_results =
+        sqlx::query_scalar_with::<_, i64, _>(count_query, count_arguments).fetch_one(pool);
+
+    let (rows, total_results) = tokio::try_join!(rows, total_results).map_err(database_error)?;
+
+    Ok(DatabasePage {
+        rows,
+        page,
+        total_results: total_results as u64,
+    })
+}
@@ -0,0 +1,145 @@
+//! Routes for choosing which of a movie's posters is shown in a user's own lists, such as their
+//! watchlist.
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::put,
+    Json, Router,
+};
+use axum_login::AuthSession;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::Backend,
+    discover::get_tmdb_client,
+    tmdb::{client::ApiFetchError, queries::movie_details::MovieImagesRequest},
+};
+
+/// Builds the router for preferred poster routes. These are nested under `/api/v1/me`.
+pub fn build_router(pool: PgPool) -> Router {
+    Router::new()
+        .route(
+            "/posters/{id}",
+            put(update_preferred_poster).delete(remove_preferred_poster),
+        )
+        .with_state(pool)
+}
+
+/// The poster a user has chosen for a movie.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PreferredPoster {
+    /// The TMDB path of the poster, as returned in the movie's image gallery.
+    path: String,
+}
+
+/// Loads the posters a user has chosen for any of the given movies, keyed by movie ID. Movies
+/// without a chosen poster are left out.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the query fails.
+pub async fn load_preferred_posters(
+    pool: &PgPool,
+    user_id: Uuid,
+    movie_ids: &[i64],
+) -> Result<HashMap<u64, String>, sqlx::Error> {
+    let rows: Vec<(i64, String)> = sqlx::query_as(
+        "SELECT movie_id, poster_path FROM user_movie_posters
+        WHERE user_id = $1 AND movie_id = ANY($2)",
+    )
+    .bind(user_id)
+    .bind(movie_ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(movie_id, path)| (movie_id as u64, path))
+        .collect())
+}
+
+/// Checks that a poster is one of a movie's posters on TMDB, in any language.
+///
+/// # Errors
+///
+/// Returns [`ApiFetchError::InvalidQueryParam`] if the movie has no such poster, or another
+/// [`ApiFetchError`] if the movie's images cannot be fetched.
+async fn validate_poster(movie_id: u64, path: &str) -> Result<(), ApiFetchError> {
+    let images = MovieImagesRequest::new()
+        .fetch_images(&get_tmdb_client(), movie_id)
+        .await?;
+
+    if images.posters.iter().any(|poster| poster.file_path == path) {
+        Ok(())
+    } else {
+        Err(ApiFetchError::InvalidQueryParam {
+            param: "path",
+            reason: format!("`{}` is not a poster of movie {}", path, movie_id),
+        })
+    }
+}
+
+/// Sets the poster shown for a movie in the logged in user's lists.
+async fn update_preferred_poster(
+    auth_session: AuthSession<Backend>,
+    State(pool): State<PgPool>,
+    Path(movie_id): Path<u64>,
+    Json(poster): Json<PreferredPoster>,
+) -> impl IntoResponse {
+    let user = match auth_session.user {
+        Some(user) => user,
+        None => return StatusCode::UNAUTHORIZED.into_response(),
+    };
+
+    if let Err(e) = validate_poster(movie_id, &poster.path).await {
+        return e.into_response();
+    }
+
+    match sqlx::query(
+        "INSERT INTO user_movie_posters (user_id, movie_id, poster_path) VALUES ($1, $2, $3)
+        ON CONFLICT (user_id, movie_id)
+        DO UPDATE SET poster_path = EXCLUDED.poster_path, updated_at = NOW()",
+    )
+    .bind(user.id)
+    .bind(movie_id as i64)
+    .bind(&poster.path)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Json(poster).into_response(),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Goes back to showing TMDB's poster for a movie in the logged in user's lists.
+async fn remove_preferred_poster(
+    auth_session: AuthSession<Backend>,
+    State(pool): State<PgPool>,
+    Path(movie_id): Path<u64>,
+) -> impl IntoResponse {
+    let user = match auth_session.user {
+        Some(user) => user,
+        None => return StatusCode::UNAUTHORIZED,
+    };
+
+    match sqlx::query("DELETE FROM user_movie_posters WHERE user_id = $1 AND movie_id = $2")
+        .bind(user.id)
+        .bind(movie_id as i64)
+        .execute(&pool)
+        .await
+    {
+        Ok(_) => StatusCode::OK,
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! User preferences that change what the API returns for a user, such as the region used for
+//! watch provider availability and the streaming services they are subscribed to.
+use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::get, Json, Router};
+use axum_login::AuthSession;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::{Backend, User},
+    discover::get_tmdb_client,
+    filters::{validate_country, validate_language},
+    tmdb::{
+        client::{ApiFetchError, TMDBClient},
+        queries::certifications::ParentalFilter,
+    },
+};
+
+/// Builds the router for preference routes. These are nested under `/api/v1/me`.
+pub fn build_router(pool: PgPool) -> Router {
+    Router::new()
+        .route(
+            "/preferences",
+            get(fetch_preferences).put(update_preferences),
+        )
+        .route(
+            "/providers",
+            get(fetch_watch_providers).put(update_watch_providers),
+        )
+        .with_state(pool)
+}
+
+/// A user's general preferences.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Preferences {
+    /// The ISO 3166-1 code of the region used for watch provider availability, release dates
+    /// and certifications.
+    region: Option<String>,
+    /// The most restrictive age certification in the region that movie lists may include. Movie
+    /// lists are not filtered if this is not set.
+    #[serde(rename = "maxCertification")]
+    max_certification: Option<String>,
+    /// The language TMDB content is returned in (e.g., `pt-BR`). The request's `Accept-Language`
+    /// header is used if this is not set.
+    language: Option<String>,
+}
+
+/// The streaming services a user is subscribed to.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WatchProviderPreferences {
+    /// The TMDB IDs of the watch providers.
+    providers: Vec<u64>,
+}
+
+/// The streaming services a user is subscribed to, along with the region they are in.
+#[derive(Debug)]
+pub struct WatchServices {
+    /// The ISO 3166-1 code of the user's region.
+    pub region: String,
+    /// The TMDB IDs of the watch providers the user is subscribed to.
+    pub provider_ids: Vec<u64>,
+}
+
+/// Loads the TMDB IDs of the watch providers a user is subscribed to.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the query fails.
+pub async fn load_watch_providers(pool: &PgPool, user_id: Uuid) -> Result<Vec<u64>, sqlx::Error> {
+    let ids: Vec<i64> = sqlx::query_scalar(
+        "SELECT provider_id FROM user_watch_providers WHERE user_id = $1 ORDER BY provider_id",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ids.into_iter().map(|id| id as u64).collect())
+}
+
+/// Loads the streaming services a user is subscribed to. Returns `None` if the user has not
+/// saved a region or any watch providers, as availability cannot be checked without both.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the query fails.
+pub async fn load_watch_services(
+    pool: &PgPool,
+    user: &User,
+) -> Result<Option<WatchServices>, sqlx::Error> {
+    let Some(region) = user.region.clone() else {
+        return Ok(None);
+    };
+
+    let provider_ids = load_watch_providers(pool, user.id).await?;
+
+    if provider_ids.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(WatchServices {
+        region,
+        provider_ids,
+    }))
+}
+
+/// Loads the parental filter a user has set, if any.
+///
+/// # Arguments
+///
+/// * `client` - The `TMDBClient` instance used to fetch the certification list.
+/// * `user` - The user to load the parental filter of, if logged in.
+///
+/// # Errors
+///
+/// Returns an [`ApiFetchError`] if the certification list cannot be fetched.
+pub async fn load_parental_filter(
+    client: &TMDBClient,
+    user: Option<&User>,
+) -> Result<Option<ParentalFilter>, ApiFetchError> {
+    match user.map(|user| (&user.region, &user.max_certification)) {
+        Some((Some(region), Some(max_certification))) => {
+            ParentalFilter::new(client, region.clone(), max_certification.clone()).await
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Checks that a user's new preferences are valid.
+///
+/// # Errors
+///
+/// Returns [`ApiFetchError::InvalidQueryParam`] if a preference fails validation, or another
+/// [`ApiFetchError`] if the certification list cannot be fetched.
+async fn validate_preferences(preferences: &Preferences) -> Result<(), ApiFetchError> {
+    if let Some(region) = &preferences.region {
+        validate_country("region", region)?;
+    }
+
+    if let Some(language) = &preferences.language {
+        validate_language("language", language)?;
+    }
+
+    match (&preferences.max_certification, &preferences.region) {
+        (Some(max_certification), Some(region)) => {
+            let filter = ParentalFilter::new(
+                &get_tmdb_client(),
+                region.clone(),
+                max_certification.clone(),
+            )
+            .await?;
+
+            if filter.is_none() {
+                return Err(ApiFetchError::InvalidQueryParam {
+                    param: "maxCertification",
+                    reason: format!(
+                        "`{}` is not a certification used in {}",
+                        max_certification, region
+                    ),
+                });
+            }
+        }
+        (Some(_), None) => {
+            return Err(ApiFetchError::InvalidQueryParam {
+                param: "region",
+                reason: "required when `maxCertification` is set".to_owned(),
+            });
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Fetches the logged in user's preferences.
+async fn fetch_preferences(auth_session: AuthSession<Backend>) -> impl IntoResponse {
+    match auth_session.user {
+        Some(user) => Json(Preferences {
+            region: user.region,
+            max_certification: user.max_certification,
+            language: user.language,
+        })
+        .into_response(),
+        None => StatusCode::UNAUTHORIZED.into_response(),
+    }
+}
+
+/// Replaces the logged in user's preferences.
+async fn update_preferences(
+    auth_session: AuthSession<Backend>,
+    State(pool): State<PgPool>,
+    Json(preferences): Json<Preferences>,
+) -> impl IntoResponse {
+    let user = match auth_session.user {
+        Some(user) => user,
+        None => return StatusCode::UNAUTHORIZED.into_response(),
+    };
+
+    if let Err(e) = validate_preferences(&preferences).await {
+        return e.into_response();
+    }
+
+    match sqlx::query(
+        "UPDATE users SET region = $1, max_certification = $2, language = $3, updated_at = NOW()
+        WHERE id = $4",
+    )
+    .bind(&preferences.region)
+    .bind(&preferences.max_certification)
+    .bind(&preferences.language)
+    .bind(user.id)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Json(preferences).into_response(),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Fetches the watch providers the logged in user is subscribed to.
+async fn fetch_watch_providers(
+    auth_session: AuthSession<Backend>,
+    State(pool): State<PgPool>,
+) -> impl IntoResponse {
+    let user = match auth_session.user {
+        Some(user) => user,
+        None => return StatusCode::UNAUTHORIZED.into_response(),
+    };
+
+    match load_watch_providers(&pool, user.id).await {
+        Ok(providers) => Json(WatchProviderPreferences { providers }).into_response(),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Replaces the watch providers the logged in user is subscribed to.
+async fn update_watch_providers(
+    auth_session: AuthSession<Backend>,
+    State(pool): State<PgPool>,
+    Json(mut preferences): Json<WatchProviderPreferences>,
+) -> impl IntoResponse {
+    let user = match auth_session.user {
+        Some(user) => user,
+        None => return StatusCode::UNAUTHORIZED.into_response(),
+    };
+
+    preferences.providers.sort_unstable();
+    preferences.providers.dedup();
+
+    match replace_watch_providers(&pool, user.id, &preferences.providers).await {
+        Ok(()) => Json(preferences).into_response(),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Replaces every watch provider a user is subscribed to in a single transaction.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if any query fails, in which case nothing is changed.
+async fn replace_watch_providers(
+    pool: &PgPool,
+    user_id: Uuid,
+    provider_ids: &[u64],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM user_watch_providers WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let provider_ids: Vec<i64> = provider_ids.iter().map(|id| *id as i64).collect();
+
+    sqlx::query(
+        "INSERT INTO user_watch_providers (user_id, provider_id)
+        SELECT $1, UNNEST($2::BIGINT[])
+        ON CONFLICT DO NOTHING",
+    )
+    .bind(user_id)
+    .bind(&provider_ids)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
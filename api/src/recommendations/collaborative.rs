@@ -0,0 +1,141 @@
+//! Item-item collaborative filtering over Cinescore interactions.
+//!
+//! Every user's interactions are turned into a weight per movie (see
+//! [`super::load_signals`]). Two movies are similar when the same users feel the same way about
+//! them, measured by the cosine similarity of their weight vectors.
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use super::{Candidate, Source};
+
+/// The minimum number of users that must have interacted with both movies for their similarity
+/// to be trusted.
+const MIN_CO_RATERS: u32 = 2;
+
+/// The number of most similar movies kept for each movie.
+const MAX_NEIGHBOURS: usize = 50;
+
+/// Every user's preference weight for every movie they have interacted with.
+pub type Signals = HashMap<Uuid, HashMap<u64, f64>>;
+
+/// The most similar movies for each movie, sorted by descending similarity.
+pub type Neighbours = HashMap<u64, Vec<(u64, f64)>>;
+
+/// Computes the most similar movies for every movie in `signals`.
+///
+/// Only positive similarities between movies with at least [`MIN_CO_RATERS`] users in common are
+/// kept, and each movie keeps at most [`MAX_NEIGHBOURS`] neighbours.
+///
+/// # Arguments
+///
+/// * `signals` - Every user's preference weight for every movie they have interacted with.
+pub fn item_neighbours(signals: &Signals) -> Neighbours {
+    let mut norms: HashMap<u64, f64> = HashMap::new();
+    let mut dot_products: HashMap<(u64, u64), (f64, u32)> = HashMap::new();
+
+    for movies in signals.values() {
+        let movies: Vec<(u64, f64)> = movies.iter().map(|(id, w)| (*id, *w)).collect();
+
+        for (i, (a, weight_a)) in movies.iter().enumerate() {
+            *norms.entry(*a).or_default() += weight_a * weight_a;
+
+            for (b, weight_b) in &movies[i + 1..] {
+                let key = if a < b { (*a, *b) } else { (*b, *a) };
+                let entry = dot_products.entry(key).or_default();
+                entry.0 += weight_a * weight_b;
+                entry.1 += 1;
+            }
+        }
+    }
+
+    let mut neighbours: Neighbours = HashMap::new();
+
+    for ((a, b), (dot_product, co_raters)) in dot_products {
+        if co_raters < MIN_CO_RATERS {
+            continue;
+        }
+
+        let norm = norms.get(&a).copied().unwrap_or_default().sqrt()
+            * norms.get(&b).copied().unwrap_or_default().sqrt();
+
+        if norm == 0.0 {
+            continue;
+        }
+
+        let similarity = dot_product / norm;
+
+        if similarity > 0.0 {
+            neighbours.entry(a).or_default().push((b, similarity));
+            neighbours.entry(b).or_default().push((a, similarity));
+        }
+    }
+
+    for similar in neighbours.values_mut() {
+        similar.sort_by(|a, b| b.1.total_cmp(&a.1));
+        similar.truncate(MAX_NEIGHBOURS);
+    }
+
+    neighbours
+}
+
+/// Recommends movies for a single user from the movies similar to the ones they liked.
+///
+/// Each candidate is scored by the sum of its similarity to every movie the user liked, weighted
+/// by how much they liked it. The liked movie contributing the most becomes the reason for the
+/// recommendation.
+///
+/// # Arguments
+///
+/// * `user_signals` - The user's preference weight for every movie they have interacted with.
+/// * `seen` - Movies that should never be recommended, such as ones already watchlisted.
+/// * `neighbours` - The output of [`item_neighbours`].
+/// * `limit` - The maximum number of candidates to return.
+pub fn recommend(
+    user_signals: &HashMap<u64, f64>,
+    seen: &HashSet<u64>,
+    neighbours: &Neighbours,
+    limit: usize,
+) -> Vec<Candidate> {
+    // candidate movie ID -> (score, best contribution, reason movie ID)
+    let mut scores: HashMap<u64, (f64, f64, u64)> = HashMap::new();
+
+    for (liked_id, weight) in user_signals.iter().filter(|(_, w)| **w > 0.0) {
+        let Some(similar) = neighbours.get(liked_id) else {
+            continue;
+        };
+
+        for (candidate_id, similarity) in similar {
+            if seen.contains(candidate_id) || user_signals.contains_key(candidate_id) {
+                continue;
+            }
+
+            let contribution = similarity * weight;
+            let entry = scores
+                .entry(*candidate_id)
+                .or_insert((0.0, f64::MIN, *liked_id));
+
+            entry.0 += contribution;
+            if contribution > entry.1 {
+                entry.1 = contribution;
+                entry.2 = *liked_id;
+            }
+        }
+    }
+
+    let mut candidates: Vec<Candidate> = scores
+        .into_iter()
+        .map(|(movie_id, (score, _, reason_movie_id))| Candidate {
+            movie_id,
+            score,
+            source: Source::Collaborative,
+            title: None,
+            poster_path: None,
+            reason_movie_id: Some(reason_movie_id),
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates.truncate(limit);
+    candidates
+}
@@ -0,0 +1,308 @@
+//! Content-based recommendations, used for users who have not interacted with enough movies for
+//! collaborative filtering to find anything.
+//!
+//! The genres, directors and top-billed cast of the movies a user liked are tallied from their
+//! snapshots in the `movies` table, and TMDB's discover endpoint is used to find other movies
+//! sharing the most common ones.
+use std::collections::{HashMap, HashSet};
+
+use sqlx::PgPool;
+use tokio::task::JoinSet;
+
+use crate::{
+    language::PreferredLanguage,
+    metadata::{load_movies_with_credits, CachedMovie},
+    tmdb::{
+        client::{ApiFetchError, TMDBClient},
+        queries::{
+            movie_lists::DiscoverMoviesRequest,
+            traits::{
+                IncludeAdultQueryParam, SortBy, SortByQueryParam, VoteCountQueryParam,
+                WithCastQueryParam, WithCrewQueryParam, WithGenresQueryParam,
+            },
+        },
+    },
+};
+
+use super::{Candidate, Source};
+
+/// The number of a user's most liked movies used as seeds.
+const MAX_SEEDS: usize = 10;
+
+/// The minimum number of TMDB votes a movie needs before it can be recommended.
+const MIN_VOTE_COUNT: u32 = 50;
+
+/// The parts of a movie that content-based recommendations are made from.
+#[derive(Debug, Clone)]
+pub struct ContentProfile {
+    /// IDs of the movie's genres.
+    pub genre_ids: Vec<u64>,
+    /// IDs of the movie's directors.
+    pub director_ids: Vec<u64>,
+    /// IDs of the movie's top-billed cast members.
+    pub cast_ids: Vec<u64>,
+}
+
+impl From<CachedMovie> for ContentProfile {
+    /// Takes the content profile of a movie from its snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The source [`CachedMovie`] to take the profile from
+    ///
+    /// # Returns
+    ///
+    /// A new [`ContentProfile`] instance, without directors or cast if the snapshot has no
+    /// credits.
+    fn from(value: CachedMovie) -> Self {
+        let ids = |ids: Vec<i64>| ids.into_iter().map(|id| id as u64).collect();
+
+        Self {
+            genre_ids: ids(value.genre_ids),
+            director_ids: ids(value.director_ids.unwrap_or_default()),
+            cast_ids: ids(value.cast_ids.unwrap_or_default()),
+        }
+    }
+}
+
+/// A kind of feature shared between movies, along with how much a shared feature counts for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Feature {
+    /// A shared genre ID.
+    Genre(u64),
+    /// A shared director ID.
+    Director(u64),
+    /// A shared cast member ID.
+    Cast(u64),
+}
+
+impl Feature {
+    /// How strongly sharing this feature suggests a user will like a movie.
+    fn weight(&self) -> f64 {
+        match self {
+            Feature::Genre(_) => 1.0,
+            Feature::Director(_) => 3.0,
+            Feature::Cast(_) => 1.5,
+        }
+    }
+}
+
+/// Picks the movies a user liked most, which their content-based recommendations are made from.
+///
+/// # Arguments
+///
+/// * `user_signals` - The user's preference weight for every movie they have interacted with.
+///
+/// # Returns
+///
+/// Up to [`MAX_SEEDS`] movie IDs and their weights, strongest first.
+pub fn seeds(user_signals: &HashMap<u64, f64>) -> Vec<(u64, f64)> {
+    let mut seeds: Vec<(u64, f64)> = user_signals
+        .iter()
+        .filter(|(_, weight)| **weight > 0.0)
+        .map(|(id, weight)| (*id, *weight))
+        .collect();
+    seeds.sort_by(|a, b| b.1.total_cmp(&a.1));
+    seeds.truncate(MAX_SEEDS);
+    seeds
+}
+
+/// Loads the content profiles of several movies from their snapshots, fetching any movie without
+/// a snapshot with credits from TMDB (see [`load_movies_with_credits`]). Movies that cannot be
+/// fetched are left out.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `client` - The `TMDBClient` instance used to fetch movies without a snapshot.
+/// * `movie_ids` - The IDs of the movies to load the profiles of.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the snapshots cannot be loaded or saved.
+pub async fn load_profiles(
+    pool: &PgPool,
+    client: &TMDBClient,
+    movie_ids: &[u64],
+) -> Result<HashMap<u64, ContentProfile>, sqlx::Error> {
+    Ok(
+        load_movies_with_credits(pool, client, movie_ids, &PreferredLanguage::default())
+            .await?
+            .into_iter()
+            .map(|movie| (movie.movie_id as u64, ContentProfile::from(movie)))
+            .collect(),
+    )
+}
+
+/// Recommends movies for a single user based on what the movies they liked have in common. The
+/// discover requests are fetched concurrently.
+///
+/// # Arguments
+///
+/// * `client` - The `TMDBClient` instance used to make API requests.
+/// * `profiles` - The content profiles of the user's seed movies (see [`seeds`]).
+/// * `user_signals` - The user's preference weight for every movie they have interacted with.
+/// * `seen` - Movies that should never be recommended, such as ones already watchlisted.
+/// * `limit` - The maximum number of candidates to return.
+///
+/// # Errors
+///
+/// This function will return a [`ApiFetchError`] if a discover request fails. Seed movies
+/// without a profile are skipped.
+pub async fn recommend(
+    client: &TMDBClient,
+    profiles: &HashMap<u64, ContentProfile>,
+    user_signals: &HashMap<u64, f64>,
+    seen: &HashSet<u64>,
+    limit: usize,
+) -> Result<Vec<Candidate>, ApiFetchError> {
+    // feature -> (total weight, strongest seed weight, strongest seed ID)
+    let mut features: HashMap<Feature, (f64, f64, u64)> = HashMap::new();
+
+    for (seed_id, seed_weight) in seeds(user_signals) {
+        let Some(profile) = profiles.get(&seed_id) else {
+            continue;
+        };
+
+        let seed_features = profile
+            .genre_ids
+            .iter()
+            .copied()
+            .map(Feature::Genre)
+            .chain(profile.director_ids.iter().copied().map(Feature::Director))
+            .chain(profile.cast_ids.iter().copied().map(Feature::Cast));
+
+        for feature in seed_features {
+            let entry = features.entry(feature).or_insert((0.0, f64::MIN, seed_id));
+            entry.0 += feature.weight() * seed_weight;
+            if seed_weight > entry.1 {
+                entry.1 = seed_weight;
+                entry.2 = seed_id;
+            }
+        }
+    }
+
+    let top_features = |matches: fn(&Feature) -> Option<u64>, count: usize| {
+        let mut selected: Vec<(u64, f64, u64)> = features
+            .iter()
+            .filter_map(|(feature, (weight, _, seed_id))| {
+                matches(feature).map(|id| (id, *weight, *seed_id))
+            })
+            .collect();
+        selected.sort_by(|a, b| b.1.total_cmp(&a.1));
+        selected.truncate(count);
+        selected
+    };
+
+    let directors = top_features(
+        |f| match f {
+            Feature::Director(id) => Some(*id),
+            _ => None,
+        },
+        3,
+    );
+    let cast = top_features(
+        |f| match f {
+            Feature::Cast(id) => Some(*id),
+            _ => None,
+        },
+        5,
+    );
+    let genres = top_features(
+        |f| match f {
+            Feature::Genre(id) => Some(*id),
+            _ => None,
+        },
+        2,
+    );
+
+    let mut requests = Vec::new();
+
+    if let Some((_, weight, seed_id)) = directors.first() {
+        let ids: Vec<u64> = directors.iter().map(|(id, _, _)| *id).collect();
+        requests.push((
+            DiscoverMoviesRequest::new().with_crew(&ids, false),
+            *weight,
+            *seed_id,
+        ));
+    }
+
+    if let Some((_, weight, seed_id)) = cast.first() {
+        let ids: Vec<u64> = cast.iter().map(|(id, _, _)| *id).collect();
+        requests.push((
+            DiscoverMoviesRequest::new().with_cast(&ids, false),
+            *weight,
+            *seed_id,
+        ));
+    }
+
+    if let Some((_, weight, seed_id)) = genres.first() {
+        let ids: Vec<u64> = genres.iter().map(|(id, _, _)| *id).collect();
+        requests.push((
+            DiscoverMoviesRequest::new().with_genres(&ids, true),
+            *weight,
+            *seed_id,
+        ));
+    }
+
+    let mut tasks = JoinSet::new();
+
+    for (index, (request, weight, seed_id)) in requests.into_iter().enumerate() {
+        let client = client.clone();
+        tasks.spawn(async move {
+            let results = request
+                .include_adult(false)
+                .vote_count_gte(MIN_VOTE_COUNT)
+                .sort_by(SortBy::PopularityDesc)
+                .fetch_results(&client)
+                .await;
+            (index, results, weight, seed_id)
+        });
+    }
+
+    let mut responses = Vec::new();
+
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok((index, results, weight, seed_id)) => {
+                responses.push((index, results?, weight, seed_id));
+            }
+            Err(e) => tracing::error!("Content discover task failed: {}", e),
+        }
+    }
+
+    // the first request a movie appears in decides its title and reason, so results are
+    // handled in the order the requests were made rather than the order they finished in
+    responses.sort_by_key(|(index, _, _, _)| *index);
+
+    let mut candidates: HashMap<u64, Candidate> = HashMap::new();
+
+    for (_, results, weight, seed_id) in responses {
+        for (position, movie) in results.results.into_iter().enumerate() {
+            let movie = movie.base;
+
+            if seen.contains(&movie.id) || user_signals.contains_key(&movie.id) {
+                continue;
+            }
+
+            // earlier results are more popular, so count for slightly more
+            let score = weight / (1.0 + position as f64 * 0.1);
+
+            let candidate = candidates.entry(movie.id).or_insert(Candidate {
+                movie_id: movie.id,
+                score: 0.0,
+                source: Source::Content,
+                title: Some(movie.title),
+                poster_path: movie.poster_path,
+                reason_movie_id: Some(seed_id),
+            });
+            candidate.score += score;
+        }
+    }
+
+    let mut candidates: Vec<Candidate> = candidates.into_values().collect();
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates.truncate(limit);
+
+    Ok(candidates)
+}
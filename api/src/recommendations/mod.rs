@@ -0,0 +1,555 @@
+//! Personalised movie recommendations built from Cinescore ratings, likes and top fives.
+//!
+//! Recommendations are precomputed for every user by a background job ([`precompute_all`]) and
+//! stored in the `user_recommendations` table, so serving them is a single query. Users with
+//! enough interactions get item-item collaborative filtering ([`collaborative`]), and anyone it
+//! cannot find enough movies for falls back to content-based recommendations ([`content`]).
+//!
+//! Collaborative filtering only needs Postgres, so it can be tried locally by loading
+//! `seeds/recommendations.sql` into a development database and running the job with
+//! `cinescore_api enqueue precompute_recommendations` while the API is running. The tests run
+//! against the same seed data, in a fresh database created on the server `DATABASE_URL` points
+//! at.
+use std::collections::{HashMap, HashSet};
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::get, Json, Router};
+use axum_login::AuthSession;
+use sqlx::{prelude::FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    auth::Backend,
+    frontend_models::recommendations::FrontendRecommendationList,
+    language::PreferredLanguage,
+    metadata::{load_movies, CachedMovie},
+    tmdb::client::{ApiFetchError, TMDBClient},
+};
+
+use self::{collaborative::Signals, content::ContentProfile};
+
+mod collaborative;
+mod content;
+
+/// How many recommendations are precomputed for each user.
+const RECOMMENDATIONS_PER_USER: usize = 30;
+
+/// If collaborative filtering finds fewer movies than this for a user, the rest are filled in
+/// with content-based recommendations.
+const MIN_COLLABORATIVE_RESULTS: usize = 10;
+
+/// Builds the router for recommendation routes. These are nested under `/api/v1/me`.
+pub fn build_router(pool: PgPool) -> Router {
+    Router::new()
+        .route("/recommendations", get(fetch_recommendations))
+        .with_state(pool)
+}
+
+/// Represents errors that can be encountered while computing recommendations.
+#[derive(Debug, thiserror::Error)]
+pub enum RecommendationError {
+    /// An error from the database
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    /// An error from the TMDB API
+    #[error(transparent)]
+    Tmdb(#[from] ApiFetchError),
+}
+
+/// Which recommender produced a recommendation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Item-item collaborative filtering over Cinescore interactions
+    Collaborative,
+    /// Content-based recommendations from genres, keywords, directors and cast
+    Content,
+}
+
+impl Source {
+    /// The representation of the source stored in the database.
+    fn as_str(&self) -> &'static str {
+        match self {
+            Source::Collaborative => "collaborative",
+            Source::Content => "content",
+        }
+    }
+}
+
+/// A movie that may be recommended to a user.
+#[derive(Debug)]
+pub struct Candidate {
+    /// The ID of the recommended movie.
+    movie_id: u64,
+    /// How strongly the movie is recommended. Only comparable between candidates from the same
+    /// source.
+    score: f64,
+    /// Which recommender produced the candidate.
+    source: Source,
+    /// The title of the movie, if already known.
+    title: Option<String>,
+    /// Path to the movie's poster image, if already known.
+    poster_path: Option<String>,
+    /// The movie the user liked that led to this candidate, if any.
+    reason_movie_id: Option<u64>,
+}
+
+/// A precomputed recommendation, as stored in the database.
+#[derive(Debug, FromRow)]
+pub struct RecommendationRow {
+    /// The ID of the recommended movie.
+    pub movie_id: i64,
+    /// How strongly the movie is recommended.
+    pub score: f64,
+    /// Which recommender produced the recommendation.
+    pub source: String,
+    /// The title of the recommended movie, if it could be looked up.
+    pub title: Option<String>,
+    /// Path to the recommended movie's poster image.
+    pub poster_path: Option<String>,
+    /// The ID of the movie the user liked that led to this recommendation.
+    pub reason_movie_id: Option<i64>,
+    /// The title of the movie the user liked that led to this recommendation.
+    pub reason_title: Option<String>,
+}
+
+/// Loads every user's preference weight for every movie they have rated, liked or put in their
+/// top five. Ratings map from -1 (1 star) to 1 (5 stars), a like counts for 1, and a top five
+/// entry counts for between 0.4 (rank 5) and 2 (rank 1). A movie's weight is the sum of all of
+/// its signals.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the query fails.
+async fn load_signals(pool: &PgPool) -> Result<Signals, sqlx::Error> {
+    let rows: Vec<(Uuid, i64, f64)> = sqlx::query_as(
+        "SELECT user_id, movie_id, SUM(weight)::float8 FROM (
+            SELECT user_id, movie_id, (rating - 3) / 2.0 AS weight FROM movie_ratings
+            UNION ALL
+            SELECT user_id, movie_id, 1.0 AS weight FROM movie_likes
+            UNION ALL
+            SELECT user_id, movie_id, (6 - rank) / 2.5 AS weight FROM user_top_five
+        ) signals
+        GROUP BY user_id, movie_id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut signals: Signals = HashMap::new();
+
+    for (user_id, movie_id, weight) in rows {
+        signals
+            .entry(user_id)
+            .or_default()
+            .insert(movie_id as u64, weight);
+    }
+
+    Ok(signals)
+}
+
+/// Loads every user's watchlist. Watchlisted movies are never recommended, since the user
+/// already knows about them.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if the query fails.
+async fn load_watchlists(pool: &PgPool) -> Result<HashMap<Uuid, HashSet<u64>>, sqlx::Error> {
+    let rows: Vec<(Uuid, i64)> = sqlx::query_as("SELECT user_id, movie_id FROM movie_watchlist")
+        .fetch_all(pool)
+        .await?;
+
+    let mut watchlists: HashMap<Uuid, HashSet<u64>> = HashMap::new();
+
+    for (user_id, movie_id) in rows {
+        watchlists
+            .entry(user_id)
+            .or_default()
+            .insert(movie_id as u64);
+    }
+
+    Ok(watchlists)
+}
+
+/// Fills in a user's recommendations with content-based ones, up to
+/// [`RECOMMENDATIONS_PER_USER`].
+///
+/// # Arguments
+///
+/// * `client` - The `TMDBClient` instance used for content-based recommendations.
+/// * `profiles` - The content profiles of every user's seed movies.
+/// * `user_signals` - The user's preference weight for every movie they have interacted with.
+/// * `seen` - Movies that should never be recommended.
+/// * `candidates` - The user's collaborative filtering recommendations, which are extended.
+async fn add_content_recommendations(
+    client: &TMDBClient,
+    profiles: &HashMap<u64, ContentProfile>,
+    user_signals: &HashMap<u64, f64>,
+    seen: &HashSet<u64>,
+    candidates: &mut Vec<Candidate>,
+) {
+    let mut seen = seen.clone();
+    seen.extend(candidates.iter().map(|c| c.movie_id));
+
+    let remaining = RECOMMENDATIONS_PER_USER - candidates.len();
+
+    match content::recommend(client, profiles, user_signals, &seen, remaining).await {
+        Ok(content_candidates) => candidates.extend(content_candidates),
+        Err(e) => tracing::warn!("Content-based recommendations failed: {}", e),
+    }
+}
+
+/// Replaces a user's stored recommendations. Titles and posters missing from candidates are
+/// taken from the movies loaded for them, and left empty for any that could not be loaded.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `user_id` - The user the recommendations are for.
+/// * `candidates` - The recommendations, best first.
+/// * `movies` - The details of recommended movies and the movies that led to them, by ID.
+///
+/// # Errors
+///
+/// Returns a [`sqlx::Error`] if writing to the database fails.
+async fn store_recommendations(
+    pool: &PgPool,
+    user_id: Uuid,
+    candidates: Vec<Candidate>,
+    movies: &HashMap<u64, CachedMovie>,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM user_recommendations WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    for (rank, mut candidate) in candidates.into_iter().enumerate() {
+        if candidate.title.is_none() {
+            match movies.get(&candidate.movie_id) {
+                Some(movie) => {
+                    candidate.title = Some(movie.title.clone());
+                    candidate.poster_path = movie.poster_path.clone();
+                }
+                None => {
+                    tracing::warn!("Unable to look up recommended movie {}", candidate.movie_id)
+                }
+            }
+        }
+
+        let reason_title = candidate
+            .reason_movie_id
+            .and_then(|id| movies.get(&id))
+            .map(|movie| movie.title.clone());
+
+        sqlx::query(
+            "INSERT INTO user_recommendations
+                (user_id, movie_id, rank, score, source, title, poster_path, reason_movie_id, reason_title)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        )
+        .bind(user_id)
+        .bind(candidate.movie_id as i64)
+        .bind(rank as i32 + 1)
+        .bind(candidate.score)
+        .bind(candidate.source.as_str())
+        .bind(candidate.title)
+        .bind(candidate.poster_path)
+        .bind(candidate.reason_movie_id.map(|id| id as i64))
+        .bind(reason_title)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await
+}
+
+/// Recomputes the recommendations of every user with at least one interaction, and removes the
+/// recommendations of users who no longer have any.
+///
+/// Every user's recommendations are computed before any are stored, so the titles and posters
+/// they need can be loaded in one batch, from the movie snapshots where possible (see
+/// [`load_movies`]). The seed movies of users who fall back to content-based recommendations are
+/// likewise loaded together before any of their recommendations are made.
+///
+/// # Errors
+///
+/// Returns a [`RecommendationError`] if loading interactions or movies fails. Failing to store a
+/// single user's recommendations is logged and skipped.
+pub async fn precompute_all(pool: &PgPool, client: &TMDBClient) -> Result<(), RecommendationError> {
+    let signals = load_signals(pool).await?;
+    let watchlists = load_watchlists(pool).await?;
+    let neighbours = collaborative::item_neighbours(&signals);
+
+    tracing::info!(
+        "Computing recommendations for {} users from {} similar movies",
+        signals.len(),
+        neighbours.len()
+    );
+
+    let empty = HashSet::new();
+    let mut recommendations = Vec::with_capacity(signals.len());
+
+    for (user_id, user_signals) in &signals {
+        let seen = watchlists.get(user_id).unwrap_or(&empty);
+        let candidates =
+            collaborative::recommend(user_signals, seen, &neighbours, RECOMMENDATIONS_PER_USER);
+        recommendations.push((*user_id, user_signals, seen, candidates));
+    }
+
+    // users collaborative filtering did not find enough movies for fall back to content-based
+    // recommendations, whose seed movies are loaded together
+    let seed_ids: Vec<u64> = recommendations
+        .iter()
+        .filter(|(_, _, _, candidates)| candidates.len() < MIN_COLLABORATIVE_RESULTS)
+        .flat_map(|(_, user_signals, _, _)| content::seeds(user_signals))
+        .map(|(movie_id, _)| movie_id)
+        .collect::<HashSet<u64>>()
+        .into_iter()
+        .collect();
+
+    let profiles = content::load_profiles(pool, client, &seed_ids).await?;
+
+    for (_, user_signals, seen, candidates) in &mut recommendations {
+        if candidates.len() < MIN_COLLABORATIVE_RESULTS {
+            add_content_recommendations(client, &profiles, user_signals, seen, candidates).await;
+        }
+    }
+
+    let movie_ids: Vec<u64> = recommendations
+        .iter()
+        .flat_map(|(_, _, _, candidates)| candidates)
+        .flat_map(|candidate| {
+            let missing_title = candidate.title.is_none().then_some(candidate.movie_id);
+            missing_title.into_iter().chain(candidate.reason_movie_id)
+        })
+        .collect::<HashSet<u64>>()
+        .into_iter()
+        .collect();
+
+    let movies: HashMap<u64, CachedMovie> =
+        load_movies(pool, client, &movie_ids, &PreferredLanguage::default())
+            .await?
+            .into_iter()
+            .map(|movie| (movie.movie_id as u64, movie))
+            .collect();
+
+    for (user_id, _, _, candidates) in recommendations {
+        if let Err(e) = store_recommendations(pool, user_id, candidates, &movies).await {
+            tracing::error!(
+                "Failed to store recommendations for user {}: {}",
+                user_id,
+                e
+            );
+        }
+    }
+
+    let user_ids: Vec<Uuid> = signals.into_keys().collect();
+
+    sqlx::query("DELETE FROM user_recommendations WHERE user_id <> ALL($1)")
+        .bind(user_ids)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Fetches the logged in user's precomputed recommendations, best first.
+async fn fetch_recommendations(
+    auth_session: AuthSession<Backend>,
+    State(pool): State<PgPool>,
+) -> impl IntoResponse {
+    let user = match auth_session.user {
+        Some(user) => user,
+        None => return StatusCode::UNAUTHORIZED.into_response(),
+    };
+
+    match sqlx::query_as::<_, RecommendationRow>(
+        "SELECT movie_id, score, source, title, poster_path, reason_movie_id, reason_title
+        FROM user_recommendations
+        WHERE user_id = $1
+        ORDER BY rank",
+    )
+    .bind(user.id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(rows) => Json(FrontendRecommendationList::from(rows)).into_response(),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A seeded user who likes science fiction.
+    const ALICE: Uuid = Uuid::from_u128(1);
+    /// A seeded user who likes fantasy.
+    const DAVE: Uuid = Uuid::from_u128(4);
+    /// A seeded user who has only rated one movie.
+    const ERIN: Uuid = Uuid::from_u128(5);
+
+    /// A client that cannot reach TMDB, so that results only depend on the seed data.
+    fn offline_client() -> TMDBClient {
+        TMDBClient::new(String::new()).with_base_url("http://127.0.0.1:9")
+    }
+
+    /// The IDs of the movies recommended to a seeded user by collaborative filtering.
+    async fn collaborative_ids(pool: &PgPool, user_id: Uuid, seen: &HashSet<u64>) -> Vec<u64> {
+        let signals = load_signals(pool).await.unwrap();
+        let neighbours = collaborative::item_neighbours(&signals);
+
+        collaborative::recommend(
+            &signals[&user_id],
+            seen,
+            &neighbours,
+            RECOMMENDATIONS_PER_USER,
+        )
+        .into_iter()
+        .map(|candidate| candidate.movie_id)
+        .collect()
+    }
+
+    #[sqlx::test(fixtures(path = "../../seeds", scripts("recommendations")))]
+    async fn signals_combine_every_interaction(pool: PgPool) {
+        let signals = load_signals(&pool).await.unwrap();
+        let alice = &signals[&ALICE];
+
+        assert_eq!(signals.len(), 5);
+        // a 5 star rating and a like
+        assert_eq!(alice[&603], 2.0);
+        // a 5 star rating and first in the top five
+        assert_eq!(alice[&27205], 3.0);
+        // a 4 star rating
+        assert_eq!(alice[&157336], 0.5);
+        // a 2 star rating
+        assert_eq!(alice[&120], -0.5);
+        // watchlisting a movie is not a signal
+        assert!(!alice.contains_key(&78));
+    }
+
+    #[sqlx::test(fixtures(path = "../../seeds", scripts("recommendations")))]
+    async fn recommends_what_similar_users_liked(pool: PgPool) {
+        let signals = load_signals(&pool).await.unwrap();
+        let neighbours = collaborative::item_neighbours(&signals);
+        let seen = &load_watchlists(&pool).await.unwrap()[&ALICE];
+
+        let candidates = collaborative::recommend(&signals[&ALICE], seen, &neighbours, 30);
+
+        // bob and carol loved Inception as much as alice, and The Dark Knight too
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].movie_id, 155);
+        assert_eq!(candidates[0].reason_movie_id, Some(27205));
+        assert_eq!(candidates[0].source, Source::Collaborative);
+        assert!(candidates[0].score > 0.0);
+
+        assert_eq!(
+            collaborative_ids(&pool, ERIN, &HashSet::new()).await,
+            vec![27205]
+        );
+        // nobody else shares dave's taste in fantasy
+        assert!(collaborative_ids(&pool, DAVE, &HashSet::new())
+            .await
+            .is_empty());
+    }
+
+    #[sqlx::test(fixtures(path = "../../seeds", scripts("recommendations")))]
+    async fn watchlisted_movies_are_not_recommended(pool: PgPool) {
+        let watchlists = load_watchlists(&pool).await.unwrap();
+
+        assert!(collaborative_ids(&pool, ALICE, &HashSet::new())
+            .await
+            .contains(&78));
+        assert!(!collaborative_ids(&pool, ALICE, &watchlists[&ALICE])
+            .await
+            .contains(&78));
+    }
+
+    #[sqlx::test(fixtures(path = "../../seeds", scripts("recommendations")))]
+    async fn content_profiles_come_from_snapshots(pool: PgPool) {
+        sqlx::query(
+            "INSERT INTO movies (movie_id, title, runtime, genre_ids, director_ids, cast_ids) VALUES
+                (120, 'The Fellowship of the Ring', 179, '{12, 14}', '{108}', '{109, 1327}'),
+                (121, 'The Two Towers', 179, '{12, 14}', NULL, NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let signals = load_signals(&pool).await.unwrap();
+        let seed_ids: Vec<u64> = content::seeds(&signals[&DAVE])
+            .into_iter()
+            .map(|(movie_id, _)| movie_id)
+            .collect();
+        let profiles = content::load_profiles(&pool, &offline_client(), &seed_ids)
+            .await
+            .unwrap();
+
+        // a snapshot without credits is fetched again, which fails offline
+        assert_eq!(profiles.len(), 1);
+        let fellowship = &profiles[&120];
+        assert_eq!(fellowship.genre_ids, vec![12, 14]);
+        assert_eq!(fellowship.director_ids, vec![108]);
+        assert_eq!(fellowship.cast_ids, vec![109, 1327]);
+    }
+
+    #[sqlx::test(fixtures(path = "../../seeds", scripts("recommendations")))]
+    async fn precompute_stores_recommendations(pool: PgPool) {
+        // snapshots, so titles can be looked up without TMDB
+        sqlx::query(
+            "INSERT INTO movies (movie_id, title, runtime) VALUES
+                (603, 'The Matrix', 136),
+                (155, 'The Dark Knight', 152),
+                (27205, 'Inception', 148),
+                (157336, 'Interstellar', 169)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // a user whose interactions have all been removed since their last recommendations
+        let stale_user = Uuid::from_u128(6);
+        sqlx::query(
+            "INSERT INTO users (id, username, email, password_hash)
+            VALUES ($1, 'seed_frank', 'frank@seed.invalid', '!')",
+        )
+        .bind(stale_user)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO user_recommendations (user_id, movie_id, rank, score, source)
+            VALUES ($1, 603, 1, 1.0, 'collaborative')",
+        )
+        .bind(stale_user)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        precompute_all(&pool, &offline_client()).await.unwrap();
+
+        let stored = |user_id: Uuid| {
+            sqlx::query_as::<_, RecommendationRow>(
+                "SELECT movie_id, score, source, title, poster_path, reason_movie_id, reason_title
+                FROM user_recommendations
+                WHERE user_id = $1
+                ORDER BY rank",
+            )
+            .bind(user_id)
+            .fetch_all(&pool)
+        };
+
+        let alice = stored(ALICE).await.unwrap();
+        assert_eq!(alice.len(), 1);
+        assert_eq!(alice[0].movie_id, 155);
+        assert_eq!(alice[0].source, "collaborative");
+        assert_eq!(alice[0].title.as_deref(), Some("The Dark Knight"));
+        assert_eq!(alice[0].reason_movie_id, Some(27205));
+        assert_eq!(alice[0].reason_title.as_deref(), Some("Inception"));
+
+        // content-based recommendations need TMDB, so dave has none
+        assert!(stored(DAVE).await.unwrap().is_empty());
+        assert!(stored(stale_user).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,372 @@
+//! Search over TMDB's movies, people, collections and keywords, merged with Cinescore's own users
+//! where it makes sense.
+use std::{sync::LazyLock, time::Duration};
+
+use axum::{extract::State, Json};
+use axum_login::AuthSession;
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    auth::Backend,
+    discover::{apply_parental_filter, apply_parental_filter_to_search, get_tmdb_client},
+    filters::validate_language,
+    frontend_models::{
+        keywords::FrontendKeywordList,
+        movies::FrontendMovieList,
+        people::FrontendPeopleList,
+        search::{FrontendSearchResult, FrontendSearchResults, FrontendUserListing},
+    },
+    language::PreferredLanguage,
+    pagination::validate_page,
+    tmdb::{
+        cache::TtlCache,
+        client::{ApiFetchError, TMDBClient},
+        models::{common::PaginatedSearchResult, search::MultiSearchResult},
+        queries::{
+            genres::resolve_genre_names,
+            search::{
+                SearchCollectionsRequest, SearchKeywordsRequest, SearchMoviesRequest,
+                SearchMultiRequest, SearchPeopleRequest,
+            },
+            traits::{
+                IncludeAdultQueryParam, LanguageQueryParam, PageQueryParam,
+                PrimaryReleaseYearQueryParam, Query, QueryQueryParam,
+            },
+        },
+    },
+};
+
+/// Parameters for search queries.
+#[derive(Deserialize)]
+pub struct SearchParams {
+    /// The search query string.
+    query: String,
+}
+
+/// Searches for movies matching the given query string.
+pub async fn search_movies(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    params: axum::extract::Query<SearchParams>,
+) -> Result<Json<FrontendMovieList>, ApiFetchError> {
+    let client = get_tmdb_client();
+
+    let movies = language
+        .apply(SearchMoviesRequest::new())
+        .query(params.query.clone())
+        .fetch(&client)
+        .await?;
+
+    Ok(Json(language.localise(
+        apply_parental_filter(&client, &auth_session, movies).await?,
+    )))
+}
+
+/// The most Cinescore users included in a page of multi search results.
+const MAX_USER_RESULTS: i64 = 5;
+
+/// The most results returned by autocomplete.
+const AUTOCOMPLETE_LIMIT: usize = 8;
+
+/// How long autocomplete results are cached for. This only needs to absorb the repeated requests
+/// made while someone is typing.
+const AUTOCOMPLETE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// The normalised search parameters autocomplete results are cached by: the query, language,
+/// whether adult results are included and the year.
+type AutocompleteKey = (String, Option<String>, bool, Option<u16>);
+
+/// The most autocomplete results cached at once.
+const AUTOCOMPLETE_CACHE_MAX_ENTRIES: usize = 1_000;
+
+/// Cache of autocomplete results, keyed by the normalised search parameters.
+static AUTOCOMPLETE_CACHE: LazyLock<TtlCache<AutocompleteKey, FrontendSearchResults>> =
+    LazyLock::new(|| TtlCache::new(AUTOCOMPLETE_CACHE_TTL, AUTOCOMPLETE_CACHE_MAX_ENTRIES));
+
+/// Parameters for multi search queries.
+#[derive(Deserialize)]
+pub struct MultiSearchParams {
+    /// The search query string.
+    query: String,
+    /// The page of results to fetch.
+    page: Option<u32>,
+    /// The language to return results in (e.g., `en-US`).
+    language: Option<String>,
+    /// Whether to include adult movies and people.
+    include_adult: Option<bool>,
+    /// Only include movies released in this year. TMDB applies the filter, so the totals only count
+    /// matching movies. People and collections are not affected.
+    year: Option<u16>,
+}
+
+/// Checks that a search query has something in it other than whitespace. A blank query would
+/// match every Cinescore user.
+///
+/// # Arguments
+/// * `query` - The search query.
+///
+/// # Errors
+/// Returns [`ApiFetchError::InvalidQueryParam`] if the query is blank.
+fn validate_search_query(query: &str) -> Result<(), ApiFetchError> {
+    if query.trim().is_empty() {
+        Err(ApiFetchError::InvalidQueryParam {
+            param: "query",
+            reason: "must not be blank".to_owned(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Searches TMDB for movies released in a year and for people, merged into a single page as if
+/// they came from a multi search. TMDB's multi search cannot filter by year, so movies are
+/// searched on their own to have TMDB filter them, which keeps the totals accurate.
+///
+/// # Arguments
+/// * `client` - The `TMDBClient` instance used to make API requests.
+/// * `params` - The search parameters.
+/// * `page` - The validated page of results to fetch.
+/// * `year` - The year movies must have been released in.
+///
+/// # Errors
+/// Returns an [`ApiFetchError`] if searching TMDB fails.
+async fn search_movies_in_year_and_people(
+    client: &TMDBClient,
+    params: &MultiSearchParams,
+    page: u32,
+    year: u16,
+) -> Result<PaginatedSearchResult<MultiSearchResult>, ApiFetchError> {
+    let include_adult = params.include_adult.unwrap_or(false);
+
+    let mut movies = SearchMoviesRequest::new()
+        .query(params.query.clone())
+        .include_adult(include_adult)
+        .primary_release_year(year.to_string())
+        .page(page);
+    let mut people = SearchPeopleRequest::new()
+        .query(params.query.clone())
+        .include_adult(include_adult)
+        .page(page);
+
+    if let Some(language) = &params.language {
+        movies = movies.language(language.clone());
+        people = people.language(language.clone());
+    }
+
+    let (movies, people) = tokio::join!(movies.fetch_results(client), people.fetch_results(client));
+    let (movies, people) = (movies?, people?);
+
+    Ok(PaginatedSearchResult {
+        page: u64::from(page),
+        total_pages: movies.total_pages.max(people.total_pages),
+        total_results: movies.total_results + people.total_results,
+        results: movies
+            .results
+            .into_iter()
+            .map(MultiSearchResult::Movie)
+            .chain(people.results.into_iter().map(MultiSearchResult::Person))
+            .collect(),
+    })
+}
+
+/// Searches TMDB for movies, people and collections, and Cinescore for users, merging the results
+/// into a single page. Cinescore users are only included on the first page. If a year is given,
+/// movies and people are searched separately so TMDB can filter the movies by it (see
+/// [`search_movies_in_year_and_people`]).
+///
+/// # Arguments
+/// * `client` - The `TMDBClient` instance used to make API requests.
+/// * `pool` - The database to search for users in.
+/// * `params` - The search parameters.
+///
+/// # Errors
+/// Returns [`ApiFetchError::InvalidQueryParam`] if a parameter fails validation, or another
+/// [`ApiFetchError`] if searching TMDB fails.
+async fn multi_search(
+    client: &TMDBClient,
+    pool: &PgPool,
+    params: &MultiSearchParams,
+) -> Result<FrontendSearchResults, ApiFetchError> {
+    validate_search_query(&params.query)?;
+    let include_adult = params.include_adult.unwrap_or(false);
+    let page = validate_page(params.page.unwrap_or(1))?;
+
+    let mut collections = SearchCollectionsRequest::new()
+        .query(params.query.clone())
+        .include_adult(include_adult)
+        .page(page);
+
+    if let Some(language) = &params.language {
+        validate_language("language", language)?;
+        collections = collections.language(language.clone());
+    }
+
+    let multi = async {
+        match params.year {
+            Some(year) => search_movies_in_year_and_people(client, params, page, year).await,
+            None => {
+                let mut multi = SearchMultiRequest::new()
+                    .query(params.query.clone())
+                    .include_adult(include_adult)
+                    .page(page);
+
+                if let Some(language) = &params.language {
+                    multi = multi.language(language.clone());
+                }
+
+                multi.fetch_results(client).await
+            }
+        }
+    };
+
+    let (multi, collections) = tokio::join!(multi, collections.fetch_results(client));
+    let (multi, collections) = (multi?, collections?);
+
+    let users: Vec<FrontendUserListing> = if page == 1 {
+        let pattern = format!(
+            "%{}%",
+            params
+                .query
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+
+        sqlx::query_as::<_, (uuid::Uuid, String)>(
+            "SELECT id, username FROM users
+            WHERE username ILIKE $1
+            ORDER BY LENGTH(username), username
+            LIMIT $2",
+        )
+        .bind(pattern)
+        .bind(MAX_USER_RESULTS)
+        .fetch_all(pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Database error: {}", e);
+            Vec::new()
+        })
+        .into_iter()
+        .map(|(id, username)| FrontendUserListing { id, username })
+        .collect()
+    } else {
+        Vec::new()
+    };
+
+    let total_results = multi.total_results + collections.total_results + users.len() as u64;
+    let total_pages = multi.total_pages.max(collections.total_pages);
+
+    let results: Vec<FrontendSearchResult> = multi
+        .results
+        .into_iter()
+        .filter_map(FrontendSearchResult::from_multi)
+        .chain(
+            collections
+                .results
+                .into_iter()
+                .map(|collection| FrontendSearchResult::Collection(collection.into())),
+        )
+        .chain(users.into_iter().map(FrontendSearchResult::User))
+        .collect();
+
+    Ok(resolve_genre_names(
+        client,
+        FrontendSearchResults::new(results, u64::from(page), total_pages, total_results),
+        params.language.clone(),
+    )
+    .await)
+}
+
+/// Searches for movies, people, collections and Cinescore users matching the given query string.
+pub async fn search_multi(
+    State(pool): State<PgPool>,
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    params: axum::extract::Query<MultiSearchParams>,
+) -> Result<Json<FrontendSearchResults>, ApiFetchError> {
+    let client = get_tmdb_client();
+    tracing::info!("Fetching multi search results");
+
+    let mut params = params.0;
+    let language = language.or_explicit(params.language.take());
+    params.language = language.0.clone();
+
+    let results = multi_search(&client, &pool, &params).await?;
+
+    Ok(Json(language.localise(
+        apply_parental_filter_to_search(&client, &auth_session, results).await?,
+    )))
+}
+
+/// Returns the first few multi search results for a partial query string, for autocompleting
+/// searches as they are typed. Results are cached briefly, so repeated keystrokes do not each
+/// hit TMDB.
+pub async fn search_autocomplete(
+    State(pool): State<PgPool>,
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    params: axum::extract::Query<MultiSearchParams>,
+) -> Result<Json<FrontendSearchResults>, ApiFetchError> {
+    let client = get_tmdb_client();
+
+    let mut params = params.0;
+    let language = language.or_explicit(params.language.take());
+    params.language = language.0.clone();
+    validate_search_query(&params.query)?;
+
+    let cache_key = (
+        params.query.trim().to_lowercase(),
+        params.language.clone(),
+        params.include_adult.unwrap_or(false),
+        params.year,
+    );
+
+    let results = match AUTOCOMPLETE_CACHE.get(&cache_key) {
+        Some(results) => results,
+        None => {
+            params.query = cache_key.0.clone();
+            params.page = None;
+
+            let mut results = multi_search(&client, &pool, &params).await?;
+            results.truncate(AUTOCOMPLETE_LIMIT);
+
+            AUTOCOMPLETE_CACHE.insert(cache_key, results.clone());
+            results
+        }
+    };
+
+    Ok(Json(language.localise(
+        apply_parental_filter_to_search(&client, &auth_session, results).await?,
+    )))
+}
+
+/// Searches for keywords matching the given query string, for autocompleting keywords.
+pub async fn search_keywords(
+    params: axum::extract::Query<SearchParams>,
+) -> Result<Json<FrontendKeywordList>, ApiFetchError> {
+    let client = get_tmdb_client();
+
+    Ok(Json(
+        SearchKeywordsRequest::new()
+            .query(params.query.clone())
+            .fetch(&client)
+            .await?,
+    ))
+}
+
+/// Searches for people matching the given query string.
+pub async fn search_people(
+    language: PreferredLanguage,
+    params: axum::extract::Query<SearchParams>,
+) -> Result<Json<FrontendPeopleList>, ApiFetchError> {
+    let client = get_tmdb_client();
+
+    let people = language
+        .apply(SearchPeopleRequest::new())
+        .query(params.query.clone())
+        .fetch(&client)
+        .await?;
+
+    Ok(Json(language.localise(people)))
+}
@@ -0,0 +1,129 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{PoisonError, RwLock},
+    time::{Duration, Instant},
+};
+
+/// A small thread-safe, in-memory cache where every entry expires after a fixed time-to-live.
+///
+/// This is intended for TMDB data that rarely changes (such as the genre list), so that it can be
+/// shared between requests without hitting the API every time. The cache holds at most a fixed
+/// number of entries. Expired entries are only pruned once it is full, and if it is still full
+/// after that, the oldest tenth of the entries are evicted at once, so inserts stay cheap.
+#[derive(Debug)]
+pub struct TtlCache<K, V> {
+    /// How long an entry stays valid after being inserted
+    ttl: Duration,
+    /// The most entries the cache holds
+    max_entries: usize,
+    /// The cached entries, alongside the instant they were inserted at
+    entries: RwLock<HashMap<K, (Instant, V)>>,
+}
+
+impl<K, V> TtlCache<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    /// Creates a new, empty cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - How long each entry should be considered valid for.
+    /// * `max_entries` - The most entries the cache holds.
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries: max_entries.max(1),
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns a clone of the cached value for `key`, if one exists and has not expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let entries = self.entries.read().unwrap_or_else(PoisonError::into_inner);
+
+        entries
+            .get(key)
+            .filter(|(inserted_at, _)| inserted_at.elapsed() < self.ttl)
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Inserts a value into the cache, replacing any existing entry for `key`. If the cache is
+    /// full, expired entries are pruned first, then the oldest entries if it is still full.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to store the value under.
+    /// * `value` - The value to cache.
+    pub fn insert(&self, key: K, value: V) {
+        let mut entries = self.entries.write().unwrap_or_else(PoisonError::into_inner);
+
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            entries.retain(|_, (inserted_at, _)| inserted_at.elapsed() < self.ttl);
+        }
+
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            let mut inserted: Vec<Instant> = entries
+                .values()
+                .map(|(inserted_at, _)| *inserted_at)
+                .collect();
+            inserted.sort_unstable();
+
+            // evicting a tenth at once means this only runs every so often while the cache is full
+            let cutoff = inserted[(self.max_entries / 10).min(inserted.len() - 1)];
+            entries.retain(|_, (inserted_at, _)| *inserted_at > cutoff);
+        }
+
+        entries.insert(key, (Instant::now(), value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_the_oldest_entries_when_full() {
+        let cache = TtlCache::new(Duration::from_secs(60), 20);
+        for key in 0..20 {
+            cache.insert(key, key);
+        }
+
+        cache.insert(20, 20);
+
+        // the oldest tenth, and any entries inserted at the same instant, are evicted
+        assert!(cache.get(&0).is_none());
+        assert!(cache.get(&1).is_none());
+        assert_eq!(cache.get(&20), Some(20));
+        assert!(cache.entries.read().unwrap().len() <= 20);
+    }
+
+    #[test]
+    fn replaces_entries_without_evicting() {
+        let cache = TtlCache::new(Duration::from_secs(60), 2);
+        cache.insert(0, 0);
+        cache.insert(1, 1);
+
+        cache.insert(1, 2);
+
+        assert_eq!(cache.get(&0), Some(0));
+        assert_eq!(cache.get(&1), Some(2));
+    }
+
+    #[test]
+    fn prunes_expired_entries_before_evicting() {
+        let cache = TtlCache::new(Duration::ZERO, 2);
+        cache.insert(0, 0);
+        cache.insert(1, 1);
+
+        cache.insert(2, 2);
+
+        assert_eq!(cache.entries.read().unwrap().len(), 1);
+    }
+}
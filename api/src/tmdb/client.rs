@@ -7,12 +7,13 @@ use reqwest::{
 };
 use serde::de::DeserializeOwned;
 
-/// Base URL for image resources from TMDB.
-pub const IMAGE_BASE_URL: &str = "https://image.tmdb.org/t/p/original/";
-
 /// Base URL for the TMDB API.
 pub const API_BASE_URL: &str = "https://api.themoviedb.org/3";
 
+/// The most requests a batch of concurrent lookups (e.g., the details of every movie on a page)
+/// makes to TMDB at once, so large batches do not run into TMDB's rate limits.
+pub const MAX_CONCURRENT_REQUESTS: usize = 8;
+
 /// Represents various errors that can be encountered while querying the TMDB API
 #[derive(Debug, thiserror::Error)]
 pub enum ApiFetchError {
@@ -29,6 +30,15 @@ pub enum ApiFetchError {
         /// The name of the request that was expecting the param
         request_name: &'static str,
     },
+    /// An error that was triggered due to a query parameter supplied by the user failing
+    /// validation before the request was sent to TMDB
+    #[error("Invalid query parameter `{param}`: {reason}")]
+    InvalidQueryParam {
+        /// The query param which is invalid
+        param: &'static str,
+        /// Why the query param was rejected
+        reason: String,
+    },
     /// An error that occured during the deserialization of the JSON returned from the TMDB API
     #[error("Deserialization error at {path}: {source}")]
     Deserialization {
@@ -43,6 +53,11 @@ pub enum ApiFetchError {
 impl IntoResponse for ApiFetchError {
     /// Converts an [`ApiFetchError`] into an [`axum::response::Response`]
     fn into_response(self) -> axum::response::Response {
+        if let Self::InvalidQueryParam { .. } = self {
+            tracing::debug!("{}", self);
+            return (axum::http::StatusCode::BAD_REQUEST, self.to_string()).into_response();
+        }
+
         tracing::error!("{}", self);
 
         if let Self::Request(e) = self {
@@ -89,6 +104,21 @@ impl TMDBClient {
         }
     }
 
+    /// Sends requests to another base URL instead of TMDB's, so tests never reach TMDB.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL to send requests to, without a trailing slash.
+    ///
+    /// # Returns
+    ///
+    /// The `TMDBClient` with its base URL replaced.
+    #[cfg(test)]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
     /// Makes an asynchronous GET request to the TMDB API.
     ///
     /// # Arguments
@@ -1,6 +1,9 @@
 //! This module provides an interface for interacting with the TMDB API.
 //! It includes the API client, data models, and query structures for making requests.
 
+/// In-memory caching for TMDB data that rarely changes.
+pub mod cache;
+
 /// The TMDB API client for handling requests and authentication.
 pub mod client;
 
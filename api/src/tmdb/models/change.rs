@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+/// Represents a movie or person whose details have changed on TMDB.
+#[derive(Debug, Deserialize)]
+pub struct ChangedItem {
+    /// Unique identifier for the movie or person.
+    pub id: u64,
+}
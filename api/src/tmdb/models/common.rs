@@ -5,7 +5,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 /// Represents a genre
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Genre {
     /// Unique identifier for the genre.
     pub id: u64,
@@ -13,6 +13,13 @@ pub struct Genre {
     pub name: String,
 }
 
+/// Represents the list of official genres returned by TMDB.
+#[derive(Debug, Deserialize)]
+pub struct GenreList {
+    /// All genres known to TMDB.
+    pub genres: Vec<Genre>,
+}
+
 /// Represents a production company involved in a movie.
 #[derive(Debug, Deserialize)]
 pub struct ProductionCompany {
@@ -47,7 +54,7 @@ pub struct Language {
 }
 
 /// The person's gender represented as a number:
-#[derive(Debug, Serialize_repr, Deserialize_repr)]
+#[derive(Debug, Clone, Copy, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
 pub enum Gender {
     /// 0: Not specified
@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+use serde::Deserialize;
+
+/// Represents the company that owns another company.
+#[derive(Debug, Deserialize)]
+pub struct ParentCompany {
+    /// Unique identifier for the company.
+    pub id: u64,
+    /// Path to the company's logo.
+    pub logo_path: Option<String>,
+    /// Name of the company.
+    pub name: String,
+}
+
+/// Represents detailed information about a production company.
+#[derive(Debug, Deserialize)]
+pub struct CompanyDetails {
+    /// Unique identifier for the company.
+    pub id: u64,
+    /// Description of the company.
+    pub description: String,
+    /// Where the company is headquartered.
+    pub headquarters: String,
+    /// Homepage URL of the company.
+    pub homepage: String,
+    /// Path to the company's logo.
+    pub logo_path: Option<String>,
+    /// Name of the company.
+    pub name: String,
+    /// Country of origin.
+    pub origin_country: String,
+    /// The company that owns this company, if any.
+    pub parent_company: Option<ParentCompany>,
+}
@@ -0,0 +1,41 @@
+#![allow(dead_code)]
+use serde::Deserialize;
+
+/// Represents TMDB's API configuration.
+#[derive(Debug, Deserialize)]
+pub struct Configuration {
+    /// How to build image URLs.
+    pub images: ImageConfiguration,
+}
+
+/// Represents how image URLs are built. An image URL is the base URL, followed by a size, then
+/// the image's path (e.g., `https://image.tmdb.org/t/p/w342/abc.jpg`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageConfiguration {
+    /// The base URL of every image, over HTTPS.
+    pub secure_base_url: String,
+    /// The sizes backdrop images are available in (e.g., `w780`).
+    pub backdrop_sizes: Vec<String>,
+    /// The sizes logo images are available in.
+    pub logo_sizes: Vec<String>,
+    /// The sizes poster images are available in.
+    pub poster_sizes: Vec<String>,
+    /// The sizes profile images are available in. These may be heights (e.g., `h632`) rather
+    /// than widths.
+    pub profile_sizes: Vec<String>,
+}
+
+impl Default for ImageConfiguration {
+    /// The configuration TMDB has documented for years, used until the real one is fetched.
+    fn default() -> Self {
+        let sizes = |sizes: &[&str]| sizes.iter().map(|size| (*size).to_owned()).collect();
+
+        Self {
+            secure_base_url: "https://image.tmdb.org/t/p/".to_owned(),
+            backdrop_sizes: sizes(&["w300", "w780", "w1280", "original"]),
+            logo_sizes: sizes(&["w45", "w92", "w154", "w185", "w300", "w500", "original"]),
+            poster_sizes: sizes(&["w92", "w154", "w185", "w342", "w500", "w780", "original"]),
+            profile_sizes: sizes(&["w45", "w185", "h632", "original"]),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+#![allow(dead_code)]
+use serde::Deserialize;
+
+/// Represents a single image of a movie or person, such as one of a movie's posters.
+#[allow(clippy::doc_markdown)]
+#[derive(Debug, Deserialize)]
+pub struct Image {
+    /// The width of the image divided by its height.
+    pub aspect_ratio: f64,
+    /// The path of the image.
+    pub file_path: String,
+    /// The height of the original image, in pixels.
+    pub height: u32,
+    /// The width of the original image, in pixels.
+    pub width: u32,
+    /// ISO 639-1 code of the language of any text in the image. `None` for images without text.
+    pub iso_639_1: Option<String>,
+    /// Average vote score.
+    pub vote_average: f64,
+    /// Total number of votes received.
+    pub vote_count: u64,
+}
+
+/// Represents every image of a movie.
+#[derive(Debug, Deserialize)]
+pub struct MovieImages {
+    /// The movie ID.
+    pub id: u64,
+    /// The movie's backdrops.
+    #[serde(default)]
+    pub backdrops: Vec<Image>,
+    /// The movie's logos.
+    #[serde(default)]
+    pub logos: Vec<Image>,
+    /// The movie's posters.
+    #[serde(default)]
+    pub posters: Vec<Image>,
+}
+
+/// Represents every image of a person.
+#[derive(Debug, Deserialize)]
+pub struct PersonImages {
+    /// The person ID.
+    pub id: u64,
+    /// The person's profile pictures.
+    #[serde(default)]
+    pub profiles: Vec<Image>,
+}
@@ -0,0 +1,18 @@
+#![allow(dead_code)]
+use serde::Deserialize;
+
+/// Represents a keyword used to tag movies.
+#[derive(Debug, Deserialize)]
+pub struct Keyword {
+    /// Unique identifier for the keyword.
+    pub id: u64,
+    /// The keyword itself.
+    pub name: String,
+}
+
+/// Represents the keywords a movie is tagged with.
+#[derive(Debug, Deserialize)]
+pub struct KeywordList {
+    /// The keywords.
+    pub keywords: Vec<Keyword>,
+}
@@ -1,14 +1,44 @@
 //! This module contains structures and utilities for interacting with the TMDB API.
 //! It is divided into submodules based on different types of API requests and responses.
 
+/// Data structures for movies and people whose details have changed on TMDB.
+pub mod change;
+
 /// Common types and utilities shared across multiple TMDB API models.
 pub mod common;
 
+/// Data structures for TMDB's API configuration, such as how to build image URLs.
+pub mod configuration;
+
+/// Data structures for production companies.
+pub mod company;
+
+/// Data structures for galleries of movie and person images.
+pub mod image;
+
+/// Data structures for keywords used to tag movies.
+pub mod keyword;
+
 /// Data structures and request models for fetching movie-related data from TMDB.
 pub mod movie;
 
 /// Data structures and request models for fetching person-related data from TMDB.
 pub mod person;
 
+/// Data structures for movie release dates and age certifications.
+pub mod release_dates;
+
+/// Data structures for reviews of movies written by TMDB users.
+pub mod review;
+
+/// Data structures for results that mix several kinds of media, such as multi search.
+pub mod search;
+
 /// Models related to social media links and external IDs for movies and people.
 pub mod socials;
+
+/// Data structures for videos, such as trailers, attached to movies.
+pub mod video;
+
+/// Data structures for where movies can be streamed, rented or bought.
+pub mod watch_providers;
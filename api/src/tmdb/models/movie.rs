@@ -3,8 +3,16 @@ use chrono::NaiveDate;
 use serde::Deserialize;
 
 use super::{
-    common::{deserialize_date, Genre, Language, ProductionCompany, ProductionCountry},
+    common::{
+        deserialize_date, Genre, Language, PaginatedSearchResult, ProductionCompany,
+        ProductionCountry,
+    },
+    keyword::KeywordList,
     person::SearchPerson,
+    release_dates::ReleaseDateResults,
+    socials::Socials,
+    video::VideoList,
+    watch_providers::WatchProviderResults,
 };
 
 /// Represents a collection of movies
@@ -15,9 +23,21 @@ pub struct Collection {
     /// The collection name
     pub name: String,
     /// The collection's poster image path
-    pub poster_path: String,
+    pub poster_path: Option<String>,
     /// The collection's backdrop image path
-    pub backdrop_path: String,
+    pub backdrop_path: Option<String>,
+}
+
+/// Represents a collection of movies along with every movie in it
+#[derive(Debug, Deserialize)]
+pub struct CollectionDetails {
+    /// Basic collection details
+    #[serde(flatten)]
+    pub base: Collection,
+    /// Brief overview of the collection
+    pub overview: Option<String>,
+    /// The movies in the collection
+    pub parts: Vec<SearchMovie>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -92,6 +112,21 @@ pub struct MovieDetails {
     pub tagline: String,
     /// Optional credits information if requested.
     pub credits: Option<MovieCredits>,
+    /// Optional recommended movies if requested.
+    pub recommendations: Option<PaginatedSearchResult<SearchMovie>>,
+    /// Optional similar movies if requested.
+    pub similar: Option<PaginatedSearchResult<SearchMovie>>,
+    /// Optional videos, such as trailers, if requested.
+    pub videos: Option<VideoList>,
+    /// Optional watch providers in every country, if requested.
+    #[serde(rename = "watch/providers")]
+    pub watch_providers: Option<WatchProviderResults>,
+    /// Optional release dates in every country, if requested.
+    pub release_dates: Option<ReleaseDateResults>,
+    /// Optional keywords the movie is tagged with, if requested.
+    pub keywords: Option<KeywordList>,
+    /// Optional external IDs, such as the movie's Wikidata ID, if requested.
+    pub external_ids: Option<Socials>,
 }
 
 /// Represents credits information for a movie, including cast and crew.
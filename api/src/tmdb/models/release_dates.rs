@@ -0,0 +1,187 @@
+#![allow(dead_code)]
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_repr::Deserialize_repr;
+
+/// The kind of release a release date is for, represented as a number:
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize_repr)]
+#[repr(u8)]
+pub enum ReleaseType {
+    /// 1: Premiere
+    Premiere = 1,
+    /// 2: Limited theatrical release
+    TheatricalLimited = 2,
+    /// 3: Theatrical release
+    Theatrical = 3,
+    /// 4: Digital release
+    Digital = 4,
+    /// 5: Physical release
+    Physical = 5,
+    /// 6: TV release
+    Tv = 6,
+}
+
+/// Represents a single release of a movie in a country.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseDate {
+    /// The age certification the movie was released with. Empty if there is none.
+    pub certification: String,
+    /// Content descriptors attached to the certification.
+    #[serde(default)]
+    pub descriptors: Vec<String>,
+    /// ISO 639-1 code of the release's language. Empty if it is not language-specific.
+    pub iso_639_1: String,
+    /// A note about the release (e.g., the festival it premiered at).
+    pub note: String,
+    /// When the movie was released.
+    pub release_date: DateTime<Utc>,
+    /// The kind of release.
+    #[serde(rename = "type")]
+    pub release_type: ReleaseType,
+}
+
+/// Represents every release of a movie in a single country.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CountryReleaseDates {
+    /// ISO 3166-1 code of the country.
+    pub iso_3166_1: String,
+    /// The movie's releases in the country.
+    pub release_dates: Vec<ReleaseDate>,
+}
+
+impl CountryReleaseDates {
+    /// The certification the movie is rated with in the country. Theatrical releases are
+    /// preferred, then limited theatrical releases, then premieres, then the remaining releases in
+    /// the order of [`ReleaseType`]. Ties go to the earliest release. Releases without a
+    /// certification are ignored.
+    pub fn certification(&self) -> Option<&str> {
+        let preference = |release: &ReleaseDate| match release.release_type {
+            ReleaseType::Theatrical => 0,
+            ReleaseType::TheatricalLimited => 1,
+            ReleaseType::Premiere => 2,
+            other => other as u8,
+        };
+
+        self.release_dates
+            .iter()
+            .filter(|release| !release.certification.is_empty())
+            .min_by_key(|release| (preference(release), release.release_date))
+            .map(|release| release.certification.as_str())
+    }
+}
+
+/// Represents every release of a movie in every country.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseDateResults {
+    /// The movie's releases, grouped by country.
+    pub results: Vec<CountryReleaseDates>,
+}
+
+/// Represents an age certification used in a country.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Certification {
+    /// The certification (e.g., PG-13).
+    pub certification: String,
+    /// What the certification means.
+    pub meaning: String,
+    /// The position of the certification from least to most restrictive.
+    pub order: u32,
+}
+
+/// Represents the age certifications used in every country.
+#[derive(Debug, Deserialize)]
+pub struct CertificationList {
+    /// Certifications keyed by ISO 3166-1 country code.
+    pub certifications: HashMap<String, Vec<Certification>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a release of a kind, on a date in RFC 3339 format.
+    fn release(release_type: ReleaseType, certification: &str, date: &str) -> ReleaseDate {
+        ReleaseDate {
+            certification: certification.to_owned(),
+            descriptors: Vec::new(),
+            iso_639_1: String::new(),
+            note: String::new(),
+            release_date: date.parse().unwrap(),
+            release_type,
+        }
+    }
+
+    /// Finds the certification of a movie released in a country with these releases.
+    fn certification(release_dates: Vec<ReleaseDate>) -> Option<String> {
+        CountryReleaseDates {
+            iso_3166_1: "GB".to_owned(),
+            release_dates,
+        }
+        .certification()
+        .map(str::to_owned)
+    }
+
+    #[test]
+    fn prefers_theatrical_releases() {
+        assert_eq!(
+            certification(vec![
+                release(ReleaseType::Premiere, "12A", "2024-01-01T00:00:00Z"),
+                release(ReleaseType::Digital, "15", "2024-02-01T00:00:00Z"),
+                release(ReleaseType::Theatrical, "PG", "2024-03-01T00:00:00Z"),
+            ])
+            .as_deref(),
+            Some("PG")
+        );
+    }
+
+    #[test]
+    fn prefers_limited_releases_over_earlier_premieres() {
+        assert_eq!(
+            certification(vec![
+                release(ReleaseType::Premiere, "12A", "2024-01-01T00:00:00Z"),
+                release(ReleaseType::TheatricalLimited, "15", "2024-02-01T00:00:00Z"),
+            ])
+            .as_deref(),
+            Some("15")
+        );
+    }
+
+    #[test]
+    fn prefers_premieres_over_other_releases() {
+        assert_eq!(
+            certification(vec![
+                release(ReleaseType::Digital, "15", "2024-01-01T00:00:00Z"),
+                release(ReleaseType::Premiere, "12A", "2024-02-01T00:00:00Z"),
+            ])
+            .as_deref(),
+            Some("12A")
+        );
+    }
+
+    #[test]
+    fn prefers_earliest_release_of_a_kind() {
+        assert_eq!(
+            certification(vec![
+                release(ReleaseType::Theatrical, "15", "2024-02-01T00:00:00Z"),
+                release(ReleaseType::Theatrical, "12A", "2024-01-01T00:00:00Z"),
+            ])
+            .as_deref(),
+            Some("12A")
+        );
+    }
+
+    #[test]
+    fn ignores_releases_without_a_certification() {
+        assert_eq!(
+            certification(vec![
+                release(ReleaseType::Theatrical, "", "2024-01-01T00:00:00Z"),
+                release(ReleaseType::Physical, "18", "2024-02-01T00:00:00Z"),
+            ])
+            .as_deref(),
+            Some("18")
+        );
+        assert_eq!(certification(Vec::new()), None);
+    }
+}
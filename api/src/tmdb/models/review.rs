@@ -0,0 +1,35 @@
+#![allow(dead_code)]
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Represents a review of a movie written by a TMDB user.
+#[derive(Debug, Deserialize)]
+pub struct Review {
+    /// Unique identifier for the review.
+    pub id: String,
+    /// The username of the review's author.
+    pub author: String,
+    /// Details of the review's author.
+    pub author_details: ReviewAuthor,
+    /// The text of the review, in Markdown.
+    pub content: String,
+    /// When the review was written.
+    pub created_at: DateTime<Utc>,
+    /// When the review was last edited.
+    pub updated_at: DateTime<Utc>,
+    /// URL of the review on TMDB.
+    pub url: String,
+}
+
+/// Represents the author of a review.
+#[derive(Debug, Deserialize)]
+pub struct ReviewAuthor {
+    /// The author's display name, which may be empty.
+    pub name: String,
+    /// The author's username.
+    pub username: String,
+    /// Path to the author's avatar. Older avatars are a full Gravatar URL with a leading `/`.
+    pub avatar_path: Option<String>,
+    /// The rating the author gave the movie, out of 10.
+    pub rating: Option<f64>,
+}
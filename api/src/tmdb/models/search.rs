@@ -0,0 +1,18 @@
+#![allow(dead_code)]
+use serde::Deserialize;
+
+use super::{movie::SearchMovie, person::SearchPerson};
+
+/// Represents a single result from a multi search, tagged by the kind of media it is.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "media_type", rename_all = "lowercase")]
+pub enum MultiSearchResult {
+    /// A movie
+    Movie(SearchMovie),
+    /// A person
+    Person(SearchPerson),
+    /// Any other kind of media, such as a TV show. Cinescore only covers movies, so these are
+    /// ignored.
+    #[serde(other)]
+    Other,
+}
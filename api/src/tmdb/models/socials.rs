@@ -1,7 +1,8 @@
 #![allow(dead_code)]
 use serde::Deserialize;
 
-/// Represents a collection of external social media and database IDs associated with a person.
+/// Represents a collection of external social media and database IDs associated with a person
+/// or movie. Movies only have some of them.
 #[allow(clippy::doc_markdown)]
 #[derive(Debug, Deserialize)]
 pub struct Socials {
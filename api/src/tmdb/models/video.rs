@@ -0,0 +1,37 @@
+#![allow(dead_code)]
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Represents a video attached to a movie, such as a trailer or featurette.
+#[allow(clippy::doc_markdown)]
+#[derive(Debug, Deserialize)]
+pub struct Video {
+    /// Unique identifier for the video.
+    pub id: String,
+    /// ISO 639-1 code of the video's language.
+    pub iso_639_1: String,
+    /// ISO 3166-1 code of the country the video was made for.
+    pub iso_3166_1: String,
+    /// Name of the video.
+    pub name: String,
+    /// The video's key on the site hosting it (e.g., a YouTube video ID).
+    pub key: String,
+    /// The site hosting the video (e.g., YouTube, Vimeo).
+    pub site: String,
+    /// The vertical resolution of the video.
+    pub size: u64,
+    /// The type of video (e.g., Trailer, Teaser, Clip, Featurette).
+    #[serde(rename = "type")]
+    pub video_type: String,
+    /// Whether the video was published by the studio behind the movie.
+    pub official: bool,
+    /// When the video was published.
+    pub published_at: DateTime<Utc>,
+}
+
+/// Represents the list of videos attached to a movie.
+#[derive(Debug, Deserialize)]
+pub struct VideoList {
+    /// The videos attached to the movie.
+    pub results: Vec<Video>,
+}
@@ -0,0 +1,53 @@
+#![allow(dead_code)]
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// Represents a watch provider, such as a streaming service or digital store.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchProvider {
+    /// Path to the provider's logo.
+    pub logo_path: Option<String>,
+    /// Unique identifier for the provider.
+    pub provider_id: u64,
+    /// Name of the provider.
+    pub provider_name: String,
+    /// The order TMDB suggests displaying the provider in.
+    pub display_priority: u64,
+}
+
+/// Represents where a movie can be watched in a single country, grouped by how it is offered.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchProviderAvailability {
+    /// A link to TMDB's page listing all the ways to watch the movie.
+    pub link: String,
+    /// Providers offering the movie as part of a subscription.
+    #[serde(default)]
+    pub flatrate: Vec<WatchProvider>,
+    /// Providers offering the movie to rent.
+    #[serde(default)]
+    pub rent: Vec<WatchProvider>,
+    /// Providers offering the movie to buy.
+    #[serde(default)]
+    pub buy: Vec<WatchProvider>,
+    /// Providers offering the movie for free.
+    #[serde(default)]
+    pub free: Vec<WatchProvider>,
+    /// Providers offering the movie for free with ads.
+    #[serde(default)]
+    pub ads: Vec<WatchProvider>,
+}
+
+/// Represents where a movie can be watched in every country it is available in.
+#[derive(Debug, Deserialize)]
+pub struct WatchProviderResults {
+    /// Availability keyed by ISO 3166-1 country code.
+    pub results: HashMap<String, WatchProviderAvailability>,
+}
+
+/// Represents the list of watch providers available in a region.
+#[derive(Debug, Deserialize)]
+pub struct WatchProviderList {
+    /// The watch providers.
+    pub results: Vec<WatchProvider>,
+}
@@ -0,0 +1,261 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::{
+    frontend_models::movies::FrontendMovieList,
+    generate_request_struct,
+    tmdb::{
+        cache::TtlCache,
+        client::{ApiFetchError, TMDBClient, MAX_CONCURRENT_REQUESTS},
+        models::release_dates::{Certification, CertificationList, ReleaseDateResults},
+    },
+};
+
+use super::traits::CertificationLteQueryParam;
+
+/// How long the certification list and movie release dates are cached for.
+const CERTIFICATION_CACHE_TTL: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// The most movies whose release dates are cached at once.
+const RELEASE_DATES_CACHE_MAX_ENTRIES: usize = 10_000;
+
+/// Cache of the age certifications used in every country. There is only ever a single entry.
+static CERTIFICATION_CACHE: LazyLock<TtlCache<(), HashMap<String, Vec<Certification>>>> =
+    LazyLock::new(|| TtlCache::new(CERTIFICATION_CACHE_TTL, 1));
+
+/// Cache of movie release dates, keyed by movie ID.
+static RELEASE_DATES_CACHE: LazyLock<TtlCache<u64, ReleaseDateResults>> =
+    LazyLock::new(|| TtlCache::new(CERTIFICATION_CACHE_TTL, RELEASE_DATES_CACHE_MAX_ENTRIES));
+
+generate_request_struct!(
+    MovieCertificationsRequest,
+    "Request struct for fetching the age certifications used for movies in every country."
+);
+
+generate_request_struct!(
+    ReleaseDatesRequest,
+    "Request struct for fetching the release dates of a movie in every country."
+);
+
+impl MovieCertificationsRequest {
+    /// Fetches the age certifications used in every country, keyed by ISO 3166-1 country code.
+    /// The cached list is returned if one is available.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if the response cannot be parsed.
+    pub async fn fetch_certification_map(
+        self,
+        client: &TMDBClient,
+    ) -> Result<HashMap<String, Vec<Certification>>, ApiFetchError> {
+        if let Some(certifications) = CERTIFICATION_CACHE.get(&()) {
+            tracing::debug!("Using cached movie certifications");
+            return Ok(certifications);
+        }
+
+        tracing::debug!("Fetching movie certifications from TMDB API");
+
+        let response = client
+            .get::<CertificationList>("certification/movie/list", self.params)
+            .await?;
+
+        CERTIFICATION_CACHE.insert((), response.certifications.clone());
+
+        Ok(response.certifications)
+    }
+}
+
+impl ReleaseDatesRequest {
+    /// Fetches the release dates of a movie in every country, returning the cached release dates
+    /// if they are available.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    /// * `id` - The ID of the movie to fetch release dates for.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if the response cannot be parsed.
+    pub async fn fetch_release_dates(
+        self,
+        client: &TMDBClient,
+        id: u64,
+    ) -> Result<ReleaseDateResults, ApiFetchError> {
+        if let Some(release_dates) = RELEASE_DATES_CACHE.get(&id) {
+            return Ok(release_dates);
+        }
+
+        tracing::debug!("Fetching release dates for movie ID {}", id);
+
+        let response = client
+            .get::<ReleaseDateResults>(&format!("movie/{}/release_dates", id), self.params)
+            .await?;
+
+        RELEASE_DATES_CACHE.insert(id, response.clone());
+
+        Ok(response)
+    }
+}
+
+/// Hides movies rated above an age certification in a country. Movies without a certification in
+/// the country are hidden as well, since there is no way to tell whether they are suitable.
+#[derive(Debug)]
+pub struct ParentalFilter {
+    /// The ISO 3166-1 code of the country the certifications belong to.
+    country: String,
+    /// The most restrictive certification that is allowed.
+    max_certification: String,
+    /// The order of every certification used in the country.
+    orders: HashMap<String, u32>,
+    /// The order of the most restrictive certification that is allowed.
+    max_order: u32,
+}
+
+impl ParentalFilter {
+    /// Creates a parental filter for a country. Returns `None` if the certification is not used
+    /// in the country.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to fetch the certification list.
+    /// * `country` - The ISO 3166-1 code of the country the certification belongs to.
+    /// * `max_certification` - The most restrictive certification to allow.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the certification list cannot be fetched.
+    pub async fn new(
+        client: &TMDBClient,
+        country: String,
+        max_certification: String,
+    ) -> Result<Option<Self>, ApiFetchError> {
+        let certifications = MovieCertificationsRequest::new()
+            .fetch_certification_map(client)
+            .await?;
+
+        let orders: HashMap<String, u32> = certifications
+            .get(&country)
+            .into_iter()
+            .flatten()
+            .map(|certification| (certification.certification.clone(), certification.order))
+            .collect();
+
+        Ok(orders
+            .get(&max_certification)
+            .copied()
+            .map(|max_order| Self {
+                country,
+                max_certification,
+                orders,
+                max_order,
+            }))
+    }
+
+    /// Checks whether a movie with the given certification is allowed through the filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `certification` - The movie's certification in the filter's country, if it has one.
+    fn allows(&self, certification: Option<&str>) -> bool {
+        certification
+            .and_then(|certification| self.orders.get(certification))
+            .is_some_and(|order| *order <= self.max_order)
+    }
+
+    /// The ISO 3166-1 code of the country the filter's certifications belong to.
+    pub fn country(&self) -> &str {
+        &self.country
+    }
+
+    /// Restricts a discover request so TMDB only returns movies the filter allows. This sets the
+    /// request's certification country to the filter's, so requests filtering certifications in
+    /// another country should be rejected first.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to restrict.
+    pub fn restrict<R: CertificationLteQueryParam>(&self, request: R) -> R {
+        request.certification_lte(&self.max_certification, &self.country)
+    }
+
+    /// Removes every movie the filter does not allow from a list. The release dates of each movie
+    /// are fetched concurrently, and movies whose release dates cannot be fetched are removed.
+    ///
+    /// Only the movies on the list's page are checked, so the list's totals become upper bounds
+    /// and the list is marked as such. Where the list comes from TMDB's discover endpoint,
+    /// [`ParentalFilter::restrict`] the request instead, which keeps the totals exact.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to fetch release dates.
+    /// * `list` - The list of movies to filter.
+    pub async fn apply(
+        &self,
+        client: &TMDBClient,
+        mut list: FrontendMovieList,
+    ) -> FrontendMovieList {
+        let allowed = self.allowed_movies(client, list.movie_ids()).await;
+        list.retain_movies(|movie_id| allowed.contains(&movie_id));
+        list
+    }
+
+    /// Finds which of the given movies the filter allows. The release dates of each movie are
+    /// fetched concurrently, making at most [`MAX_CONCURRENT_REQUESTS`] requests at once, and
+    /// movies whose release dates cannot be fetched are not allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to fetch release dates.
+    /// * `movie_ids` - The IDs of the movies to check.
+    pub async fn allowed_movies(&self, client: &TMDBClient, movie_ids: Vec<u64>) -> HashSet<u64> {
+        let mut tasks = JoinSet::new();
+        let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS));
+
+        for movie_id in movie_ids {
+            let client = client.clone();
+            let permits = permits.clone();
+            tasks.spawn(async move {
+                // the semaphore is never closed, so acquiring always succeeds
+                let _permit = permits.acquire().await;
+                let release_dates = ReleaseDatesRequest::new()
+                    .fetch_release_dates(&client, movie_id)
+                    .await;
+                (movie_id, release_dates)
+            });
+        }
+
+        let mut allowed = HashSet::new();
+
+        while let Some(result) = tasks.join_next().await {
+            match result {
+                Ok((movie_id, Ok(release_dates))) => {
+                    let certification = release_dates
+                        .results
+                        .iter()
+                        .find(|release_dates| release_dates.iso_3166_1 == self.country)
+                        .and_then(|release_dates| release_dates.certification());
+
+                    if self.allows(certification) {
+                        allowed.insert(movie_id);
+                    }
+                }
+                Ok((movie_id, Err(e))) => {
+                    tracing::warn!("Hiding movie {} from parental filter: {}", movie_id, e);
+                }
+                Err(e) => tracing::error!("Parental filter task failed: {}", e),
+            }
+        }
+
+        allowed
+    }
+}
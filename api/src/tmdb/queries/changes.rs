@@ -0,0 +1,67 @@
+use crate::{
+    generate_request_struct,
+    tmdb::{
+        client::{ApiFetchError, TMDBClient},
+        models::{change::ChangedItem, common::PaginatedSearchResult},
+    },
+};
+
+use super::traits::PageQueryParam;
+
+generate_request_struct!(
+    MovieChangesRequest,
+    "Request struct for fetching the movies whose details have changed in the last day."
+);
+
+generate_request_struct!(
+    PersonChangesRequest,
+    "Request struct for fetching the people whose details have changed in the last day."
+);
+
+impl MovieChangesRequest {
+    /// Fetches a page of the movies whose details have changed on TMDB in the last day.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if deserialization fails.
+    pub async fn fetch_changes(
+        self,
+        client: &TMDBClient,
+    ) -> Result<PaginatedSearchResult<ChangedItem>, ApiFetchError> {
+        tracing::debug!("Fetching movie changes from TMDB API");
+
+        client
+            .get::<PaginatedSearchResult<ChangedItem>>("movie/changes", self.params)
+            .await
+    }
+}
+
+impl PersonChangesRequest {
+    /// Fetches a page of the people whose details have changed on TMDB in the last day.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if deserialization fails.
+    pub async fn fetch_changes(
+        self,
+        client: &TMDBClient,
+    ) -> Result<PaginatedSearchResult<ChangedItem>, ApiFetchError> {
+        tracing::debug!("Fetching person changes from TMDB API");
+
+        client
+            .get::<PaginatedSearchResult<ChangedItem>>("person/changes", self.params)
+            .await
+    }
+}
+
+impl PageQueryParam for MovieChangesRequest {}
+
+impl PageQueryParam for PersonChangesRequest {}
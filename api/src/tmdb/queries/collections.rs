@@ -0,0 +1,51 @@
+use crate::{
+    frontend_models::collections::FrontendCollection,
+    generate_request_struct,
+    tmdb::{
+        client::{ApiFetchError, TMDBClient},
+        models::movie::CollectionDetails,
+    },
+};
+
+use super::{
+    genres::resolve_genre_names,
+    traits::{IdQuery, LanguageQueryParam},
+};
+
+generate_request_struct!(
+    CollectionDetailsRequest,
+    "Request struct for fetching a collection of movies, such as a franchise."
+);
+
+impl IdQuery<FrontendCollection> for CollectionDetailsRequest {
+    /// Asynchronously fetches a collection and every movie in it from the TMDB API.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    /// * `id` - The ID of the collection to fetch.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `FrontendCollection` on success or a [`ApiFetchError`] on failure.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if the response cannot be parsed.
+    async fn fetch(
+        self,
+        client: &TMDBClient,
+        id: u64,
+    ) -> Result<FrontendCollection, ApiFetchError> {
+        tracing::debug!("Fetching collection details for collection ID {}", id);
+
+        let language = self.params.get("language").cloned();
+        let response = client
+            .get::<CollectionDetails>(&format!("collection/{}", id), self.params)
+            .await?;
+
+        Ok(resolve_genre_names(client, FrontendCollection::from(response), language).await)
+    }
+}
+
+impl LanguageQueryParam for CollectionDetailsRequest {}
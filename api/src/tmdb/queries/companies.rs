@@ -0,0 +1,45 @@
+use crate::{
+    frontend_models::companies::FrontendCompanyDetails,
+    generate_request_struct,
+    tmdb::{
+        client::{ApiFetchError, TMDBClient},
+        models::company::CompanyDetails,
+    },
+};
+
+use super::traits::IdQuery;
+
+generate_request_struct!(
+    CompanyDetailsRequest,
+    "Request struct for fetching production company details."
+);
+
+impl IdQuery<FrontendCompanyDetails> for CompanyDetailsRequest {
+    /// Asynchronously fetches the details of a production company from the TMDB API.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    /// * `id` - The ID of the company to fetch details for.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `FrontendCompanyDetails` on success or a [`ApiFetchError`] on failure.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if the response cannot be parsed.
+    async fn fetch(
+        self,
+        client: &TMDBClient,
+        id: u64,
+    ) -> Result<FrontendCompanyDetails, ApiFetchError> {
+        tracing::debug!("Fetching company details for company ID {}", id);
+
+        let response = client
+            .get::<CompanyDetails>(&format!("company/{}", id), self.params)
+            .await?;
+
+        Ok(FrontendCompanyDetails::from(response))
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    sync::{Arc, LazyLock, PoisonError, RwLock},
+    time::Duration,
+};
+
+use crate::{
+    generate_request_struct,
+    tmdb::{
+        client::{ApiFetchError, TMDBClient},
+        models::configuration::{Configuration, ImageConfiguration},
+    },
+};
+
+/// How often the image configuration is refreshed. TMDB rarely changes it.
+const IMAGE_CONFIGURATION_REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// The current image configuration. This starts out as [`ImageConfiguration::default`] so image
+/// URLs can always be built, and is replaced once the real configuration has been fetched.
+static IMAGE_CONFIGURATION: LazyLock<RwLock<Arc<ImageConfiguration>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(ImageConfiguration::default())));
+
+generate_request_struct!(
+    ConfigurationRequest,
+    "Request struct for fetching TMDB's API configuration."
+);
+
+impl ConfigurationRequest {
+    /// Fetches TMDB's API configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if the response cannot be parsed.
+    pub async fn fetch_configuration(
+        self,
+        client: &TMDBClient,
+    ) -> Result<Configuration, ApiFetchError> {
+        tracing::debug!("Fetching API configuration from TMDB API");
+
+        client
+            .get::<Configuration>("configuration", self.params)
+            .await
+    }
+}
+
+/// Returns the current image configuration.
+pub fn image_configuration() -> Arc<ImageConfiguration> {
+    IMAGE_CONFIGURATION
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Spawns a background task that fetches the image configuration immediately, then refreshes it
+/// every [`IMAGE_CONFIGURATION_REFRESH_INTERVAL`]. The previous configuration is kept if a
+/// refresh fails.
+///
+/// # Arguments
+///
+/// * `client` - The `TMDBClient` instance used to make API requests.
+pub fn spawn_image_configuration_job(client: TMDBClient) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(IMAGE_CONFIGURATION_REFRESH_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            match ConfigurationRequest::new()
+                .fetch_configuration(&client)
+                .await
+            {
+                Ok(configuration) => {
+                    tracing::info!("Refreshed TMDB image configuration");
+                    *IMAGE_CONFIGURATION
+                        .write()
+                        .unwrap_or_else(PoisonError::into_inner) = Arc::new(configuration.images);
+                }
+                Err(e) => tracing::warn!("Unable to refresh TMDB image configuration: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,130 @@
+use std::{collections::HashMap, sync::LazyLock, time::Duration};
+
+use crate::{
+    frontend_models::genres::{FrontendGenreList, ResolveGenres},
+    generate_request_struct,
+    tmdb::{
+        cache::TtlCache,
+        client::{ApiFetchError, TMDBClient},
+        models::common::{Genre, GenreList},
+    },
+};
+
+use super::traits::{LanguageQueryParam, Query};
+
+/// How long the genre list is cached for. TMDB only changes genres very rarely.
+const GENRE_CACHE_TTL: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// The most languages the genre list is cached in at once.
+const GENRE_CACHE_MAX_ENTRIES: usize = 100;
+
+/// Cache of the official movie genre list, keyed by the requested language.
+static GENRE_CACHE: LazyLock<TtlCache<String, Vec<Genre>>> =
+    LazyLock::new(|| TtlCache::new(GENRE_CACHE_TTL, GENRE_CACHE_MAX_ENTRIES));
+
+generate_request_struct!(
+    MovieGenresRequest,
+    "Request struct for fetching the list of official movie genres."
+);
+
+impl MovieGenresRequest {
+    /// Fetches the list of movie genres, returning the cached list if one is available.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if the response cannot be parsed.
+    async fn fetch_genres(self, client: &TMDBClient) -> Result<Vec<Genre>, ApiFetchError> {
+        let cache_key = self.params.get("language").cloned().unwrap_or_default();
+
+        if let Some(genres) = GENRE_CACHE.get(&cache_key) {
+            tracing::debug!("Using cached movie genres for language `{}`", cache_key);
+            return Ok(genres);
+        }
+
+        tracing::debug!("Fetching movie genres from TMDB API");
+
+        let response = client
+            .get::<GenreList>("genre/movie/list", self.params)
+            .await?;
+
+        GENRE_CACHE.insert(cache_key, response.genres.clone());
+
+        Ok(response.genres)
+    }
+
+    /// Fetches the list of movie genres as a map of genre ID to genre name.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if the response cannot be parsed.
+    pub async fn fetch_genre_map(
+        self,
+        client: &TMDBClient,
+    ) -> Result<HashMap<u64, String>, ApiFetchError> {
+        Ok(self
+            .fetch_genres(client)
+            .await?
+            .into_iter()
+            .map(|genre| (genre.id, genre.name))
+            .collect())
+    }
+}
+
+impl Query<FrontendGenreList> for MovieGenresRequest {
+    /// Asynchronously fetches the list of official movie genres from the TMDB API.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `FrontendGenreList` on success or a [`ApiFetchError`] on failure.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if the response cannot be parsed.
+    async fn fetch(self, client: &TMDBClient) -> Result<FrontendGenreList, ApiFetchError> {
+        let genres = self.fetch_genres(client).await?;
+
+        Ok(FrontendGenreList::from(GenreList { genres }))
+    }
+}
+
+impl LanguageQueryParam for MovieGenresRequest {}
+
+/// Resolves the genre IDs of every movie in a frontend model into genre names.
+///
+/// Failing to fetch the genre list is not fatal, the movies are simply returned without any
+/// genres attached.
+///
+/// # Arguments
+///
+/// * `client` - The `TMDBClient` instance used to fetch the genre list.
+/// * `list` - The frontend model to resolve genres for.
+/// * `language` - The language the genre names should be in, if any.
+pub async fn resolve_genre_names<T: ResolveGenres>(
+    client: &TMDBClient,
+    mut list: T,
+    language: Option<String>,
+) -> T {
+    let request = match language {
+        Some(language) => MovieGenresRequest::new().language(language),
+        None => MovieGenresRequest::new(),
+    };
+
+    match request.fetch_genre_map(client).await {
+        Ok(genres) => list.resolve_genres(&genres),
+        Err(e) => tracing::warn!("Unable to resolve movie genres: {}", e),
+    }
+
+    list
+}
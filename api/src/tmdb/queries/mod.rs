@@ -4,6 +4,24 @@
 /// TODO:Common utilities and types used in multiple API queries.
 pub mod traits;
 
+/// Request models for fetching movie release dates and age certifications from TMDB.
+pub mod certifications;
+
+/// Request models for fetching the movies and people whose details have changed on TMDB.
+pub mod changes;
+
+/// Request models for fetching collections of movies, such as franchises, from TMDB.
+pub mod collections;
+
+/// Request models for fetching TMDB's API configuration, and the image configuration it contains.
+pub mod configuration;
+
+/// Request models for fetching production company details from TMDB.
+pub mod companies;
+
+/// Request models for fetching the list of movie genres from TMDB.
+pub mod genres;
+
 /// Request models for fetching detailed movie information from TMDB.
 pub mod movie_details;
 
@@ -16,6 +34,12 @@ pub mod people_details;
 /// Request models for searching movies/people on TMDB.
 pub mod search;
 
+/// Request models for fetching everything that is trending on TMDB, movies and people alike.
+pub mod trending;
+
+/// Request models for fetching the watch providers available on TMDB.
+pub mod watch_providers;
+
 /// Macro for generating API request structures with predefined parameters.
 #[macro_export]
 macro_rules! generate_request_struct {
@@ -1,19 +1,64 @@
 use crate::{
-    frontend_models::movies::FrontendMovieDetails,
+    frontend_models::{
+        images::FrontendMovieImages, movies::FrontendMovieDetails, reviews::FrontendReviewList,
+    },
     generate_request_struct,
     tmdb::{
         client::{ApiFetchError, TMDBClient},
-        models::movie::MovieDetails,
+        models::{
+            common::PaginatedSearchResult, image::MovieImages, movie::MovieDetails, review::Review,
+        },
     },
 };
 
-use super::traits::{AppendToResponseQueryParam, IdQuery, LanguageQueryParam};
+use super::{
+    genres::resolve_genre_names,
+    traits::{
+        AppendToResponseQueryParam, IdQuery, IncludeImageLanguageQueryParam,
+        IncludeVideoLanguageQueryParam, LanguageQueryParam, PageQueryParam,
+    },
+};
 
 generate_request_struct!(
     MovieDetailsRequest,
     "Request struct for fetching movie details."
 );
 
+generate_request_struct!(
+    MovieImagesRequest,
+    "Request struct for fetching every image of a movie."
+);
+
+generate_request_struct!(
+    MovieReviewsRequest,
+    "Request struct for fetching reviews of a movie written by TMDB users."
+);
+
+impl MovieDetailsRequest {
+    /// Fetches the raw movie details from the TMDB API, without converting them into a frontend
+    /// model. This is useful for internal consumers that need fields the frontend does not.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    /// * `id` - The ID of the movie to fetch details for.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if deserialization fails.
+    pub async fn fetch_details(
+        self,
+        client: &TMDBClient,
+        id: u64,
+    ) -> Result<MovieDetails, ApiFetchError> {
+        tracing::debug!("Fetching movie details for movie ID {}", id);
+
+        client
+            .get::<MovieDetails>(&format!("movie/{}", id), self.params)
+            .await
+    }
+}
+
 impl IdQuery<FrontendMovieDetails> for MovieDetailsRequest {
     /// Asynchronously fetches the movie details from the TMDB API for the given movie ID.
     ///
@@ -34,15 +79,100 @@ impl IdQuery<FrontendMovieDetails> for MovieDetailsRequest {
         client: &TMDBClient,
         id: u64,
     ) -> Result<FrontendMovieDetails, ApiFetchError> {
-        tracing::debug!("Fetching movie details for movie ID {}", id);
+        let language = self.params.get("language").cloned();
+        let response = self.fetch_details(client, id).await?;
+
+        Ok(to_frontend_details(client, response, language).await)
+    }
+}
+
+/// Converts raw movie details into the frontend model, preferring trailers in the requested
+/// language and resolving the genres of any embedded related movies.
+///
+/// # Arguments
+///
+/// * `client` - The `TMDBClient` instance used to resolve genre names.
+/// * `response` - The raw movie details.
+/// * `language` - The language the details were fetched in, if any.
+pub async fn to_frontend_details(
+    client: &TMDBClient,
+    response: MovieDetails,
+    language: Option<String>,
+) -> FrontendMovieDetails {
+    let has_related = response.recommendations.is_some() || response.similar.is_some();
+    let mut details = FrontendMovieDetails::from(response);
+
+    if let Some(language) = &language {
+        details.prefer_trailer_language(language);
+    }
+
+    // only embedded related movies need their genres resolved
+    if has_related {
+        resolve_genre_names(client, details, language).await
+    } else {
+        details
+    }
+}
+
+impl MovieImagesRequest {
+    /// Fetches the raw images of a movie from the TMDB API, without converting them into a
+    /// frontend model.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    /// * `id` - The ID of the movie to fetch images for.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if deserialization fails.
+    pub async fn fetch_images(
+        self,
+        client: &TMDBClient,
+        id: u64,
+    ) -> Result<MovieImages, ApiFetchError> {
+        tracing::debug!("Fetching images for movie ID {}", id);
+
+        client
+            .get::<MovieImages>(&format!("movie/{}/images", id), self.params)
+            .await
+    }
+}
+
+impl IdQuery<FrontendMovieImages> for MovieImagesRequest {
+    async fn fetch(
+        self,
+        client: &TMDBClient,
+        id: u64,
+    ) -> Result<FrontendMovieImages, ApiFetchError> {
+        let response = self.fetch_images(client, id).await?;
+
+        Ok(FrontendMovieImages::from(response))
+    }
+}
+
+impl IdQuery<FrontendReviewList> for MovieReviewsRequest {
+    async fn fetch(
+        self,
+        client: &TMDBClient,
+        id: u64,
+    ) -> Result<FrontendReviewList, ApiFetchError> {
+        tracing::debug!("Fetching reviews for movie ID {}", id);
 
         let response = client
-            .get::<MovieDetails>(&format!("movie/{}", id), self.params)
+            .get::<PaginatedSearchResult<Review>>(&format!("movie/{}/reviews", id), self.params)
             .await?;
 
-        Ok(FrontendMovieDetails::from(response))
+        Ok(FrontendReviewList::from(response))
     }
 }
 
 impl AppendToResponseQueryParam for MovieDetailsRequest {}
+impl IncludeVideoLanguageQueryParam for MovieDetailsRequest {}
 impl LanguageQueryParam for MovieDetailsRequest {}
+
+impl IncludeImageLanguageQueryParam for MovieImagesRequest {}
+impl LanguageQueryParam for MovieImagesRequest {}
+
+impl LanguageQueryParam for MovieReviewsRequest {}
+impl PageQueryParam for MovieReviewsRequest {}
@@ -7,15 +7,23 @@ use crate::{
     },
 };
 
-use super::traits::{
-    IncludeAdultQueryParam, IncludeVideoQueryParam, LanguageQueryParam, PageQueryParam,
-    PrimaryReleaseDateQueryParam, PrimaryReleaseYearQueryParam, Query, RegionQueryParam,
-    SortByQueryParam, YearQueryParam,
+use super::{
+    genres::resolve_genre_names,
+    traits::{
+        CertificationLteQueryParam, CertificationQueryParam, IdQuery, IncludeAdultQueryParam,
+        IncludeVideoQueryParam, LanguageQueryParam, PageQueryParam, PrimaryReleaseDateQueryParam,
+        PrimaryReleaseYearQueryParam, Query, RegionQueryParam, ReleaseDateQueryParam,
+        RuntimeQueryParam, SortByQueryParam, TimeWindowParam, VoteAverageQueryParam,
+        VoteCountQueryParam, WatchMonetizationTypesQueryParam, WatchProvidersQueryParam,
+        WithCastQueryParam, WithCompaniesQueryParam, WithCrewQueryParam, WithGenresQueryParam,
+        WithKeywordsQueryParam, WithOriginalLanguageQueryParam, WithReleaseTypeQueryParam,
+        WithoutGenresQueryParam, YearQueryParam,
+    },
 };
 
 generate_request_struct!(
     MovieListTrendingRequest,
-    "Request struct for fetching today's trending movies."
+    "Request struct for fetching trending movies, today or this week."
 );
 
 generate_request_struct!(
@@ -23,11 +31,31 @@ generate_request_struct!(
     "Request struct for fetching movies that are now playing in theatures."
 );
 
+generate_request_struct!(
+    MovieListPopularRequest,
+    "Request struct for fetching the movies that are most popular on TMDB right now."
+);
+
+generate_request_struct!(
+    MovieListTopRatedRequest,
+    "Request struct for fetching the movies with the highest TMDB vote averages."
+);
+
 generate_request_struct!(
     DiscoverMoviesRequest,
     "Request struct for fetching movies with a set of filters."
 );
 
+generate_request_struct!(
+    MovieRecommendationsRequest,
+    "Request struct for fetching movies recommended by TMDB based on a movie."
+);
+
+generate_request_struct!(
+    SimilarMoviesRequest,
+    "Request struct for fetching movies similar to a movie."
+);
+
 impl Query<FrontendMovieList> for MovieListTrendingRequest {
     /// Asynchronously fetches the trending movies list from the TMDB API.
     ///
@@ -42,14 +70,19 @@ impl Query<FrontendMovieList> for MovieListTrendingRequest {
     /// # Errors
     ///
     /// This function will return a `reqwest::Error` if the request fails or if deserialization fails.
-    async fn fetch(self, client: &TMDBClient) -> Result<FrontendMovieList, ApiFetchError> {
-        tracing::debug!("Fetching daily trending movies from TMDB API");
+    async fn fetch(mut self, client: &TMDBClient) -> Result<FrontendMovieList, ApiFetchError> {
+        let time_window = self.take_time_window();
+        tracing::debug!("Fetching trending movies ({}) from TMDB API", time_window);
 
+        let language = self.params.get("language").cloned();
         let response = client
-            .get::<PaginatedSearchResult<SearchMovie>>("/trending/movie/day", self.params)
+            .get::<PaginatedSearchResult<SearchMovie>>(
+                &format!("trending/movie/{}", time_window),
+                self.params,
+            )
             .await?;
 
-        Ok(FrontendMovieList::from(response))
+        Ok(resolve_genre_names(client, FrontendMovieList::from(response), language).await)
     }
 }
 
@@ -70,11 +103,87 @@ impl Query<FrontendMovieList> for MovieListNowPlayingRequest {
     async fn fetch(self, client: &TMDBClient) -> Result<FrontendMovieList, ApiFetchError> {
         tracing::debug!("Fetching now playing movies from TMDB API");
 
+        let language = self.params.get("language").cloned();
         let response = client
             .get::<PaginatedSearchResult<SearchMovie>>("movie/now_playing", self.params)
             .await?;
 
-        Ok(FrontendMovieList::from(response))
+        Ok(resolve_genre_names(client, FrontendMovieList::from(response), language).await)
+    }
+}
+
+impl Query<FrontendMovieList> for MovieListPopularRequest {
+    /// Asynchronously fetches the popular movies list from the TMDB API.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `FrontendMovieList` on success or a `reqwest::Error` on failure.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a `reqwest::Error` if the request fails or if deserialization fails.
+    async fn fetch(self, client: &TMDBClient) -> Result<FrontendMovieList, ApiFetchError> {
+        tracing::debug!("Fetching popular movies from TMDB API");
+
+        let language = self.params.get("language").cloned();
+        let response = client
+            .get::<PaginatedSearchResult<SearchMovie>>("movie/popular", self.params)
+            .await?;
+
+        Ok(resolve_genre_names(client, FrontendMovieList::from(response), language).await)
+    }
+}
+
+impl Query<FrontendMovieList> for MovieListTopRatedRequest {
+    /// Asynchronously fetches the top rated movies list from the TMDB API.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `FrontendMovieList` on success or a `reqwest::Error` on failure.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a `reqwest::Error` if the request fails or if deserialization fails.
+    async fn fetch(self, client: &TMDBClient) -> Result<FrontendMovieList, ApiFetchError> {
+        tracing::debug!("Fetching top rated movies from TMDB API");
+
+        let language = self.params.get("language").cloned();
+        let response = client
+            .get::<PaginatedSearchResult<SearchMovie>>("movie/top_rated", self.params)
+            .await?;
+
+        Ok(resolve_genre_names(client, FrontendMovieList::from(response), language).await)
+    }
+}
+
+impl DiscoverMoviesRequest {
+    /// Fetches the raw discover results from the TMDB API, without converting them into a
+    /// frontend model.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if deserialization fails.
+    pub async fn fetch_results(
+        self,
+        client: &TMDBClient,
+    ) -> Result<PaginatedSearchResult<SearchMovie>, ApiFetchError> {
+        tracing::debug!("Fetching discover movies from TMDB API");
+
+        client
+            .get::<PaginatedSearchResult<SearchMovie>>("discover/movie", self.params)
+            .await
     }
 }
 
@@ -93,13 +202,70 @@ impl Query<FrontendMovieList> for DiscoverMoviesRequest {
     ///
     /// This function will return a `reqwest::Error` if the request fails or if deserialization fails.
     async fn fetch(self, client: &TMDBClient) -> Result<FrontendMovieList, ApiFetchError> {
-        tracing::debug!("Fetching discover movies from TMDB API");
+        let language = self.params.get("language").cloned();
+        let response = self.fetch_results(client).await?;
+
+        Ok(resolve_genre_names(client, FrontendMovieList::from(response), language).await)
+    }
+}
+
+impl IdQuery<FrontendMovieList> for MovieRecommendationsRequest {
+    /// Asynchronously fetches movies recommended based on the given movie ID from the TMDB API.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    /// * `id` - The ID of the movie to fetch recommendations for.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `FrontendMovieList` on success or a [`ApiFetchError`] on failure.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if deserialization fails.
+    async fn fetch(self, client: &TMDBClient, id: u64) -> Result<FrontendMovieList, ApiFetchError> {
+        tracing::debug!("Fetching recommendations for movie ID {}", id);
 
+        let language = self.params.get("language").cloned();
         let response = client
-            .get::<PaginatedSearchResult<SearchMovie>>("discover/movie", self.params)
+            .get::<PaginatedSearchResult<SearchMovie>>(
+                &format!("movie/{}/recommendations", id),
+                self.params,
+            )
+            .await?;
+
+        Ok(resolve_genre_names(client, FrontendMovieList::from(response), language).await)
+    }
+}
+
+impl IdQuery<FrontendMovieList> for SimilarMoviesRequest {
+    /// Asynchronously fetches movies similar to the given movie ID from the TMDB API.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    /// * `id` - The ID of the movie to fetch similar movies for.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `FrontendMovieList` on success or a [`ApiFetchError`] on failure.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if deserialization fails.
+    async fn fetch(self, client: &TMDBClient, id: u64) -> Result<FrontendMovieList, ApiFetchError> {
+        tracing::debug!("Fetching similar movies for movie ID {}", id);
+
+        let language = self.params.get("language").cloned();
+        let response = client
+            .get::<PaginatedSearchResult<SearchMovie>>(
+                &format!("movie/{}/similar", id),
+                self.params,
+            )
             .await?;
 
-        Ok(FrontendMovieList::from(response))
+        Ok(resolve_genre_names(client, FrontendMovieList::from(response), language).await)
     }
 }
 
@@ -107,9 +273,24 @@ impl LanguageQueryParam for MovieListNowPlayingRequest {}
 impl PageQueryParam for MovieListNowPlayingRequest {}
 impl RegionQueryParam for MovieListNowPlayingRequest {}
 
+impl LanguageQueryParam for MovieListPopularRequest {}
+impl PageQueryParam for MovieListPopularRequest {}
+impl RegionQueryParam for MovieListPopularRequest {}
+
+impl LanguageQueryParam for MovieListTopRatedRequest {}
+impl PageQueryParam for MovieListTopRatedRequest {}
+impl RegionQueryParam for MovieListTopRatedRequest {}
+
 impl LanguageQueryParam for MovieListTrendingRequest {}
+impl PageQueryParam for MovieListTrendingRequest {}
+impl TimeWindowParam for MovieListTrendingRequest {}
+
+impl LanguageQueryParam for MovieRecommendationsRequest {}
+impl PageQueryParam for MovieRecommendationsRequest {}
+
+impl LanguageQueryParam for SimilarMoviesRequest {}
+impl PageQueryParam for SimilarMoviesRequest {}
 
-// TODO: im missing most of these but dont require them yet
 impl IncludeAdultQueryParam for DiscoverMoviesRequest {}
 impl LanguageQueryParam for DiscoverMoviesRequest {}
 impl RegionQueryParam for DiscoverMoviesRequest {}
@@ -117,5 +298,21 @@ impl PageQueryParam for DiscoverMoviesRequest {}
 impl PrimaryReleaseYearQueryParam for DiscoverMoviesRequest {}
 impl YearQueryParam for DiscoverMoviesRequest {}
 impl PrimaryReleaseDateQueryParam for DiscoverMoviesRequest {}
+impl ReleaseDateQueryParam for DiscoverMoviesRequest {}
+impl WithReleaseTypeQueryParam for DiscoverMoviesRequest {}
 impl IncludeVideoQueryParam for DiscoverMoviesRequest {}
 impl SortByQueryParam for DiscoverMoviesRequest {}
+impl WithGenresQueryParam for DiscoverMoviesRequest {}
+impl WithoutGenresQueryParam for DiscoverMoviesRequest {}
+impl WithCastQueryParam for DiscoverMoviesRequest {}
+impl WithCrewQueryParam for DiscoverMoviesRequest {}
+impl WithCompaniesQueryParam for DiscoverMoviesRequest {}
+impl WithKeywordsQueryParam for DiscoverMoviesRequest {}
+impl VoteAverageQueryParam for DiscoverMoviesRequest {}
+impl VoteCountQueryParam for DiscoverMoviesRequest {}
+impl RuntimeQueryParam for DiscoverMoviesRequest {}
+impl WithOriginalLanguageQueryParam for DiscoverMoviesRequest {}
+impl WatchProvidersQueryParam for DiscoverMoviesRequest {}
+impl WatchMonetizationTypesQueryParam for DiscoverMoviesRequest {}
+impl CertificationQueryParam for DiscoverMoviesRequest {}
+impl CertificationLteQueryParam for DiscoverMoviesRequest {}
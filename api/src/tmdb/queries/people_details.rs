@@ -1,49 +1,102 @@
 use crate::{
-    frontend_models::people::{FrontendPeopleList, FrontendPersonDetails},
+    frontend_models::{
+        images::FrontendPersonImages,
+        people::{FrontendPeopleList, FrontendPersonDetails},
+    },
     generate_request_struct,
     tmdb::{
         client::{ApiFetchError, TMDBClient},
         models::{
             common::PaginatedSearchResult,
+            image::PersonImages,
             person::{PersonDetails, SearchPerson},
         },
     },
 };
 
-use super::traits::{AppendToResponseQueryParam, IdQuery, LanguageQueryParam, Query};
+use super::traits::{
+    AppendToResponseQueryParam, IdQuery, IncludeImageLanguageQueryParam, LanguageQueryParam,
+    PageQueryParam, Query, TimeWindowParam,
+};
 
 generate_request_struct!(
     PersonDetailsRequest,
     "Request struct for fetching person details."
 );
 
+generate_request_struct!(
+    PersonImagesRequest,
+    "Request struct for fetching every image of a person."
+);
+
 generate_request_struct!(
     TrendingPeopleRequest,
-    "Request struct for fetching trending people."
+    "Request struct for fetching trending people, today or this week."
 );
 
+impl PersonDetailsRequest {
+    /// Fetches the raw person details from the TMDB API, without converting them into a
+    /// frontend model.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    /// * `id` - The ID of the person to fetch details for.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if deserialization fails.
+    pub async fn fetch_details(
+        self,
+        client: &TMDBClient,
+        id: u64,
+    ) -> Result<PersonDetails, ApiFetchError> {
+        tracing::debug!("Fetching person details for person ID {}", id);
+
+        client
+            .get::<PersonDetails>(&format!("person/{}", id), self.params)
+            .await
+    }
+}
+
 impl IdQuery<FrontendPersonDetails> for PersonDetailsRequest {
     async fn fetch(
         self,
         client: &TMDBClient,
         id: u64,
     ) -> Result<FrontendPersonDetails, ApiFetchError> {
-        tracing::debug!("Fetching person details for person ID {}", id);
+        let response = self.fetch_details(client, id).await?;
+
+        Ok(FrontendPersonDetails::from(response))
+    }
+}
+
+impl IdQuery<FrontendPersonImages> for PersonImagesRequest {
+    async fn fetch(
+        self,
+        client: &TMDBClient,
+        id: u64,
+    ) -> Result<FrontendPersonImages, ApiFetchError> {
+        tracing::debug!("Fetching images for person ID {}", id);
 
         let response = client
-            .get::<PersonDetails>(&format!("person/{}", id), self.params)
+            .get::<PersonImages>(&format!("person/{}/images", id), self.params)
             .await?;
 
-        Ok(FrontendPersonDetails::from(response))
+        Ok(FrontendPersonImages::from(response))
     }
 }
 
 impl Query<FrontendPeopleList> for TrendingPeopleRequest {
-    async fn fetch(self, client: &TMDBClient) -> Result<FrontendPeopleList, ApiFetchError> {
-        tracing::debug!("Fetching daily trending people from TMDB API");
+    async fn fetch(mut self, client: &TMDBClient) -> Result<FrontendPeopleList, ApiFetchError> {
+        let time_window = self.take_time_window();
+        tracing::debug!("Fetching trending people ({}) from TMDB API", time_window);
 
         let response = client
-            .get::<PaginatedSearchResult<SearchPerson>>("/trending/person/day", self.params)
+            .get::<PaginatedSearchResult<SearchPerson>>(
+                &format!("trending/person/{}", time_window),
+                self.params,
+            )
             .await?;
 
         Ok(FrontendPeopleList::from(response))
@@ -53,4 +106,9 @@ impl Query<FrontendPeopleList> for TrendingPeopleRequest {
 impl AppendToResponseQueryParam for PersonDetailsRequest {}
 impl LanguageQueryParam for PersonDetailsRequest {}
 
+impl IncludeImageLanguageQueryParam for PersonImagesRequest {}
+impl LanguageQueryParam for PersonImagesRequest {}
+
 impl LanguageQueryParam for TrendingPeopleRequest {}
+impl PageQueryParam for TrendingPeopleRequest {}
+impl TimeWindowParam for TrendingPeopleRequest {}
@@ -1,15 +1,26 @@
 use crate::{
-    frontend_models::{movies::FrontendMovieList, people::FrontendPeopleList},
+    frontend_models::{
+        keywords::FrontendKeywordList, movies::FrontendMovieList, people::FrontendPeopleList,
+    },
     generate_request_struct,
     tmdb::{
-        client::ApiFetchError,
-        models::{common::PaginatedSearchResult, movie::SearchMovie, person::SearchPerson},
+        client::{ApiFetchError, TMDBClient},
+        models::{
+            common::PaginatedSearchResult,
+            keyword::Keyword,
+            movie::{Collection, SearchMovie},
+            person::SearchPerson,
+            search::MultiSearchResult,
+        },
     },
 };
 
-use super::traits::{
-    IncludeAdultQueryParam, LanguageQueryParam, PageQueryParam, PrimaryReleaseYearQueryParam,
-    Query, QueryQueryParam, RegionQueryParam, YearQueryParam,
+use super::{
+    genres::resolve_genre_names,
+    traits::{
+        IncludeAdultQueryParam, LanguageQueryParam, PageQueryParam, PrimaryReleaseYearQueryParam,
+        Query, QueryQueryParam, RegionQueryParam, YearQueryParam,
+    },
 };
 
 generate_request_struct!(
@@ -22,6 +33,153 @@ generate_request_struct!(
     "Request struct for searching for movies in the TMDB API"
 );
 
+generate_request_struct!(
+    SearchKeywordsRequest,
+    "Request struct for searching for keywords in the TMDB API"
+);
+
+generate_request_struct!(
+    SearchMultiRequest,
+    "Request struct for searching for movies and people at once in the TMDB API"
+);
+
+generate_request_struct!(
+    SearchCollectionsRequest,
+    "Request struct for searching for collections of movies in the TMDB API"
+);
+
+impl SearchMultiRequest {
+    /// Fetches the raw multi search results from the TMDB API, without converting them into a
+    /// frontend model, so they can be merged with other results.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the query parameter is missing, the request
+    /// fails or the response cannot be parsed.
+    pub async fn fetch_results(
+        self,
+        client: &TMDBClient,
+    ) -> Result<PaginatedSearchResult<MultiSearchResult>, ApiFetchError> {
+        // require query param to be set
+        match self.params.get("query") {
+            Some(v) => tracing::debug!("Fetching multi search results for \"{}\"", v),
+            None => {
+                return Err(ApiFetchError::MissingQueryParam {
+                    param: "query",
+                    request_name: "SearchMultiRequest",
+                });
+            }
+        }
+
+        client
+            .get::<PaginatedSearchResult<MultiSearchResult>>("search/multi", self.params)
+            .await
+    }
+}
+
+impl SearchMoviesRequest {
+    /// Fetches the raw movie search results from the TMDB API, without converting them into a
+    /// frontend model, so they can be merged with other results.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the query parameter is missing, the request
+    /// fails or the response cannot be parsed.
+    pub async fn fetch_results(
+        self,
+        client: &TMDBClient,
+    ) -> Result<PaginatedSearchResult<SearchMovie>, ApiFetchError> {
+        // require query param to be set
+        match self.params.get("query") {
+            Some(v) => tracing::debug!("Fetching movie search results for \"{}\"", v),
+            None => {
+                return Err(ApiFetchError::MissingQueryParam {
+                    param: "query",
+                    request_name: "SearchMoviesRequest",
+                });
+            }
+        }
+
+        client
+            .get::<PaginatedSearchResult<SearchMovie>>("search/movie", self.params)
+            .await
+    }
+}
+
+impl SearchPeopleRequest {
+    /// Fetches the raw people search results from the TMDB API, without converting them into a
+    /// frontend model, so they can be merged with other results.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the query parameter is missing, the request
+    /// fails or the response cannot be parsed.
+    pub async fn fetch_results(
+        self,
+        client: &TMDBClient,
+    ) -> Result<PaginatedSearchResult<SearchPerson>, ApiFetchError> {
+        // require query param to be set
+        match self.params.get("query") {
+            Some(v) => tracing::debug!("Fetching people search results for \"{}\"", v),
+            None => {
+                return Err(ApiFetchError::MissingQueryParam {
+                    param: "query",
+                    request_name: "SearchPersonRequest",
+                });
+            }
+        }
+
+        client
+            .get::<PaginatedSearchResult<SearchPerson>>("search/person", self.params)
+            .await
+    }
+}
+
+impl SearchCollectionsRequest {
+    /// Fetches the raw collection search results from the TMDB API, without converting them into
+    /// a frontend model, so they can be merged with other results.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the query parameter is missing, the request
+    /// fails or the response cannot be parsed.
+    pub async fn fetch_results(
+        self,
+        client: &TMDBClient,
+    ) -> Result<PaginatedSearchResult<Collection>, ApiFetchError> {
+        // require query param to be set
+        match self.params.get("query") {
+            Some(v) => tracing::debug!("Fetching collection search results for \"{}\"", v),
+            None => {
+                return Err(ApiFetchError::MissingQueryParam {
+                    param: "query",
+                    request_name: "SearchCollectionsRequest",
+                });
+            }
+        }
+
+        client
+            .get::<PaginatedSearchResult<Collection>>("search/collection", self.params)
+            .await
+    }
+}
+
 impl Query<FrontendPeopleList> for SearchPeopleRequest {
     async fn fetch(
         self,
@@ -62,11 +220,36 @@ impl Query<FrontendMovieList> for SearchMoviesRequest {
             }
         }
 
+        let language = self.params.get("language").cloned();
         let response = client
             .get::<PaginatedSearchResult<SearchMovie>>("search/movie", self.params)
             .await?;
 
-        Ok(FrontendMovieList::from(response))
+        Ok(resolve_genre_names(client, FrontendMovieList::from(response), language).await)
+    }
+}
+
+impl Query<FrontendKeywordList> for SearchKeywordsRequest {
+    async fn fetch(
+        self,
+        client: &crate::tmdb::client::TMDBClient,
+    ) -> Result<FrontendKeywordList, crate::tmdb::client::ApiFetchError> {
+        // require query param to be set
+        match self.params.get("query") {
+            Some(v) => tracing::debug!("Fetching keyword search results for \"{}\"", v),
+            None => {
+                return Err(ApiFetchError::MissingQueryParam {
+                    param: "query",
+                    request_name: "SearchKeywordsRequest",
+                });
+            }
+        }
+
+        let response = client
+            .get::<PaginatedSearchResult<Keyword>>("search/keyword", self.params)
+            .await?;
+
+        Ok(FrontendKeywordList::from(response))
     }
 }
 
@@ -82,3 +265,16 @@ impl PageQueryParam for SearchMoviesRequest {}
 impl PrimaryReleaseYearQueryParam for SearchMoviesRequest {}
 impl RegionQueryParam for SearchMoviesRequest {}
 impl YearQueryParam for SearchMoviesRequest {}
+
+impl QueryQueryParam for SearchKeywordsRequest {}
+impl PageQueryParam for SearchKeywordsRequest {}
+
+impl QueryQueryParam for SearchMultiRequest {}
+impl IncludeAdultQueryParam for SearchMultiRequest {}
+impl LanguageQueryParam for SearchMultiRequest {}
+impl PageQueryParam for SearchMultiRequest {}
+
+impl QueryQueryParam for SearchCollectionsRequest {}
+impl IncludeAdultQueryParam for SearchCollectionsRequest {}
+impl LanguageQueryParam for SearchCollectionsRequest {}
+impl PageQueryParam for SearchCollectionsRequest {}
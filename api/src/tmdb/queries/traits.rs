@@ -2,12 +2,16 @@
 use std::{
     collections::HashMap,
     fmt::{self, Display},
+    str::FromStr,
 };
 
 use chrono::NaiveDate;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::tmdb::client::{ApiFetchError, TMDBClient};
+use crate::tmdb::{
+    client::{ApiFetchError, TMDBClient},
+    models::release_dates::ReleaseType,
+};
 
 /// Defines a route that supports query parameters.
 ///
@@ -132,6 +136,66 @@ pub trait AppendToResponseQueryParam: HasParams {
     }
 }
 
+/// A trait for adding an `include_image_language` query parameter to an API request.
+///
+/// By default TMDB only returns images in the request's `language`, or without text if no
+/// language is set, so this is used to choose which languages are included.
+pub trait IncludeImageLanguageQueryParam: HasParams {
+    /// Sets the `include_image_language` query parameter for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `languages` - A comma separated list of ISO 639-1 language codes. `null` includes
+    ///   images without text.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn include_image_language<S: Into<String> + Display>(mut self, languages: S) -> Self
+    where
+        Self: Sized,
+    {
+        tracing::debug!(
+            "Inserting include_image_language `{}` into query parameters",
+            languages
+        );
+
+        self.params()
+            .insert("include_image_language", languages.to_string());
+        self
+    }
+}
+
+/// A trait for adding an `include_video_language` query parameter to an API request.
+///
+/// By default TMDB only returns videos in the request's `language`, so this is used to also
+/// include videos in other languages.
+pub trait IncludeVideoLanguageQueryParam: HasParams {
+    /// Sets the `include_video_language` query parameter for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `languages` - A comma separated list of ISO 639-1 language codes. `null` includes
+    ///   videos without a language.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn include_video_language<S: Into<String> + Display>(mut self, languages: S) -> Self
+    where
+        Self: Sized,
+    {
+        tracing::debug!(
+            "Inserting include_video_language `{}` into query parameters",
+            languages
+        );
+
+        self.params()
+            .insert("include_video_language", languages.to_string());
+        self
+    }
+}
+
 /// A trait for adding a `region` query parameter to an API request.
 ///
 /// This is useful for region-based filtering of results.
@@ -350,6 +414,564 @@ pub trait PrimaryReleaseDateQueryParam: HasParams {
     }
 }
 
+/// A trait for adding `release_date.gte` and `release_date.lte` query parameters to an API request.
+///
+/// Unlike [`PrimaryReleaseDateQueryParam`], these match a movie's release dates in the request's
+/// `region`, so they can be used to find local release dates.
+pub trait ReleaseDateQueryParam: HasParams {
+    /// Sets the `release_date.gte` and/or `release_date.lte` query parameters for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `gte` - An optional lower bound for the release date.
+    /// * `lte` - An optional upper bound for the release date.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn release_date_range(mut self, gte: Option<NaiveDate>, lte: Option<NaiveDate>) -> Self
+    where
+        Self: Sized,
+    {
+        if let Some(date) = gte {
+            tracing::debug!(
+                "Inserting release_date.gte `{}` into query parameters",
+                date
+            );
+            self.params().insert("release_date.gte", date.to_string());
+        }
+
+        if let Some(date) = lte {
+            tracing::debug!(
+                "Inserting release_date.lte `{}` into query parameters",
+                date
+            );
+            self.params().insert("release_date.lte", date.to_string());
+        }
+
+        self
+    }
+}
+
+/// A trait for adding a `with_release_type` query parameter to an API request.
+///
+/// This is used with [`ReleaseDateQueryParam`] to only match certain kinds of release, such as
+/// theatrical ones.
+pub trait WithReleaseTypeQueryParam: HasParams {
+    /// Sets the `with_release_type` query parameter for the request. A movie matches if it has a
+    /// release of any of the given types.
+    ///
+    /// # Arguments
+    ///
+    /// * `release_types` - The kinds of release to match.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn with_release_type(mut self, release_types: &[ReleaseType]) -> Self
+    where
+        Self: Sized,
+    {
+        let release_types = release_types
+            .iter()
+            .map(|release_type| (*release_type as u8).to_string())
+            .collect::<Vec<_>>()
+            .join("|");
+
+        tracing::debug!(
+            "Inserting with_release_type `{}` into query parameters",
+            release_types
+        );
+
+        self.params().insert("with_release_type", release_types);
+        self
+    }
+}
+
+/// Joins a list of IDs into the format TMDB expects for list filters. A comma means every ID must
+/// match (`AND`), and a pipe means any ID may match (`OR`).
+///
+/// # Arguments
+///
+/// * `ids` - The IDs to join.
+/// * `match_all` - Whether to join with `AND` rather than `OR` semantics.
+fn join_ids(ids: &[u64], match_all: bool) -> String {
+    let separator = if match_all { "," } else { "|" };
+
+    ids.iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// A trait for adding a `with_genres` query parameter to an API request.
+///
+/// This is used for filtering results to movies with specific genres.
+pub trait WithGenresQueryParam: HasParams {
+    /// Sets the `with_genres` query parameter for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The IDs of the genres to filter by.
+    /// * `match_all` - Whether movies must match every ID (`AND`) rather than any of them (`OR`).
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn with_genres(mut self, ids: &[u64], match_all: bool) -> Self
+    where
+        Self: Sized,
+    {
+        let ids = join_ids(ids, match_all);
+
+        tracing::debug!("Inserting with_genres `{}` into query parameters", ids);
+        self.params().insert("with_genres", ids);
+
+        self
+    }
+}
+
+/// A trait for adding a `without_genres` query parameter to an API request.
+///
+/// This is used for excluding movies with specific genres from the results.
+pub trait WithoutGenresQueryParam: HasParams {
+    /// Sets the `without_genres` query parameter for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The IDs of the genres to exclude.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn without_genres(mut self, ids: &[u64]) -> Self
+    where
+        Self: Sized,
+    {
+        let ids = join_ids(ids, true);
+
+        tracing::debug!("Inserting without_genres `{}` into query parameters", ids);
+        self.params().insert("without_genres", ids);
+
+        self
+    }
+}
+
+/// A trait for adding a `with_cast` query parameter to an API request.
+///
+/// This is used for filtering results to movies featuring specific cast members.
+pub trait WithCastQueryParam: HasParams {
+    /// Sets the `with_cast` query parameter for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The IDs of the people to filter by.
+    /// * `match_all` - Whether movies must match every ID (`AND`) rather than any of them (`OR`).
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn with_cast(mut self, ids: &[u64], match_all: bool) -> Self
+    where
+        Self: Sized,
+    {
+        let ids = join_ids(ids, match_all);
+
+        tracing::debug!("Inserting with_cast `{}` into query parameters", ids);
+        self.params().insert("with_cast", ids);
+
+        self
+    }
+}
+
+/// A trait for adding a `with_crew` query parameter to an API request.
+///
+/// This is used for filtering results to movies made by specific crew members.
+pub trait WithCrewQueryParam: HasParams {
+    /// Sets the `with_crew` query parameter for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The IDs of the people to filter by.
+    /// * `match_all` - Whether movies must match every ID (`AND`) rather than any of them (`OR`).
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn with_crew(mut self, ids: &[u64], match_all: bool) -> Self
+    where
+        Self: Sized,
+    {
+        let ids = join_ids(ids, match_all);
+
+        tracing::debug!("Inserting with_crew `{}` into query parameters", ids);
+        self.params().insert("with_crew", ids);
+
+        self
+    }
+}
+
+/// A trait for adding a `with_companies` query parameter to an API request.
+///
+/// This is used for filtering results to movies made by specific production companies.
+pub trait WithCompaniesQueryParam: HasParams {
+    /// Sets the `with_companies` query parameter for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The IDs of the production companies to filter by.
+    /// * `match_all` - Whether movies must match every ID (`AND`) rather than any of them (`OR`).
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn with_companies(mut self, ids: &[u64], match_all: bool) -> Self
+    where
+        Self: Sized,
+    {
+        let ids = join_ids(ids, match_all);
+
+        tracing::debug!("Inserting with_companies `{}` into query parameters", ids);
+        self.params().insert("with_companies", ids);
+
+        self
+    }
+}
+
+/// A trait for adding a `with_keywords` query parameter to an API request.
+///
+/// This is used for filtering results to movies tagged with specific keywords.
+pub trait WithKeywordsQueryParam: HasParams {
+    /// Sets the `with_keywords` query parameter for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The IDs of the keywords to filter by.
+    /// * `match_all` - Whether movies must match every ID (`AND`) rather than any of them (`OR`).
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn with_keywords(mut self, ids: &[u64], match_all: bool) -> Self
+    where
+        Self: Sized,
+    {
+        let ids = join_ids(ids, match_all);
+
+        tracing::debug!("Inserting with_keywords `{}` into query parameters", ids);
+        self.params().insert("with_keywords", ids);
+
+        self
+    }
+}
+
+/// A trait for adding `vote_average` query parameters to an API request.
+///
+/// This is used for filtering results by their average score.
+pub trait VoteAverageQueryParam: HasParams {
+    /// Sets the `vote_average.gte` and/or `vote_average.lte` query parameters for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `gte` - An optional lower bound for the average score.
+    /// * `lte` - An optional upper bound for the average score.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn vote_average_range(mut self, gte: Option<f32>, lte: Option<f32>) -> Self
+    where
+        Self: Sized,
+    {
+        if let Some(score) = gte {
+            tracing::debug!(
+                "Inserting vote_average.gte `{}` into query parameters",
+                score
+            );
+            self.params().insert("vote_average.gte", score.to_string());
+        }
+
+        if let Some(score) = lte {
+            tracing::debug!(
+                "Inserting vote_average.lte `{}` into query parameters",
+                score
+            );
+            self.params().insert("vote_average.lte", score.to_string());
+        }
+
+        self
+    }
+}
+
+/// A trait for adding a `vote_count.gte` query parameter to an API request.
+///
+/// This is used for filtering out results with too few votes for their score to be meaningful.
+pub trait VoteCountQueryParam: HasParams {
+    /// Sets the `vote_count.gte` query parameter for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `gte` - The minimum number of votes a result must have.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn vote_count_gte(mut self, gte: u32) -> Self
+    where
+        Self: Sized,
+    {
+        tracing::debug!("Inserting vote_count.gte `{}` into query parameters", gte);
+
+        self.params().insert("vote_count.gte", gte.to_string());
+        self
+    }
+}
+
+/// A trait for adding `with_runtime` query parameters to an API request.
+///
+/// This is used for filtering results by their runtime in minutes.
+pub trait RuntimeQueryParam: HasParams {
+    /// Sets the `with_runtime.gte` and/or `with_runtime.lte` query parameters for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `gte` - An optional lower bound for the runtime.
+    /// * `lte` - An optional upper bound for the runtime.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn runtime_range(mut self, gte: Option<u32>, lte: Option<u32>) -> Self
+    where
+        Self: Sized,
+    {
+        if let Some(runtime) = gte {
+            tracing::debug!(
+                "Inserting with_runtime.gte `{}` into query parameters",
+                runtime
+            );
+            self.params()
+                .insert("with_runtime.gte", runtime.to_string());
+        }
+
+        if let Some(runtime) = lte {
+            tracing::debug!(
+                "Inserting with_runtime.lte `{}` into query parameters",
+                runtime
+            );
+            self.params()
+                .insert("with_runtime.lte", runtime.to_string());
+        }
+
+        self
+    }
+}
+
+/// A trait for adding a `with_original_language` query parameter to an API request.
+///
+/// This is used for filtering results by the language they were originally made in.
+pub trait WithOriginalLanguageQueryParam: HasParams {
+    /// Sets the `with_original_language` query parameter for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - An ISO 639-1 language code to filter results by.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn with_original_language<S: Into<String> + Display>(mut self, language: S) -> Self
+    where
+        Self: Sized,
+    {
+        tracing::debug!(
+            "Inserting with_original_language `{}` into query parameters",
+            language
+        );
+
+        self.params()
+            .insert("with_original_language", language.to_string());
+        self
+    }
+}
+
+/// A trait for adding `with_watch_providers` and `watch_region` query parameters to an API
+/// request.
+///
+/// This is used for filtering results to movies available on specific streaming services.
+pub trait WatchProvidersQueryParam: HasParams {
+    /// Sets the `with_watch_providers` and `watch_region` query parameters for the request. TMDB
+    /// ignores watch providers unless a region is also provided, so both are set together.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The IDs of the watch providers to filter by.
+    /// * `match_all` - Whether movies must be on every provider (`AND`) rather than any of them (`OR`).
+    /// * `watch_region` - An ISO 3166-1 country code to check availability in.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn with_watch_providers<S: Into<String> + Display>(
+        mut self,
+        ids: &[u64],
+        match_all: bool,
+        watch_region: S,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        let ids = join_ids(ids, match_all);
+
+        tracing::debug!(
+            "Inserting with_watch_providers `{}` in watch_region `{}` into query parameters",
+            ids,
+            watch_region
+        );
+
+        self.params().insert("with_watch_providers", ids);
+        self.params()
+            .insert("watch_region", watch_region.to_string());
+        self
+    }
+}
+
+/// A trait for adding `certification` and `certification_country` query parameters to an API
+/// request.
+///
+/// This is used for filtering results by age certification.
+pub trait CertificationQueryParam: HasParams {
+    /// Sets the `certification` and `certification_country` query parameters for the request.
+    /// TMDB ignores certifications unless a country is also provided, so both are set together.
+    ///
+    /// # Arguments
+    ///
+    /// * `certification` - The certification to filter by (e.g., PG-13). Multiple certifications
+    ///   can be separated with `|`.
+    /// * `country` - An ISO 3166-1 country code the certification belongs to.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn certification<S: Into<String> + Display, C: Into<String> + Display>(
+        mut self,
+        certification: S,
+        country: C,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        tracing::debug!(
+            "Inserting certification `{}` in certification_country `{}` into query parameters",
+            certification,
+            country
+        );
+
+        self.params()
+            .insert("certification", certification.to_string());
+        self.params()
+            .insert("certification_country", country.to_string());
+        self
+    }
+}
+
+/// A trait for adding `certification.lte` and `certification_country` query parameters to an API
+/// request.
+///
+/// This is used for hiding movies above an age certification.
+pub trait CertificationLteQueryParam: HasParams {
+    /// Sets the `certification.lte` and `certification_country` query parameters for the request.
+    /// Movies without a certification in the country are excluded as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `certification` - The most restrictive certification to include (e.g., PG-13).
+    /// * `country` - An ISO 3166-1 country code the certification belongs to.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn certification_lte<S: Into<String> + Display, C: Into<String> + Display>(
+        mut self,
+        certification: S,
+        country: C,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        tracing::debug!(
+            "Inserting certification.lte `{}` in certification_country `{}` into query parameters",
+            certification,
+            country
+        );
+
+        self.params()
+            .insert("certification.lte", certification.to_string());
+        self.params()
+            .insert("certification_country", country.to_string());
+        self
+    }
+}
+
+/// A trait for adding a `watch_region` query parameter to an API request.
+///
+/// This is used for limiting watch provider lists to a single region.
+pub trait WatchRegionQueryParam: HasParams {
+    /// Sets the `watch_region` query parameter for the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `watch_region` - An ISO 3166-1 country code to list watch providers for.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn watch_region<S: Into<String> + Display>(mut self, watch_region: S) -> Self
+    where
+        Self: Sized,
+    {
+        tracing::debug!(
+            "Inserting watch_region `{}` into query parameters",
+            watch_region
+        );
+
+        self.params()
+            .insert("watch_region", watch_region.to_string());
+        self
+    }
+}
+
+/// A trait for adding a `with_watch_monetization_types` query parameter to an API request.
+///
+/// This is used alongside [`WatchProvidersQueryParam`] to only match some ways of watching a
+/// movie, such as subscriptions.
+pub trait WatchMonetizationTypesQueryParam: HasParams {
+    /// Sets the `with_watch_monetization_types` query parameter for the request. Movies offered
+    /// in any of the given ways are matched.
+    ///
+    /// # Arguments
+    ///
+    /// * `types` - The ways of watching to match (e.g., `flatrate`, `free`, `ads`, `rent`, `buy`).
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn with_watch_monetization_types(mut self, types: &[&str]) -> Self
+    where
+        Self: Sized,
+    {
+        let types = types.join("|");
+
+        tracing::debug!(
+            "Inserting with_watch_monetization_types `{}` into query parameters",
+            types
+        );
+
+        self.params().insert("with_watch_monetization_types", types);
+        self
+    }
+}
+
 /// An enum defining the things you can sort by in the TMDB API
 #[derive(Debug, Clone, Copy)]
 pub enum SortBy {
@@ -383,6 +1005,38 @@ pub enum SortBy {
     VoteCountDesc,
 }
 
+impl SortBy {
+    /// Every variant of [`SortBy`], used for parsing.
+    const ALL: [SortBy; 14] = [
+        SortBy::OriginalTitleAsc,
+        SortBy::OriginalTitleDesc,
+        SortBy::PopularityAsc,
+        SortBy::PopularityDesc,
+        SortBy::RevenueAsc,
+        SortBy::RevenueDesc,
+        SortBy::PrimaryReleaseDateAsc,
+        SortBy::PrimaryReleaseDateDesc,
+        SortBy::TitleAsc,
+        SortBy::TitleDesc,
+        SortBy::VoteAverageAsc,
+        SortBy::VoteAverageDesc,
+        SortBy::VoteCountAsc,
+        SortBy::VoteCountDesc,
+    ];
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    /// Parses a [`SortBy`] from the same representation TMDB uses (e.g., `popularity.desc`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|sort_by| sort_by.to_string() == s)
+            .ok_or_else(|| format!("`{}` is not a valid sort order", s))
+    }
+}
+
 impl Display for SortBy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let value = match self {
@@ -428,3 +1082,60 @@ pub trait SortByQueryParam: HasParams {
         self
     }
 }
+
+/// How far back TMDB looks when working out what is trending.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeWindow {
+    /// What is trending today
+    #[default]
+    Day,
+    /// What is trending this week
+    Week,
+}
+
+impl Display for TimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            TimeWindow::Day => "day",
+            TimeWindow::Week => "week",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// A trait for setting the time window of a trending request.
+///
+/// TMDB takes the time window as part of the path rather than the query string, so requests
+/// implementing this take it back out of their parameters before sending them.
+pub trait TimeWindowParam: HasParams {
+    /// Sets the time window for the request. Requests default to [`TimeWindow::Day`].
+    ///
+    /// # Arguments
+    ///
+    /// * `time_window` - How far back to look for trending items
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct implementing this trait with the updated query parameters.
+    fn time_window(mut self, time_window: TimeWindow) -> Self
+    where
+        Self: Sized,
+    {
+        tracing::debug!("Setting time window to `{}`", time_window);
+        self.params().insert("time_window", time_window.to_string());
+
+        self
+    }
+
+    /// Removes the time window from the request's parameters, returning it for use in the path.
+    ///
+    /// # Returns
+    ///
+    /// The time window that was set, or `day` if none was.
+    fn take_time_window(&mut self) -> String {
+        self.params()
+            .remove("time_window")
+            .unwrap_or_else(|| TimeWindow::default().to_string())
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    frontend_models::search::{FrontendSearchResult, FrontendSearchResults},
+    generate_request_struct,
+    tmdb::{
+        client::{ApiFetchError, TMDBClient},
+        models::{common::PaginatedSearchResult, search::MultiSearchResult},
+    },
+};
+
+use super::{
+    genres::resolve_genre_names,
+    traits::{LanguageQueryParam, PageQueryParam, Query, TimeWindowParam},
+};
+
+generate_request_struct!(
+    TrendingAllRequest,
+    "Request struct for fetching trending movies and people together, today or this week."
+);
+
+impl Query<FrontendSearchResults> for TrendingAllRequest {
+    /// Asynchronously fetches trending movies and people from the TMDB API, in a single list
+    /// ranked by how much each is trending. TV shows are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if deserialization fails.
+    async fn fetch(mut self, client: &TMDBClient) -> Result<FrontendSearchResults, ApiFetchError> {
+        let time_window = self.take_time_window();
+        tracing::debug!(
+            "Fetching everything trending ({}) from TMDB API",
+            time_window
+        );
+
+        let language = self.params.get("language").cloned();
+        let response = client
+            .get::<PaginatedSearchResult<MultiSearchResult>>(
+                &format!("trending/all/{}", time_window),
+                self.params,
+            )
+            .await?;
+
+        let results = response
+            .results
+            .into_iter()
+            .filter_map(FrontendSearchResult::from_multi)
+            .collect();
+
+        Ok(resolve_genre_names(
+            client,
+            FrontendSearchResults::new(
+                results,
+                response.page,
+                response.total_pages,
+                response.total_results,
+            ),
+            language,
+        )
+        .await)
+    }
+}
+
+impl LanguageQueryParam for TrendingAllRequest {}
+impl PageQueryParam for TrendingAllRequest {}
+impl TimeWindowParam for TrendingAllRequest {}
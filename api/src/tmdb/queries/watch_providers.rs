@@ -0,0 +1,43 @@
+use crate::{
+    frontend_models::watch_providers::FrontendWatchProviderList,
+    generate_request_struct,
+    tmdb::{
+        client::{ApiFetchError, TMDBClient},
+        models::watch_providers::WatchProviderList,
+    },
+};
+
+use super::traits::{LanguageQueryParam, Query, WatchRegionQueryParam};
+
+generate_request_struct!(
+    WatchProviderListRequest,
+    "Request struct for fetching the watch providers that offer movies."
+);
+
+impl Query<FrontendWatchProviderList> for WatchProviderListRequest {
+    /// Asynchronously fetches the list of movie watch providers from the TMDB API.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `TMDBClient` instance used to make the API request.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `FrontendWatchProviderList` on success or a [`ApiFetchError`] on failure.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`ApiFetchError`] if the request fails or if the response cannot be parsed.
+    async fn fetch(self, client: &TMDBClient) -> Result<FrontendWatchProviderList, ApiFetchError> {
+        tracing::debug!("Fetching movie watch providers from TMDB API");
+
+        let response = client
+            .get::<WatchProviderList>("watch/providers/movie", self.params)
+            .await?;
+
+        Ok(FrontendWatchProviderList::from(response))
+    }
+}
+
+impl LanguageQueryParam for WatchProviderListRequest {}
+impl WatchRegionQueryParam for WatchProviderListRequest {}
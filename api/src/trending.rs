@@ -0,0 +1,157 @@
+//! Movies trending among Cinescore's own users, as opposed to TMDB's trending lists.
+//!
+//! Every like, rating and watchlist add counts towards a movie's score, weighted by how strong a
+//! signal it is. Activity decays exponentially with age, so a movie that was popular a while ago
+//! falls down the list as newer movies pick up interest.
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_login::AuthSession;
+use serde::Deserialize;
+use sqlx::{postgres::PgArguments, prelude::FromRow, Arguments, PgPool};
+
+use crate::{
+    auth::Backend,
+    discover::{apply_parental_filter, get_tmdb_client},
+    frontend_models::movies::{FrontendMovieList, MovieListing},
+    language::PreferredLanguage,
+    metadata::load_movies,
+    pagination::{fetch_page, DatabasePage, PAGE_SIZE},
+    tmdb::queries::traits::TimeWindow,
+};
+
+/// How much a like counts towards a movie's score.
+const LIKE_WEIGHT: f64 = 3.0;
+
+/// How much a rating counts towards a movie's score. Changing a rating counts as rating it again.
+const RATING_WEIGHT: f64 = 2.0;
+
+/// How much adding a movie to a watchlist counts towards its score.
+const WATCHLIST_WEIGHT: f64 = 1.0;
+
+/// Every like, rating and watchlist add within the last `$4` hours, weighted by `$1`, `$2` and
+/// `$3` respectively, and scored by its weight halved every `$5` hours since it happened. Shared
+/// by the queries listing and counting trending movies.
+const RECENT_ACTIVITY: &str = "WITH events AS (
+    SELECT movie_id, created_at AS happened_at, $1::FLOAT8 AS weight
+    FROM movie_likes
+    WHERE created_at > NOW() - make_interval(hours => $4)
+    UNION ALL
+    SELECT movie_id, updated_at, $2::FLOAT8
+    FROM movie_ratings
+    WHERE updated_at > NOW() - make_interval(hours => $4)
+    UNION ALL
+    SELECT movie_id, created_at, $3::FLOAT8
+    FROM movie_watchlist
+    WHERE created_at > NOW() - make_interval(hours => $4)
+),
+activity AS (
+    SELECT movie_id,
+        weight * POWER(0.5::FLOAT8, EXTRACT(EPOCH FROM NOW() - happened_at)::FLOAT8 / 3600 / $5)
+            AS score
+    FROM events
+)";
+
+/// Parameters for community trending queries.
+#[derive(Deserialize)]
+pub struct CommunityTrendingParams {
+    /// Whether to list what is trending today or this week. Defaults to today.
+    #[serde(default)]
+    window: TimeWindow,
+    /// The page of results to return, starting from 1.
+    page: Option<u32>,
+}
+
+/// A trending movie, as returned by the database.
+#[derive(FromRow)]
+struct TrendingMovieRow {
+    /// The TMDB ID of the movie.
+    movie_id: i64,
+}
+
+/// Returns how quickly activity decays for a time window, as the number of hours it takes for
+/// activity to count half as much, and how many hours of activity are looked at in total.
+///
+/// # Arguments
+///
+/// * `window` - The time window being listed.
+fn decay(window: TimeWindow) -> (f64, i32) {
+    match window {
+        TimeWindow::Day => (12.0, 3 * 24),
+        TimeWindow::Week => (3.0 * 24.0, 14 * 24),
+    }
+}
+
+/// Fetches the movies trending among Cinescore's users, most trending first.
+pub async fn fetch_community_trending(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    State(pool): State<PgPool>,
+    params: Query<CommunityTrendingParams>,
+) -> impl IntoResponse {
+    let (half_life, lookback) = decay(params.window);
+
+    let list_query = format!(
+        "{}
+        SELECT movie_id
+        FROM activity
+        GROUP BY movie_id
+        ORDER BY SUM(score) DESC, movie_id",
+        RECENT_ACTIVITY
+    );
+    let count_query = format!(
+        "{} SELECT COUNT(DISTINCT movie_id) FROM activity",
+        RECENT_ACTIVITY
+    );
+    let binds = |arguments: &mut PgArguments| {
+        arguments.add(LIKE_WEIGHT)?;
+        arguments.add(RATING_WEIGHT)?;
+        arguments.add(WATCHLIST_WEIGHT)?;
+        arguments.add(lookback)?;
+        arguments.add(half_life)
+    };
+
+    let DatabasePage {
+        rows,
+        page,
+        total_results,
+    } = match fetch_page::<TrendingMovieRow, _>(
+        &pool,
+        &list_query,
+        &count_query,
+        binds,
+        params.page,
+    )
+    .await
+    {
+        Ok(page) => page,
+        Err(response) => return response,
+    };
+
+    tracing::info!(
+        "Fetching {} community trending movies ({})",
+        rows.len(),
+        params.window
+    );
+
+    let client = get_tmdb_client();
+    let movie_ids: Vec<u64> = rows.into_iter().map(|row| row.movie_id as u64).collect();
+
+    let movies: Vec<MovieListing> = match load_movies(&pool, &client, &movie_ids, &language).await {
+        Ok(movies) => movies.into_iter().map(MovieListing::from).collect(),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let list = FrontendMovieList::new(movies, page, PAGE_SIZE, total_results);
+
+    match apply_parental_filter(&client, &auth_session, list).await {
+        Ok(list) => Json(language.localise(list)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
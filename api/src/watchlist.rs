@@ -0,0 +1,155 @@
+//! Routes for viewing a user's watchlist, optionally limited to the movies they can watch on the
+//! streaming services they are subscribed to.
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use axum_login::AuthSession;
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    auth::Backend,
+    discover::{fetch_movie_details_batch, get_tmdb_client},
+    frontend_models::movies::{FrontendMovieList, MovieListing},
+    language::PreferredLanguage,
+    metadata::load_movies,
+    posters::load_preferred_posters,
+    preferences::{load_watch_services, WatchServices},
+    tmdb::models::movie::MovieDetails,
+};
+
+/// Builds the router for watchlist routes. These are nested under `/api/v1/me`.
+pub fn build_router(pool: PgPool) -> Router {
+    Router::new()
+        .route("/watchlist", get(fetch_watchlist))
+        .with_state(pool)
+}
+
+/// Parameters for watchlist queries.
+#[derive(Deserialize)]
+pub struct WatchlistParams {
+    /// Only include movies available on the user's streaming services in their region.
+    on_my_services: Option<bool>,
+}
+
+/// Checks whether a movie can be watched on any of a user's streaming services, either as part
+/// of a subscription or for free.
+///
+/// # Arguments
+///
+/// * `movie` - The movie's details, including its watch providers.
+/// * `services` - The user's region and streaming services.
+fn is_on_services(movie: &MovieDetails, services: &WatchServices) -> bool {
+    let Some(availability) = movie
+        .watch_providers
+        .as_ref()
+        .and_then(|providers| providers.results.get(&services.region))
+    else {
+        return false;
+    };
+
+    availability
+        .flatrate
+        .iter()
+        .chain(&availability.free)
+        .chain(&availability.ads)
+        .any(|provider| services.provider_ids.contains(&provider.provider_id))
+}
+
+/// Fetches the logged in user's watchlist, most recently added first. Movies are shown with the
+/// poster the user has chosen for them, if any.
+async fn fetch_watchlist(
+    auth_session: AuthSession<Backend>,
+    language: PreferredLanguage,
+    State(pool): State<PgPool>,
+    params: Query<WatchlistParams>,
+) -> impl IntoResponse {
+    let user = match auth_session.user {
+        Some(user) => user,
+        None => return StatusCode::UNAUTHORIZED.into_response(),
+    };
+
+    let services = if params.on_my_services.unwrap_or(false) {
+        match load_watch_services(&pool, &user).await {
+            Ok(Some(services)) => Some(services),
+            Ok(None) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    "A region and at least one watch provider must be saved to filter by your services",
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                tracing::error!("Database error: {}", e);
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        }
+    } else {
+        None
+    };
+
+    let movie_ids: Vec<i64> = match sqlx::query_scalar(
+        "SELECT movie_id FROM movie_watchlist WHERE user_id = $1 ORDER BY created_at DESC",
+    )
+    .bind(user.id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let mut posters = match load_preferred_posters(&pool, user.id, &movie_ids).await {
+        Ok(posters) => posters,
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    tracing::info!("Fetching watchlist of {} movies", movie_ids.len());
+
+    let client = get_tmdb_client();
+    let movie_ids: Vec<u64> = movie_ids.into_iter().map(|id| id as u64).collect();
+
+    // filtering by services needs watch providers, which are not kept in the movie cache
+    let movies: Vec<MovieListing> = match services {
+        Some(services) => {
+            fetch_movie_details_batch(&client, movie_ids, Some("watch/providers"), &language)
+                .await
+                .into_iter()
+                .filter(|movie| is_on_services(movie, &services))
+                .map(|mut movie| {
+                    if let Some(poster) = posters.remove(&movie.base.id) {
+                        movie.base.poster_path = Some(poster);
+                    }
+                    MovieListing::from(movie)
+                })
+                .collect()
+        }
+        None => match load_movies(&pool, &client, &movie_ids, &language).await {
+            Ok(movies) => movies
+                .into_iter()
+                .map(|mut movie| {
+                    if let Some(poster) = posters.remove(&(movie.movie_id as u64)) {
+                        movie.poster_path = Some(poster);
+                    }
+                    MovieListing::from(movie)
+                })
+                .collect(),
+            Err(e) => {
+                tracing::error!("Database error: {}", e);
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        },
+    };
+
+    Json(language.localise(FrontendMovieList::from(movies))).into_response()
+}
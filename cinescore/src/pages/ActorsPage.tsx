@@ -17,10 +17,12 @@ import type { Credits } from "../types";
 
 interface FrontendSocials {
 	imdb?: string;
+	wikidata?: string;
 	facebook?: string;
 	instagram?: string;
 	tiktok?: string;
 	twitter?: string;
+	youtube?: string;
 }
 
 interface FrontendPersonDetails {
@@ -123,7 +125,7 @@ const ActorPage: React.FC = () => {
 									<div className="mt-4 flex space-x-3">
 										{actor.socials?.facebook && (
 											<a
-												href={`https://facebook.com/${actor.socials.facebook.replace("@", "")}`}
+												href={actor.socials.facebook}
 												target="_blank"
 												rel="noopener noreferrer"
 												className="rounded-full bg-neutral-800 p-2 text-neutral-300 hover:bg-neutral-700 transition-colors"
@@ -134,7 +136,7 @@ const ActorPage: React.FC = () => {
 
 										{actor.socials?.instagram && (
 											<a
-												href={`https://instagram.com/${actor.socials.instagram.replace("@", "")}`}
+												href={actor.socials.instagram}
 												target="_blank"
 												rel="noopener noreferrer"
 												className="rounded-full bg-neutral-800 p-2 text-neutral-300 hover:bg-neutral-700 transition-colors"
@@ -144,7 +146,7 @@ const ActorPage: React.FC = () => {
 										)}
 										{actor.socials?.twitter && (
 											<a
-												href={`https://twitter.com/${actor.socials.twitter.replace("@", "")}`}
+												href={actor.socials.twitter}
 												target="_blank"
 												rel="noopener noreferrer"
 												className="rounded-full bg-neutral-800 p-2 text-neutral-300 hover:bg-neutral-700 transition-colors"
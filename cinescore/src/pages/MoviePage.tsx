@@ -111,9 +111,7 @@ const MoviePage: React.FC = () => {
 		);
 	}
 
-	const director = movie.credits?.crew.find(
-		(member) => member.department === "Directing",
-	);
+	const director = movie.credits?.keyRoles?.directors[0];
 
 	return (
 		<div className="flex min-h-screen flex-col bg-neutral-950 text-white">
@@ -28,7 +28,15 @@ export type Crew = {
 	id: number;
 };
 
+export type KeyRoles = {
+	directors: Array<Crew>;
+	writers: Array<Crew>;
+	composers: Array<Crew>;
+	cinematographers: Array<Crew>;
+};
+
 export type Credits = {
 	cast: Array<Cast>;
 	crew: Array<Crew>;
+	keyRoles?: KeyRoles;
 };